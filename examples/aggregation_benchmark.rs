@@ -0,0 +1,160 @@
+//! Compares the previous shared `Arc<Mutex<HashMap>>` aggregation against the
+//! per-chunk fold/reduce aggregation used by the transformations.
+//!
+//! Messages are generated in memory so that only the aggregation cost is measured.
+//!
+//! Usage: `cargo run --release --example aggregation_benchmark [num_messages] [num_chunks]`
+
+use TP0ProgramacionConcurrente::chat_message::ChatMessage;
+use TP0ProgramacionConcurrente::streaming_aggregators::StreamingAggregators;
+use rayon::prelude::*;
+use std::collections::HashMap;
+use std::env;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+const LANGUAGES: [&str; 8] = ["en", "es", "fr", "de", "ru", "ja", "pt", "ar"];
+const THREADS: [usize; 3] = [2, 4, 8];
+
+/// Aggregators shared between all threads, as used before the fold/reduce design.
+struct MutexAggregators {
+    language_channel_counts: Arc<Mutex<HashMap<String, HashMap<String, u64>>>>,
+    range_language_counts: Arc<Mutex<HashMap<String, HashMap<String, u64>>>>,
+    total_messages: Arc<Mutex<u64>>,
+}
+
+impl MutexAggregators {
+    fn new() -> Self {
+        Self {
+            language_channel_counts: Arc::new(Mutex::new(HashMap::new())),
+            range_language_counts: Arc::new(Mutex::new(HashMap::new())),
+            total_messages: Arc::new(Mutex::new(0)),
+        }
+    }
+
+    fn process_message(&self, message: ChatMessage) {
+        {
+            let mut lang_channel_counts = self.language_channel_counts.lock().unwrap();
+            let channel_counts = lang_channel_counts
+                .entry(message.language.clone())
+                .or_default();
+            *channel_counts
+                .entry(message.channel_name.clone())
+                .or_insert(0) += 1;
+        }
+
+        let range = match message.viewer_count {
+            0..=100 => "0-100",
+            101..=500 => "101-500",
+            501..=1000 => "501-1000",
+            _ => "1000+",
+        };
+
+        {
+            let mut range_lang_counts = self.range_language_counts.lock().unwrap();
+            let language_counts = range_lang_counts.entry(range.to_string()).or_default();
+            *language_counts.entry(message.language).or_insert(0) += 1;
+        }
+
+        {
+            let mut total = self.total_messages.lock().unwrap();
+            *total += 1;
+        }
+    }
+}
+
+/// Generates `num_messages` synthetic messages split into `num_chunks` chunks.
+fn generate_chunks(num_messages: usize, num_chunks: usize) -> Vec<Vec<ChatMessage>> {
+    let per_chunk = num_messages.div_ceil(num_chunks);
+    (0..num_chunks)
+        .map(|chunk| {
+            let first = chunk * per_chunk;
+            let last = (first + per_chunk).min(num_messages);
+            (first..last)
+                .map(|i| ChatMessage {
+                    channel_name: format!("channel{}", (i * 7919) % 3000),
+                    language: LANGUAGES[i % LANGUAGES.len()].to_string(),
+                    viewer_count: ((i * 31) % 5000) as u64,
+                })
+                .collect()
+        })
+        .collect()
+}
+
+fn run_mutex(pool: &rayon::ThreadPool, chunks: Vec<Vec<ChatMessage>>) -> (Duration, u64) {
+    let aggregators = MutexAggregators::new();
+    let start = Instant::now();
+    pool.install(|| {
+        chunks.into_par_iter().for_each(|chunk| {
+            for message in chunk {
+                aggregators.process_message(message);
+            }
+        });
+    });
+    let elapsed = start.elapsed();
+    let total = *aggregators.total_messages.lock().unwrap();
+    (elapsed, total)
+}
+
+fn run_fold_reduce(pool: &rayon::ThreadPool, chunks: Vec<Vec<ChatMessage>>) -> (Duration, u64) {
+    let start = Instant::now();
+    let aggregators = pool.install(|| {
+        chunks
+            .into_par_iter()
+            .map(|chunk| {
+                let mut partial = StreamingAggregators::new();
+                for message in chunk {
+                    partial.process_message(message);
+                }
+                partial
+            })
+            .reduce(StreamingAggregators::new, |mut merged, partial| {
+                merged.merge(partial);
+                merged
+            })
+    });
+    (start.elapsed(), aggregators.total_messages)
+}
+
+fn throughput(messages: u64, elapsed: Duration) -> f64 {
+    messages as f64 / elapsed.as_secs_f64() / 1_000_000.0
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let num_messages: usize = args.get(1).map_or(2_000_000, |v| {
+        v.parse()
+            .expect("Please provide a valid number of messages")
+    });
+    let num_chunks: usize = args.get(2).map_or(8, |v| {
+        v.parse().expect("Please provide a valid number of chunks")
+    });
+
+    println!("=== AGGREGATION BENCHMARK ===");
+    println!("Messages: {num_messages}, chunks: {num_chunks}");
+    println!(
+        "{:>8} | {:>16} | {:>16} | {:>8}",
+        "Threads", "Mutex (M msg/s)", "Fold (M msg/s)", "Speedup"
+    );
+
+    for threads in THREADS {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build thread pool");
+
+        let (mutex_elapsed, mutex_total) =
+            run_mutex(&pool, generate_chunks(num_messages, num_chunks));
+        let (fold_elapsed, fold_total) =
+            run_fold_reduce(&pool, generate_chunks(num_messages, num_chunks));
+        assert_eq!(mutex_total, fold_total);
+
+        println!(
+            "{:>8} | {:>16.2} | {:>16.2} | {:>7.2}x",
+            threads,
+            throughput(mutex_total, mutex_elapsed),
+            throughput(fold_total, fold_elapsed),
+            mutex_elapsed.as_secs_f64() / fold_elapsed.as_secs_f64()
+        );
+    }
+}
@@ -0,0 +1,137 @@
+//! Counts heap allocations per message for the owned `ChatMessage` path and the
+//! zero-copy `ChatMessageRef` paths (buffered and memory-mapped).
+//!
+//! A synthetic dataset is written to a temporary file and parsed as a single chunk
+//! on the current thread, aggregating every message into a `StreamingAggregators`.
+//!
+//! Usage: `cargo run --release --example allocation_benchmark [num_messages] [num_channels]`
+
+use TP0ProgramacionConcurrente::chunk_info::ChunkInfo;
+use TP0ProgramacionConcurrente::parse_options::ParseOptions;
+use TP0ProgramacionConcurrente::parser::{
+    map_file, parse_chunk_borrowed, parse_chunk_from_slice, parse_chunk_with_report,
+};
+use TP0ProgramacionConcurrente::streaming_aggregators::StreamingAggregators;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Instant;
+use tempfile::TempDir;
+
+const LANGUAGES: [&str; 8] = ["en", "es", "fr", "de", "ru", "ja", "pt", "ar"];
+
+/// Global allocator that counts every allocation.
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+/// Writes `num_messages` synthetic messages spread over `num_channels` channels.
+fn write_dataset(path: &Path, num_messages: usize, num_channels: usize) {
+    let mut writer = BufWriter::new(File::create(path).expect("Failed to create dataset"));
+    for i in 0..num_messages {
+        writeln!(
+            writer,
+            r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}}}"#,
+            (i * 7919) % num_channels,
+            LANGUAGES[i % LANGUAGES.len()],
+            (i * 31) % 5000
+        )
+        .unwrap();
+    }
+    writer.flush().unwrap();
+}
+
+/// Runs `f` and prints the allocations per message and the elapsed time.
+fn measure(name: &str, num_messages: usize, f: impl FnOnce() -> StreamingAggregators) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let start = Instant::now();
+    let aggregators = f();
+    let elapsed = start.elapsed();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    assert_eq!(aggregators.total_messages, num_messages as u64);
+
+    println!(
+        "{:>22} | {:>12} | {:>12.3} | {:>9.3}",
+        name,
+        allocations,
+        allocations as f64 / num_messages as f64,
+        elapsed.as_secs_f64()
+    );
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let num_messages: usize = args.get(1).map_or(1_000_000, |v| {
+        v.parse()
+            .expect("Please provide a valid number of messages")
+    });
+    let num_channels: usize = args.get(2).map_or(3000, |v| {
+        v.parse()
+            .expect("Please provide a valid number of channels")
+    });
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dataset = temp_dir.path().join("dataset.json");
+    write_dataset(&dataset, num_messages, num_channels);
+    let path = dataset.to_string_lossy().to_string();
+    let file_size = std::fs::metadata(&dataset).unwrap().len();
+    let chunk = ChunkInfo {
+        file_id: 0,
+        start: 0,
+        end: file_size,
+        size: file_size,
+    };
+    let options = ParseOptions::default();
+
+    println!("=== ALLOCATION BENCHMARK ===");
+    println!("Messages: {num_messages}, channels: {num_channels}");
+    println!(
+        "{:>22} | {:>12} | {:>12} | {:>9}",
+        "Path", "Allocations", "Per message", "Time (s)"
+    );
+
+    measure("owned (BufReader)", num_messages, || {
+        let mut aggregators = StreamingAggregators::new();
+        parse_chunk_with_report(&path, &chunk, options, |message| {
+            aggregators.process_message(message);
+        })
+        .unwrap();
+        aggregators
+    });
+
+    measure("borrowed (BufReader)", num_messages, || {
+        let mut aggregators = StreamingAggregators::new();
+        parse_chunk_borrowed(&path, &chunk, options, |message| {
+            aggregators.process_message_ref(message);
+        })
+        .unwrap();
+        aggregators
+    });
+
+    measure("borrowed (mmap)", num_messages, || {
+        let data = map_file(&path).unwrap();
+        let mut aggregators = StreamingAggregators::new();
+        parse_chunk_from_slice(&data, &chunk, options, |message| {
+            aggregators.process_message_ref(message);
+        })
+        .unwrap();
+        aggregators
+    });
+}
@@ -0,0 +1,143 @@
+//! Compares the buffered chunk reader (one `BufReader` per chunk) against the
+//! memory-mapped reader (one shared mapping, parsed straight from `&[u8]`).
+//!
+//! A synthetic dataset is written to a temporary file and every chunk is parsed
+//! without aggregation, so only the reading and deserialization cost is measured.
+//!
+//! Usage: `cargo run --release --example reader_benchmark [num_messages] [array|ndjson]`
+
+use TP0ProgramacionConcurrente::input_format::InputFormat;
+use TP0ProgramacionConcurrente::parse_options::ParseOptions;
+use TP0ProgramacionConcurrente::parser::{
+    analize_file_for_chunks_with_format, map_file, parse_chunk_from_slice, parse_chunk_with_report,
+};
+use rayon::prelude::*;
+use std::env;
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use tempfile::TempDir;
+
+const LANGUAGES: [&str; 8] = ["en", "es", "fr", "de", "ru", "ja", "pt", "ar"];
+const THREADS: [usize; 4] = [1, 2, 4, 8];
+
+/// Writes `num_messages` synthetic messages to `path` in the given layout.
+fn write_dataset(path: &Path, num_messages: usize, input_format: InputFormat) {
+    let mut writer = BufWriter::new(File::create(path).expect("Failed to create dataset"));
+    if input_format == InputFormat::JsonArray {
+        write!(writer, "[").unwrap();
+    }
+    for i in 0..num_messages {
+        if i > 0 && input_format == InputFormat::JsonArray {
+            write!(writer, ",").unwrap();
+        }
+        write!(
+            writer,
+            r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}, "text": "message number {i}"}}"#,
+            (i * 7919) % 3000,
+            LANGUAGES[i % LANGUAGES.len()],
+            (i * 31) % 5000
+        )
+        .unwrap();
+        writeln!(writer).unwrap();
+    }
+    if input_format == InputFormat::JsonArray {
+        write!(writer, "]").unwrap();
+    }
+    writer.flush().unwrap();
+}
+
+fn run_buffered(pool: &rayon::ThreadPool, path: &str, options: ParseOptions) -> (Duration, u64) {
+    let start = Instant::now();
+    let chunks =
+        analize_file_for_chunks_with_format(path, pool.current_num_threads(), options.input_format)
+            .unwrap();
+    let total = pool.install(|| {
+        chunks
+            .par_iter()
+            .map(|chunk| {
+                parse_chunk_with_report(path, chunk, options, |_message| {})
+                    .unwrap()
+                    .processed_count
+            })
+            .sum()
+    });
+    (start.elapsed(), total)
+}
+
+fn run_mmap(pool: &rayon::ThreadPool, path: &str, options: ParseOptions) -> (Duration, u64) {
+    let start = Instant::now();
+    let chunks =
+        analize_file_for_chunks_with_format(path, pool.current_num_threads(), options.input_format)
+            .unwrap();
+    let data = map_file(path).unwrap();
+    let total = pool.install(|| {
+        chunks
+            .par_iter()
+            .map(|chunk| {
+                parse_chunk_from_slice(&data, chunk, options, |_message| {})
+                    .unwrap()
+                    .processed_count
+            })
+            .sum()
+    });
+    (start.elapsed(), total)
+}
+
+fn throughput(bytes: u64, elapsed: Duration) -> f64 {
+    bytes as f64 / elapsed.as_secs_f64() / 1_048_576.0
+}
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let num_messages: usize = args.get(1).map_or(2_000_000, |v| {
+        v.parse()
+            .expect("Please provide a valid number of messages")
+    });
+    let input_format: InputFormat = args
+        .get(2)
+        .map_or(Ok(InputFormat::JsonArray), |v| v.parse())
+        .expect("Please provide a valid input format");
+
+    let temp_dir = TempDir::new().expect("Failed to create temp dir");
+    let dataset = temp_dir.path().join("dataset.json");
+    write_dataset(&dataset, num_messages, input_format);
+    let path = dataset.to_string_lossy().to_string();
+    let file_size = std::fs::metadata(&dataset).unwrap().len();
+
+    let options = ParseOptions {
+        input_format,
+        ..ParseOptions::default()
+    };
+
+    println!("=== READER BENCHMARK ===");
+    println!(
+        "Messages: {num_messages}, format: {input_format}, size: {:.1} MiB",
+        file_size as f64 / 1_048_576.0
+    );
+    println!(
+        "{:>8} | {:>18} | {:>18} | {:>8}",
+        "Threads", "BufReader (MiB/s)", "Mmap (MiB/s)", "Speedup"
+    );
+
+    for threads in THREADS {
+        let pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(threads)
+            .build()
+            .expect("Failed to build thread pool");
+
+        let (buffered_elapsed, buffered_total) = run_buffered(&pool, &path, options);
+        let (mmap_elapsed, mmap_total) = run_mmap(&pool, &path, options);
+        assert_eq!(buffered_total, mmap_total);
+        assert_eq!(buffered_total, num_messages as u64);
+
+        println!(
+            "{:>8} | {:>18.1} | {:>18.1} | {:>7.2}x",
+            threads,
+            throughput(file_size, buffered_elapsed),
+            throughput(file_size, mmap_elapsed),
+            buffered_elapsed.as_secs_f64() / mmap_elapsed.as_secs_f64()
+        );
+    }
+}
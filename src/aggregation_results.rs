@@ -0,0 +1,53 @@
+use crate::aggregator::Aggregator;
+use crate::aggregator_handle::AggregatorHandle;
+use crate::chunk_timings::ChunkTimings;
+use crate::skipped_records_summary::SkippedRecordsSummary;
+use std::any::Any;
+
+/// Results of a run of an [`crate::aggregator_set::AggregatorSet`].
+///
+/// Holds the output of every aggregator of the set, taken out with the handles
+/// returned when they were added, together with the statistics of the run.
+#[derive(Debug, Default)]
+pub struct AggregationResults {
+    /// Files that were analyzed (empty when reading from a stream)
+    pub input_files: Vec<String>,
+    /// Total number of messages read
+    pub total_messages: u64,
+    /// Number of messages that matched the filter and were aggregated
+    pub matched_messages: u64,
+    /// Malformed records skipped during the run, by reason
+    pub skipped_records: SkippedRecordsSummary,
+    /// Time spent on each chunk (or stream batch), summarized
+    pub chunk_timings: ChunkTimings,
+    /// Output of each aggregator, in the order they were added; `None` once taken
+    outputs: Vec<Option<Box<dyn Any + Send>>>,
+}
+
+impl AggregationResults {
+    /// Creates the results of a set from the outputs of its aggregators.
+    pub(crate) fn new(
+        outputs: Vec<Box<dyn Any + Send>>,
+        total_messages: u64,
+        matched_messages: u64,
+    ) -> Self {
+        Self {
+            total_messages,
+            matched_messages,
+            outputs: outputs.into_iter().map(Some).collect(),
+            ..Self::default()
+        }
+    }
+
+    /// Takes the output of the aggregator behind `handle`.
+    ///
+    /// Returns `None` if the output was already taken or `handle` belongs to a
+    /// different set.
+    pub fn take<A: Aggregator>(&mut self, handle: AggregatorHandle<A>) -> Option<A::Output> {
+        let slot = self.outputs.get_mut(handle.index)?;
+        if !slot.as_ref()?.is::<A::Output>() {
+            return None;
+        }
+        slot.take()?.downcast().ok().map(|output| *output)
+    }
+}
@@ -0,0 +1,70 @@
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
+
+/// An analysis that can run in the single parallel pass over the input.
+///
+/// The value added to an [`crate::aggregator_set::AggregatorSet`] is an empty
+/// aggregator holding the analysis settings. Each chunk of the input is folded
+/// into its own clone with [`Aggregator::update`], the partial aggregators are
+/// combined with [`Aggregator::merge`] in no particular order, and the final one
+/// is turned into the result with [`Aggregator::finalize`]. Merging must therefore
+/// be associative and commutative for the result to be the same for any number of
+/// threads or chunks.
+///
+/// ```
+/// use TP0ProgramacionConcurrente::aggregator::Aggregator;
+/// use TP0ProgramacionConcurrente::chat_message_ref::ChatMessageRef;
+///
+/// /// Highest viewer count seen in the input.
+/// #[derive(Clone, Default)]
+/// struct MaxViewers(u64);
+///
+/// impl Aggregator for MaxViewers {
+///     type Output = u64;
+///
+///     fn update(&mut self, message: &ChatMessageRef<'_>) {
+///         self.0 = self.0.max(message.viewer_count);
+///     }
+///
+///     fn merge(&mut self, other: Self) {
+///         self.0 = self.0.max(other.0);
+///     }
+///
+///     fn finalize(self) -> u64 {
+///         self.0
+///     }
+/// }
+/// ```
+pub trait Aggregator: Clone + Send + Sync + 'static {
+    /// Result of the analysis.
+    type Output: Send + 'static;
+
+    /// Folds one message into the aggregator.
+    fn update(&mut self, message: &ChatMessageRef<'_>);
+
+    /// Folds one full record into the aggregator.
+    ///
+    /// Only called when some aggregator of the run asks for full records (see
+    /// [`Aggregator::requires_full_record`]); the default implementation passes the
+    /// fields of [`ChatMessageRef`] to [`Aggregator::update`].
+    fn update_record(&mut self, record: &ChatRecord) {
+        if let Some(message) = record.as_message_ref() {
+            self.update(&message);
+        }
+    }
+
+    /// Returns whether the aggregator needs every field of the records.
+    ///
+    /// Full records are much slower to deserialize than the three fields of
+    /// [`ChatMessageRef`], so they are only read if some aggregator of the run
+    /// returns true here and overrides [`Aggregator::update_record`].
+    fn requires_full_record(&self) -> bool {
+        false
+    }
+
+    /// Absorbs the partial results of another clone of the same aggregator.
+    fn merge(&mut self, other: Self);
+
+    /// Turns the merged aggregator into the result of the analysis.
+    fn finalize(self) -> Self::Output;
+}
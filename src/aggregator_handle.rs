@@ -0,0 +1,39 @@
+use crate::aggregator::Aggregator;
+use std::marker::PhantomData;
+
+/// Typed reference to an aggregator added to an [`crate::aggregator_set::AggregatorSet`].
+///
+/// Returned by [`crate::aggregator_set::AggregatorSet::add`] and used to take the
+/// aggregator's result out of the [`crate::aggregation_results::AggregationResults`]
+/// of the run, with its concrete output type.
+pub struct AggregatorHandle<A: Aggregator> {
+    /// Position of the aggregator in its set
+    pub(crate) index: usize,
+    kind: PhantomData<fn() -> A>,
+}
+
+impl<A: Aggregator> AggregatorHandle<A> {
+    /// Creates the handle of the `index`-th aggregator of a set.
+    pub(crate) fn new(index: usize) -> Self {
+        Self {
+            index,
+            kind: PhantomData,
+        }
+    }
+}
+
+impl<A: Aggregator> Clone for AggregatorHandle<A> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<A: Aggregator> Copy for AggregatorHandle<A> {}
+
+impl<A: Aggregator> std::fmt::Debug for AggregatorHandle<A> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AggregatorHandle")
+            .field("index", &self.index)
+            .finish()
+    }
+}
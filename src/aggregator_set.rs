@@ -0,0 +1,209 @@
+use crate::aggregation_results::AggregationResults;
+use crate::aggregator::Aggregator;
+use crate::aggregator_handle::AggregatorHandle;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
+use std::any::Any;
+
+/// Any number of [`Aggregator`]s of different types, run together over one pass.
+///
+/// Every message is handed to all the aggregators of the set, so the input is read
+/// and parsed only once however many analyses run. The set is cloned for every chunk
+/// of the input and the partial sets are merged position by position, like a single
+/// aggregator.
+///
+/// ```
+/// use TP0ProgramacionConcurrente::aggregator_set::AggregatorSet;
+/// use TP0ProgramacionConcurrente::chat_message_ref::ChatMessageRef;
+/// use TP0ProgramacionConcurrente::top_channels_aggregator::TopChannelsAggregator;
+/// use std::borrow::Cow;
+///
+/// let mut set = AggregatorSet::new();
+/// let top_channels = set.add(TopChannelsAggregator::default());
+///
+/// set.update(&ChatMessageRef {
+///     channel_name: Cow::Borrowed("gaming1"),
+///     language: Cow::Borrowed("en"),
+///     viewer_count: 150,
+/// });
+///
+/// let mut results = set.finalize();
+/// let ranking = results.take(top_channels).unwrap();
+/// assert_eq!(ranking[0].top_channels[0].channel_name, "gaming1");
+/// ```
+#[derive(Default)]
+pub struct AggregatorSet {
+    aggregators: Vec<Box<dyn ErasedAggregator>>,
+    total_messages: u64,
+    matched_messages: u64,
+}
+
+impl AggregatorSet {
+    /// Creates an empty set.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds an aggregator to the set and returns the handle to take its result.
+    pub fn add<A: Aggregator>(&mut self, aggregator: A) -> AggregatorHandle<A> {
+        self.aggregators.push(Box::new(aggregator));
+        AggregatorHandle::new(self.aggregators.len() - 1)
+    }
+
+    /// Returns the number of aggregators in the set.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.aggregators.len()
+    }
+
+    /// Returns whether the set has no aggregators.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.aggregators.is_empty()
+    }
+
+    /// Returns the number of messages read, whether or not they were folded into the set.
+    #[must_use]
+    pub fn total_messages(&self) -> u64 {
+        self.total_messages
+    }
+
+    /// Returns the number of messages folded into the set.
+    #[must_use]
+    pub fn matched_messages(&self) -> u64 {
+        self.matched_messages
+    }
+
+    /// Returns whether some aggregator of the set needs full records.
+    #[must_use]
+    pub fn requires_full_record(&self) -> bool {
+        self.aggregators
+            .iter()
+            .any(|aggregator| aggregator.requires_full_record())
+    }
+
+    /// Folds one message into every aggregator of the set.
+    pub fn update(&mut self, message: &ChatMessageRef<'_>) {
+        for aggregator in &mut self.aggregators {
+            aggregator.update(message);
+        }
+        self.total_messages += 1;
+        self.matched_messages += 1;
+    }
+
+    /// Folds one full record into every aggregator of the set.
+    pub fn update_record(&mut self, record: &ChatRecord) {
+        for aggregator in &mut self.aggregators {
+            aggregator.update_record(record);
+        }
+        self.total_messages += 1;
+        self.matched_messages += 1;
+    }
+
+    /// Counts one message that was read but left out of every aggregator, because it
+    /// did not match the filter of the run.
+    pub(crate) fn skip(&mut self) {
+        self.total_messages += 1;
+    }
+
+    /// Absorbs the partial results of another clone of this set.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `other` is not a clone of the same set, i.e. if its aggregators
+    /// have different types.
+    pub fn merge(&mut self, other: AggregatorSet) {
+        assert_eq!(
+            self.aggregators.len(),
+            other.aggregators.len(),
+            "merged aggregator sets must be clones of the same set"
+        );
+        for (aggregator, partial) in self.aggregators.iter_mut().zip(other.aggregators) {
+            aggregator.merge(partial);
+        }
+        self.total_messages += other.total_messages;
+        self.matched_messages += other.matched_messages;
+    }
+
+    /// Finalizes every aggregator of the set into its result.
+    #[must_use]
+    pub fn finalize(self) -> AggregationResults {
+        let outputs = self
+            .aggregators
+            .into_iter()
+            .map(|aggregator| aggregator.finalize())
+            .collect();
+        AggregationResults::new(outputs, self.total_messages, self.matched_messages)
+    }
+}
+
+impl Clone for AggregatorSet {
+    fn clone(&self) -> Self {
+        Self {
+            aggregators: self
+                .aggregators
+                .iter()
+                .map(|aggregator| aggregator.clone_box())
+                .collect(),
+            total_messages: self.total_messages,
+            matched_messages: self.matched_messages,
+        }
+    }
+}
+
+impl std::fmt::Debug for AggregatorSet {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AggregatorSet")
+            .field("aggregators", &self.aggregators.len())
+            .field("total_messages", &self.total_messages)
+            .field("matched_messages", &self.matched_messages)
+            .finish()
+    }
+}
+
+/// Object-safe view of an [`Aggregator`], so aggregators of different types can be
+/// stored together.
+trait ErasedAggregator: Send + Sync {
+    fn clone_box(&self) -> Box<dyn ErasedAggregator>;
+    fn update(&mut self, message: &ChatMessageRef<'_>);
+    fn update_record(&mut self, record: &ChatRecord);
+    fn requires_full_record(&self) -> bool;
+    fn merge(&mut self, other: Box<dyn ErasedAggregator>);
+    fn finalize(self: Box<Self>) -> Box<dyn Any + Send>;
+    fn into_any(self: Box<Self>) -> Box<dyn Any>;
+}
+
+impl<A: Aggregator> ErasedAggregator for A {
+    fn clone_box(&self) -> Box<dyn ErasedAggregator> {
+        Box::new(self.clone())
+    }
+
+    fn update(&mut self, message: &ChatMessageRef<'_>) {
+        Aggregator::update(self, message);
+    }
+
+    fn update_record(&mut self, record: &ChatRecord) {
+        Aggregator::update_record(self, record);
+    }
+
+    fn requires_full_record(&self) -> bool {
+        Aggregator::requires_full_record(self)
+    }
+
+    fn merge(&mut self, other: Box<dyn ErasedAggregator>) {
+        let other = other
+            .into_any()
+            .downcast::<A>()
+            .expect("merged aggregator sets must be clones of the same set");
+        Aggregator::merge(self, *other);
+    }
+
+    fn finalize(self: Box<Self>) -> Box<dyn Any + Send> {
+        Box::new(Aggregator::finalize(*self))
+    }
+
+    fn into_any(self: Box<Self>) -> Box<dyn Any> {
+        self
+    }
+}
@@ -0,0 +1,217 @@
+use crate::custom_error::CustomError;
+use crate::filter_expr::FilterExpr;
+use crate::group_by_query::GroupByQuery;
+use crate::input_format::InputFormat;
+use crate::language_order::LanguageOrder;
+use crate::record_batch_reader::DEFAULT_BATCH_SIZE;
+use crate::time_window::TimeWindow;
+use crate::viewer_buckets::ViewerBuckets;
+
+/// Default number of channels kept per language.
+pub const DEFAULT_TOP_CHANNELS: usize = 3;
+
+/// Default number of languages kept per viewer range.
+pub const DEFAULT_TOP_LANGUAGES: usize = 5;
+
+/// Default number of chunks created per worker thread.
+///
+/// Having several chunks per worker lets rayon hand the remaining chunks to idle
+/// workers, so a slow chunk no longer delays the whole run.
+pub const DEFAULT_CHUNKS_PER_THREAD: usize = 4;
+
+/// Default minimum size, in bytes, of a chunk that adaptive splitting may split.
+pub const DEFAULT_MIN_SPLIT_SIZE: u64 = 1024 * 1024;
+
+/// Configuration for a streaming analysis run.
+///
+/// Keeps the number of worker threads and the number of chunks the file is
+/// divided into as separate knobs, so the amount of parallelism and the
+/// granularity of the work can be tuned independently. By default the input is
+/// over-partitioned into [`DEFAULT_CHUNKS_PER_THREAD`] chunks per worker.
+#[derive(Debug, Clone)]
+pub struct AnalysisConfig {
+    /// Number of worker threads in the dedicated rayon pool
+    pub num_threads: usize,
+    /// Number of chunks the input file is divided into
+    pub num_chunks: usize,
+    /// Split chunks of at least this many bytes in two when a worker is idle
+    /// (`None` keeps the planned chunks)
+    pub adaptive_split: Option<u64>,
+    /// Number of channels kept per language (`None` keeps the full ranking)
+    pub top_channels: Option<usize>,
+    /// Number of languages kept per viewer range (`None` keeps the full ranking)
+    pub top_languages: Option<usize>,
+    /// Viewer ranges used by the top languages transformation
+    pub viewer_buckets: ViewerBuckets,
+    /// Order of the languages in the top channels ranking
+    pub language_order: LanguageOrder,
+    /// File where malformed records are written, one JSON object per line
+    pub reject_file: Option<String>,
+    /// Fail on the first malformed record instead of skipping it
+    pub strict: bool,
+    /// Layout of the input file (`None` detects it from the file contents)
+    pub input_format: Option<InputFormat>,
+    /// Parse the chunks from a memory map of the file instead of buffered reads
+    pub use_mmap: bool,
+    /// Size, in bytes, after which a batch read from a stream is handed to a worker
+    pub stream_batch_size: usize,
+    /// Only messages matching this expression are aggregated (`None` keeps all of them)
+    pub filter: Option<FilterExpr>,
+    /// Query run by the group-by analysis
+    pub query: Option<GroupByQuery>,
+    /// Width of the windows of the time series analysis
+    pub time_window: Option<TimeWindow>,
+}
+
+impl AnalysisConfig {
+    /// Creates a configuration with `num_threads` workers and
+    /// [`DEFAULT_CHUNKS_PER_THREAD`] chunks per worker.
+    #[must_use]
+    pub fn new(num_threads: usize) -> Self {
+        Self {
+            num_threads,
+            num_chunks: num_threads.saturating_mul(DEFAULT_CHUNKS_PER_THREAD),
+            adaptive_split: None,
+            top_channels: Some(DEFAULT_TOP_CHANNELS),
+            top_languages: Some(DEFAULT_TOP_LANGUAGES),
+            viewer_buckets: ViewerBuckets::default(),
+            language_order: LanguageOrder::default(),
+            reject_file: None,
+            strict: false,
+            input_format: None,
+            use_mmap: false,
+            stream_batch_size: DEFAULT_BATCH_SIZE,
+            filter: None,
+            query: None,
+            time_window: None,
+        }
+    }
+
+    /// Sets the number of chunks the input file is divided into.
+    #[must_use]
+    pub fn with_chunks(mut self, num_chunks: usize) -> Self {
+        self.num_chunks = num_chunks;
+        self
+    }
+
+    /// Enables adaptive splitting of chunks of at least `min_split_size` bytes
+    /// (`None` disables it).
+    #[must_use]
+    pub fn with_adaptive_split(mut self, min_split_size: Option<u64>) -> Self {
+        self.adaptive_split = min_split_size;
+        self
+    }
+
+    /// Sets how many channels are kept per language (`None` keeps all of them).
+    #[must_use]
+    pub fn with_top_channels(mut self, top_channels: Option<usize>) -> Self {
+        self.top_channels = top_channels;
+        self
+    }
+
+    /// Sets how many languages are kept per viewer range (`None` keeps all of them).
+    #[must_use]
+    pub fn with_top_languages(mut self, top_languages: Option<usize>) -> Self {
+        self.top_languages = top_languages;
+        self
+    }
+
+    /// Sets the viewer ranges used by the top languages transformation.
+    #[must_use]
+    pub fn with_viewer_buckets(mut self, viewer_buckets: ViewerBuckets) -> Self {
+        self.viewer_buckets = viewer_buckets;
+        self
+    }
+
+    /// Sets the order of the languages in the top channels ranking.
+    #[must_use]
+    pub fn with_language_order(mut self, language_order: LanguageOrder) -> Self {
+        self.language_order = language_order;
+        self
+    }
+
+    /// Sets the file where malformed records are written.
+    #[must_use]
+    pub fn with_reject_file(mut self, reject_file: Option<String>) -> Self {
+        self.reject_file = reject_file;
+        self
+    }
+
+    /// Enables or disables strict validation of the input records.
+    #[must_use]
+    pub fn with_strict(mut self, strict: bool) -> Self {
+        self.strict = strict;
+        self
+    }
+
+    /// Sets the layout of the input file (`None` detects it from the file contents).
+    #[must_use]
+    pub fn with_input_format(mut self, input_format: Option<InputFormat>) -> Self {
+        self.input_format = input_format;
+        self
+    }
+
+    /// Enables or disables reading the input file through a memory map.
+    #[must_use]
+    pub fn with_mmap(mut self, use_mmap: bool) -> Self {
+        self.use_mmap = use_mmap;
+        self
+    }
+
+    /// Sets the size, in bytes, of the batches read from a stream.
+    #[must_use]
+    pub fn with_stream_batch_size(mut self, stream_batch_size: usize) -> Self {
+        self.stream_batch_size = stream_batch_size;
+        self
+    }
+
+    /// Sets the expression messages must match to be aggregated (`None` keeps all of them).
+    #[must_use]
+    pub fn with_filter(mut self, filter: Option<FilterExpr>) -> Self {
+        self.filter = filter;
+        self
+    }
+
+    /// Sets the query run by the group-by analysis.
+    #[must_use]
+    pub fn with_query(mut self, query: Option<GroupByQuery>) -> Self {
+        self.query = query;
+        self
+    }
+
+    /// Sets the width of the windows of the time series analysis.
+    #[must_use]
+    pub fn with_time_window(mut self, time_window: Option<TimeWindow>) -> Self {
+        self.time_window = time_window;
+        self
+    }
+
+    /// Checks that the configuration can be used to run an analysis.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::TransformationError` if the number of threads,
+    /// the number of chunks or the minimum split size is zero, and
+    /// `CustomError::ArgumentError` if the group-by query is invalid.
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.num_threads == 0 {
+            return Err(CustomError::TransformationError(
+                "Number of threads must be greater than zero".to_string(),
+            ));
+        }
+        if self.num_chunks == 0 {
+            return Err(CustomError::TransformationError(
+                "Number of chunks must be greater than zero".to_string(),
+            ));
+        }
+        if self.adaptive_split == Some(0) {
+            return Err(CustomError::TransformationError(
+                "Minimum split size must be greater than zero".to_string(),
+            ));
+        }
+        if let Some(query) = &self.query {
+            query.validate()?;
+        }
+        Ok(())
+    }
+}
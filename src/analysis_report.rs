@@ -0,0 +1,84 @@
+use crate::group_by_result::GroupByResult;
+use crate::run_metadata::RunMetadata;
+use crate::time_series_result::TimeSeriesResult;
+use crate::top_channels_result::TopChannelsByLanguageResult;
+use crate::top_languages_result::TopLanguagesByViewerRangeResult;
+
+/// Version of the serialized report schema.
+///
+/// Incremented whenever a field is renamed or removed; adding fields keeps the version.
+pub const REPORT_SCHEMA_VERSION: u32 = 1;
+
+/// Complete result of an analysis run, ready to be written in any output format.
+///
+/// Serialized as:
+///
+/// ```json
+/// {
+///   "schema_version": 1,
+///   "metadata": {
+///     "input_file": "data.json",
+///     "input_files": ["data.json"],
+///     "analysis_type": "both",
+///     "filter": null,
+///     "num_threads": 4,
+///     "num_chunks": 16,
+///     "chunk_timings": {
+///       "count": 16,
+///       "min_seconds": 0.0001,
+///       "mean_seconds": 0.0002,
+///       "max_seconds": 0.0003,
+///       "imbalance": 1.5
+///     },
+///     "top_channels_limit": 3,
+///     "top_languages_limit": 5,
+///     "total_messages": 7,
+///     "matched_messages": 7,
+///     "skipped_records": {
+///       "total": 1,
+///       "missing_field": 1,
+///       "wrong_type": 0,
+///       "invalid_json": 0
+///     },
+///     "elapsed_seconds": 0.0012
+///   },
+///   "top_channels_by_language": [
+///     {
+///       "language": "en",
+///       "top_channels": [{ "channel_name": "gaming1", "message_count": 2 }]
+///     }
+///   ],
+///   "top_languages_by_viewer_range": [
+///     {
+///       "viewer_range": "0-100",
+///       "lower_bound": 0,
+///       "upper_bound": 100,
+///       "top_languages": [{ "language": "es", "message_count": 1 }]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// A ranking that was not requested is omitted (group-by runs have a `group_by`
+/// object instead, see [`GroupByResult`], and time series runs a `time_series`
+/// object, see [`TimeSeriesResult`]), a `null` limit means the full
+/// ranking was kept, and `upper_bound` is `null` for the open-ended last viewer range.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct AnalysisReport {
+    /// Version of the report schema (see [`REPORT_SCHEMA_VERSION`])
+    pub schema_version: u32,
+    /// Information about the run
+    pub metadata: RunMetadata,
+    /// Top channels for each language, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_channels_by_language: Option<Vec<TopChannelsByLanguageResult>>,
+    /// Top languages for each viewer range, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub top_languages_by_viewer_range: Option<Vec<TopLanguagesByViewerRangeResult>>,
+    /// Result of the group-by query, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub group_by: Option<GroupByResult>,
+    /// Top channels by language of every time window, if requested
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub time_series: Option<TimeSeriesResult>,
+}
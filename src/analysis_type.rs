@@ -0,0 +1,35 @@
+use crate::custom_error::CustomError;
+
+/// Kind of analysis to run over the dataset.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AnalysisType {
+    /// Only the top channels by language
+    TopChannels,
+    /// Only the top languages by viewer range
+    TopLanguages,
+    /// Both transformations in a single pass
+    Both,
+    /// The group-by query of the configuration (see `AnalysisConfig::query`),
+    /// selected with `--group-by` rather than by name
+    GroupBy,
+    /// The top channels by language of every time window (see
+    /// `AnalysisConfig::time_window`), selected with `--window` rather than by name
+    TimeSeries,
+}
+
+impl std::str::FromStr for AnalysisType {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "top_channels" => Ok(AnalysisType::TopChannels),
+            "top_languages" => Ok(AnalysisType::TopLanguages),
+            "both" => Ok(AnalysisType::Both),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Invalid analysis type '{s}'. Use: top_channels, top_languages, or both \
+                 (group-by queries and time series run with --group-by and --window)"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,67 @@
+use crate::custom_error::CustomError;
+use std::io::Read;
+
+/// Size of the blocks read from the input while searching for a record boundary.
+const BOUNDARY_BLOCK_SIZE: usize = 64 * 1024;
+
+/// Sliding in-memory window over the part of a file searched for a record boundary.
+///
+/// The input is read in large blocks and kept in memory, so the boundary search can
+/// look at a byte, or at a whole candidate record, without a read call per byte.
+/// Bytes before the last position asked for are dropped once they add up to a block,
+/// so the window never holds much more than the longest slice requested.
+pub(crate) struct BoundaryWindow<R> {
+    reader: R,
+    /// Buffered bytes, starting at position `start` of the input
+    data: Vec<u8>,
+    start: u64,
+    /// Bytes of the input left to read before the end of the searched range
+    remaining: u64,
+}
+
+impl<R: Read> BoundaryWindow<R> {
+    /// Creates a window over `reader`, which must be positioned at `start`, that reads
+    /// at most `len` bytes.
+    pub(crate) fn new(reader: R, start: u64, len: u64) -> Self {
+        Self {
+            reader,
+            data: Vec::new(),
+            start,
+            remaining: len,
+        }
+    }
+
+    /// Returns the buffered bytes from `position` on, reading more until there are at
+    /// least `len` of them or the end of the range is reached.
+    ///
+    /// `position` must not be before a position asked for earlier. The slice returned
+    /// may be longer than `len`, and is shorter only at the end of the range.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::IOError` if reading from the input fails.
+    pub(crate) fn get(&mut self, position: u64, len: usize) -> Result<&[u8], CustomError> {
+        let skip = usize::try_from(position.saturating_sub(self.start))
+            .unwrap_or(usize::MAX)
+            .min(self.data.len());
+        if skip >= BOUNDARY_BLOCK_SIZE {
+            self.data.drain(..skip);
+            self.start += skip as u64;
+        }
+        let offset = usize::try_from(position - self.start).unwrap_or(usize::MAX);
+
+        while self.data.len().saturating_sub(offset) < len && self.remaining > 0 {
+            let block = self.remaining.min(BOUNDARY_BLOCK_SIZE as u64);
+            let read = (&mut self.reader)
+                .take(block)
+                .read_to_end(&mut self.data)
+                .map_err(|e| CustomError::IOError(e.to_string()))?;
+            if read == 0 {
+                self.remaining = 0;
+            } else {
+                self.remaining -= read as u64;
+            }
+        }
+        Ok(self.data.get(offset..).unwrap_or_default())
+    }
+}
@@ -0,0 +1,15 @@
+use crate::interner::InternId;
+
+/// Compact identifier of an interned channel name.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ChannelId(pub u32);
+
+impl InternId for ChannelId {
+    fn from_index(index: u32) -> Self {
+        ChannelId(index)
+    }
+
+    fn index(self) -> u32 {
+        self.0
+    }
+}
@@ -10,6 +10,9 @@ pub struct ChatMessage {
     /// The language of the channel (e.g., "en", "es", "fr")
     pub language: String,
     /// The number of viewers watching the channel
-    #[serde(rename = "viewerCount")]
-    pub viewer_count: i32,
+    #[serde(
+        rename = "viewerCount",
+        deserialize_with = "crate::viewer_count::deserialize"
+    )]
+    pub viewer_count: u64,
 }
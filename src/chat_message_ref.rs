@@ -0,0 +1,46 @@
+use crate::chat_message::ChatMessage;
+use std::borrow::Cow;
+
+/// A chat message whose text fields borrow from the input buffer.
+///
+/// This is the zero-copy counterpart of [`ChatMessage`] used on the hot path:
+/// `channel_name` and `language` point into the bytes being parsed unless the
+/// JSON string contains escape sequences, in which case they are decoded into
+/// an owned string.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Deserialize)]
+pub struct ChatMessageRef<'a> {
+    /// The name of the Twitch channel
+    #[serde(rename = "channelName", borrow)]
+    pub channel_name: Cow<'a, str>,
+    /// The language of the channel (e.g., "en", "es", "fr")
+    #[serde(borrow)]
+    pub language: Cow<'a, str>,
+    /// The number of viewers watching the channel
+    #[serde(
+        rename = "viewerCount",
+        deserialize_with = "crate::viewer_count::deserialize"
+    )]
+    pub viewer_count: u64,
+}
+
+impl ChatMessageRef<'_> {
+    /// Converts the message into an owned [`ChatMessage`].
+    #[must_use]
+    pub fn into_owned(self) -> ChatMessage {
+        ChatMessage {
+            channel_name: self.channel_name.into_owned(),
+            language: self.language.into_owned(),
+            viewer_count: self.viewer_count,
+        }
+    }
+}
+
+impl<'a> From<&'a ChatMessage> for ChatMessageRef<'a> {
+    fn from(message: &'a ChatMessage) -> Self {
+        Self {
+            channel_name: Cow::Borrowed(&message.channel_name),
+            language: Cow::Borrowed(&message.language),
+            viewer_count: message.viewer_count,
+        }
+    }
+}
@@ -0,0 +1,136 @@
+use crate::chat_message::ChatMessage;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::timestamp::Timestamp;
+use std::borrow::Cow;
+
+/// A chat message from the Twitch dataset with every known field.
+///
+/// Unlike [`ChatMessage`], which only reads the three fields used by the built-in
+/// analyses, this model covers the whole record. Every field is optional, so records
+/// that lack some of them (or have them set to `null`) are still accepted; unknown
+/// fields are ignored. Only the channel name, the language and the viewer count are
+/// type-checked like in the narrower models: any other field holding a value of the
+/// wrong type is read as `None`, so a full-record run skips exactly the same records
+/// as the core analyses. Deserializing it is noticeably slower than [`ChatMessageRef`],
+/// so the core analyses keep using the narrower models.
+///
+/// ```
+/// use TP0ProgramacionConcurrente::chat_record::ChatRecord;
+///
+/// let record: ChatRecord = serde_json::from_str(
+///     r#"{"channelName": "shadowkekw", "language": "ru", "viewerCount": 7414,
+///         "isSubscriber": true, "createAt": {"$date": "2021-11-05T14:59:41.071Z"}}"#,
+/// )
+/// .unwrap();
+/// assert_eq!(record.is_subscriber, Some(true));
+/// assert_eq!(
+///     record.created_at.unwrap().to_string(),
+///     "2021-11-05T14:59:41.071Z"
+/// );
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ChatRecord {
+    /// Unique identifier of the message
+    #[serde(
+        rename = "_id",
+        default,
+        deserialize_with = "crate::lenient::deserialize_option"
+    )]
+    pub id: Option<String>,
+    /// Identifier of the stream category
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub category: Option<String>,
+    /// Identifier of the channel
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub channel: Option<String>,
+    /// Identifier of the author
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub author: Option<String>,
+    /// Content of the message
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub text: Option<String>,
+    /// The name of the Twitch channel
+    pub channel_name: Option<String>,
+    /// Name of the user that sent the message
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub author_name: Option<String>,
+    /// Whether the message contains emotes
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub has_emotes: Option<bool>,
+    /// Whether this is the author's first message in the channel
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub is_first_message: Option<bool>,
+    /// Whether the author is a moderator of the channel
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub is_mod: Option<bool>,
+    /// Whether the author is subscribed to the channel
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub is_subscriber: Option<bool>,
+    /// Whether the message links to malware
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub has_malware: Option<bool>,
+    /// Whether the message contains a URL
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub has_url: Option<bool>,
+    /// Whether the message contains bad words
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub has_bad_words: Option<bool>,
+    /// Whether the message links to a blocked domain
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub has_bad_domain: Option<bool>,
+    /// Whether the message was flagged as spam (the dataset spells it `spamingText`)
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub spaming_text: Option<bool>,
+    /// Name of the stream category (e.g., "Just Chatting")
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub category_name: Option<String>,
+    /// Whether the stream is for mature audiences
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub is_mature: Option<bool>,
+    /// The language of the channel (e.g., "en", "es", "fr")
+    pub language: Option<String>,
+    /// When the stream started
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub stream_started_at: Option<Timestamp>,
+    /// Title of the stream
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub stream_title: Option<String>,
+    /// Identifier of the stream
+    #[serde(default, deserialize_with = "crate::lenient::deserialize_option")]
+    pub stream_id: Option<String>,
+    /// The number of viewers watching the channel
+    #[serde(default, deserialize_with = "crate::viewer_count::deserialize_option")]
+    pub viewer_count: Option<u64>,
+    /// When the message was sent (`createAt.$date` in the dataset)
+    #[serde(
+        rename = "createAt",
+        default,
+        deserialize_with = "crate::lenient::deserialize_option"
+    )]
+    pub created_at: Option<Timestamp>,
+}
+
+impl ChatRecord {
+    /// Returns the fields used by the built-in analyses as a [`ChatMessageRef`]
+    /// borrowing from the record.
+    ///
+    /// Returns `None` if the channel name, the language or the viewer count is missing.
+    #[must_use]
+    pub fn as_message_ref(&self) -> Option<ChatMessageRef<'_>> {
+        Some(ChatMessageRef {
+            channel_name: Cow::Borrowed(self.channel_name.as_deref()?),
+            language: Cow::Borrowed(self.language.as_deref()?),
+            viewer_count: self.viewer_count?,
+        })
+    }
+
+    /// Converts the record into a [`ChatMessage`] with the fields used by the
+    /// built-in analyses.
+    ///
+    /// Returns `None` if the channel name, the language or the viewer count is missing.
+    #[must_use]
+    pub fn to_message(&self) -> Option<ChatMessage> {
+        self.as_message_ref().map(ChatMessageRef::into_owned)
+    }
+}
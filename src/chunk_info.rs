@@ -2,7 +2,12 @@
 ///
 /// This struct contains the metadata needed to process a specific
 /// portion of a file in parallel, including start/end positions and size.
+/// Chunks of several input files are scheduled together, so every chunk
+/// also records which file it belongs to.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub struct ChunkInfo {
+    /// Index of the file the chunk belongs to in the list of input files
+    pub file_id: usize,
     /// The starting byte position in the file
     pub start: u64,
     /// The ending byte position in the file
@@ -0,0 +1,10 @@
+use crate::skipped_record::SkippedRecord;
+
+/// Outcome of parsing a single chunk.
+#[derive(Debug, Default)]
+pub struct ChunkParseReport {
+    /// Number of messages successfully parsed and passed to the callback
+    pub processed_count: u64,
+    /// Malformed records found in the chunk, in file order
+    pub skipped_records: Vec<SkippedRecord>,
+}
@@ -0,0 +1,52 @@
+/// Time spent processing each chunk of a run, summarized.
+///
+/// Makes skew between chunks visible: with balanced work, the slowest chunk
+/// takes about as long as the average one and `imbalance` stays close to 1.
+#[derive(Debug, Clone, Default, PartialEq, serde::Serialize)]
+pub struct ChunkTimings {
+    /// Number of chunks (or stream batches) processed
+    pub count: usize,
+    /// Time of the fastest chunk in seconds
+    pub min_seconds: f64,
+    /// Average time per chunk in seconds
+    pub mean_seconds: f64,
+    /// Time of the slowest chunk in seconds
+    pub max_seconds: f64,
+    /// Ratio between the slowest and the average chunk (1 when perfectly balanced)
+    pub imbalance: f64,
+}
+
+impl ChunkTimings {
+    /// Builds the summary for the processing time, in seconds, of every chunk.
+    #[must_use]
+    pub fn from_durations(seconds: &[f64]) -> Self {
+        if seconds.is_empty() {
+            return Self::default();
+        }
+        let min_seconds = seconds.iter().copied().fold(f64::INFINITY, f64::min);
+        let max_seconds = seconds.iter().copied().fold(0.0, f64::max);
+        let mean_seconds = seconds.iter().sum::<f64>() / seconds.len() as f64;
+        let imbalance = if mean_seconds > 0.0 {
+            max_seconds / mean_seconds
+        } else {
+            1.0
+        };
+        Self {
+            count: seconds.len(),
+            min_seconds,
+            mean_seconds,
+            max_seconds,
+            imbalance,
+        }
+    }
+}
+
+impl std::fmt::Display for ChunkTimings {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} chunks (min: {:.3}s, mean: {:.3}s, max: {:.3}s, imbalance: {:.2}x)",
+            self.count, self.min_seconds, self.mean_seconds, self.max_seconds, self.imbalance
+        )
+    }
+}
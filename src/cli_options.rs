@@ -0,0 +1,280 @@
+use crate::analysis_config::{
+    AnalysisConfig, DEFAULT_MIN_SPLIT_SIZE, DEFAULT_TOP_CHANNELS, DEFAULT_TOP_LANGUAGES,
+};
+use crate::analysis_type::AnalysisType;
+use crate::custom_error::CustomError;
+use crate::filter_expr::FilterExpr;
+use crate::group_by_query::{DEFAULT_QUERY_LIMIT, GroupByQuery};
+use crate::group_order::GroupOrder;
+use crate::input_format::InputFormat;
+use crate::language_order::LanguageOrder;
+use crate::output_format::OutputFormat;
+use crate::time_window::TimeWindow;
+use crate::viewer_buckets::ViewerBuckets;
+use std::num::NonZeroUsize;
+
+/// Usage string printed by the binary when the arguments are invalid.
+pub const USAGE: &str = "<input>... <num_threads> \
+[analysis_type | --group-by <field,...> --top-by <field> | --window <minute|hour|day>] \
+[--chunks <num_chunks>] [--adaptive-split] [--top-channels <n|all>] [--top-languages <n|all>] \
+[--viewer-buckets <b0,b1,...>] [--language-order <volume|alphabetical>] [--limit <n|all>] \
+[--group-order <volume|alphabetical>] [--where <expression>] [--reject-file <file>] [--strict] \
+[--input-format <auto|array|ndjson>] [--mmap] [--format <text|json|csv|markdown>] [--compact] \
+[--output <file>]";
+
+/// Options parsed from the command line.
+///
+/// Positional arguments keep their original meaning (input path, number of
+/// threads and optional analysis type), except that several inputs may be given
+/// before the number of threads; extra knobs are given as `--name value`.
+/// `--group-by` replaces the analysis type with a group-by query, and `--window`
+/// with the top channels by language of every time window.
+#[derive(Debug, Clone)]
+pub struct CliOptions {
+    /// Files, directories or glob patterns to analyze, aggregated as a single dataset
+    pub inputs: Vec<String>,
+    /// Analysis to run (default: both)
+    pub analysis_type: AnalysisType,
+    /// Worker and chunk configuration for the run
+    pub config: AnalysisConfig,
+    /// Format used to write the report (default: text)
+    pub format: OutputFormat,
+    /// File the report is written to (default: stdout)
+    pub output_path: Option<String>,
+}
+
+impl CliOptions {
+    /// Parses the command line arguments, excluding the program name.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::ArgumentError` if a positional argument is missing,
+    /// an option is unknown or a value cannot be parsed.
+    ///
+    /// The last positional argument is the analysis type if it names one, and the
+    /// number of threads otherwise; every positional argument before the number of
+    /// threads is an input.
+    pub fn parse(args: &[String]) -> Result<Self, CustomError> {
+        let mut positional = Vec::new();
+        let mut num_chunks = None;
+        let mut adaptive_split = None;
+        let mut top_channels = Some(DEFAULT_TOP_CHANNELS);
+        let mut top_languages = Some(DEFAULT_TOP_LANGUAGES);
+        let mut viewer_buckets = ViewerBuckets::default();
+        let mut language_order = LanguageOrder::default();
+        let mut filter: Option<FilterExpr> = None;
+        let mut group_by = None;
+        let mut top_by = None;
+        let mut query_limit = None;
+        let mut group_order = None;
+        let mut time_window = None;
+        let mut reject_file = None;
+        let mut strict = false;
+        let mut input_format = None;
+        let mut use_mmap = false;
+        let mut format = OutputFormat::Text;
+        let mut compact = false;
+        let mut output_path = None;
+
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--chunks" => {
+                    num_chunks = Some(parse_count("--chunks", next_value(&mut iter, arg)?)?)
+                }
+                "--adaptive-split" => adaptive_split = Some(DEFAULT_MIN_SPLIT_SIZE),
+                "--top-channels" => {
+                    top_channels = parse_limit("--top-channels", next_value(&mut iter, arg)?)?;
+                }
+                "--top-languages" => {
+                    top_languages = parse_limit("--top-languages", next_value(&mut iter, arg)?)?;
+                }
+                "--viewer-buckets" => {
+                    viewer_buckets = ViewerBuckets::parse(next_value(&mut iter, arg)?)?;
+                }
+                "--language-order" => language_order = next_value(&mut iter, arg)?.parse()?,
+                "--group-by" => group_by = Some(next_value(&mut iter, arg)?),
+                "--top-by" => top_by = Some(next_value(&mut iter, arg)?),
+                "--limit" => {
+                    query_limit = Some(parse_limit("--limit", next_value(&mut iter, arg)?)?)
+                }
+                "--group-order" => {
+                    group_order = Some(next_value(&mut iter, arg)?.parse::<GroupOrder>()?);
+                }
+                "--window" => {
+                    time_window = Some(next_value(&mut iter, arg)?.parse::<TimeWindow>()?);
+                }
+                "--where" => {
+                    let expr = next_value(&mut iter, arg)?.parse()?;
+                    filter = Some(match filter {
+                        Some(previous) => previous.and(expr),
+                        None => expr,
+                    });
+                }
+                "--reject-file" => reject_file = Some(next_value(&mut iter, arg)?.to_string()),
+                "--strict" => strict = true,
+                "--input-format" => {
+                    input_format = parse_input_format(next_value(&mut iter, arg)?)?;
+                }
+                "--mmap" => use_mmap = true,
+                "--format" => format = next_value(&mut iter, arg)?.parse()?,
+                "--compact" => compact = true,
+                "--output" => output_path = Some(next_value(&mut iter, arg)?.to_string()),
+                other if other.starts_with("--") => {
+                    return Err(CustomError::ArgumentError(format!(
+                        "Unknown option '{other}'"
+                    )));
+                }
+                _ => positional.push(arg.as_str()),
+            }
+        }
+
+        if positional.len() < 2 {
+            return Err(CustomError::ArgumentError(format!(
+                "Expected at least 2 positional arguments, got {}",
+                positional.len()
+            )));
+        }
+
+        let mut analysis_type = match positional.last().map(|value| value.parse()) {
+            Some(Ok(analysis_type)) if positional.len() > 2 => {
+                positional.pop();
+                Some(analysis_type)
+            }
+            _ => None,
+        };
+        let last = positional.len() - 1;
+        let num_threads = parse_count("num_threads", positional[last])?;
+        let inputs = positional[..last]
+            .iter()
+            .map(|value| value.to_string())
+            .collect();
+
+        let query = match (group_by, top_by) {
+            (Some(group_by), Some(top_by)) => {
+                if analysis_type.is_some() {
+                    return Err(CustomError::ArgumentError(
+                        "'--group-by' cannot be combined with an analysis type".to_string(),
+                    ));
+                }
+                analysis_type = Some(AnalysisType::GroupBy);
+                Some(
+                    GroupByQuery::parse(group_by, top_by)?
+                        .with_limit(query_limit.unwrap_or(Some(DEFAULT_QUERY_LIMIT)))
+                        .with_group_order(group_order.unwrap_or_default()),
+                )
+            }
+            (Some(_), None) => {
+                return Err(CustomError::ArgumentError(
+                    "'--group-by' requires '--top-by'".to_string(),
+                ));
+            }
+            (None, Some(_)) => {
+                return Err(CustomError::ArgumentError(
+                    "'--top-by' requires '--group-by'".to_string(),
+                ));
+            }
+            (None, None) => {
+                if query_limit.is_some() || group_order.is_some() {
+                    return Err(CustomError::ArgumentError(
+                        "'--limit' and '--group-order' can only be used with '--group-by'"
+                            .to_string(),
+                    ));
+                }
+                None
+            }
+        };
+        if time_window.is_some() {
+            if query.is_some() {
+                return Err(CustomError::ArgumentError(
+                    "'--window' cannot be combined with '--group-by'".to_string(),
+                ));
+            }
+            if analysis_type.is_some() {
+                return Err(CustomError::ArgumentError(
+                    "'--window' cannot be combined with an analysis type".to_string(),
+                ));
+            }
+            analysis_type = Some(AnalysisType::TimeSeries);
+        }
+        let analysis_type = analysis_type.unwrap_or(AnalysisType::Both);
+
+        let mut config = AnalysisConfig::new(num_threads)
+            .with_adaptive_split(adaptive_split)
+            .with_top_channels(top_channels)
+            .with_top_languages(top_languages)
+            .with_viewer_buckets(viewer_buckets)
+            .with_language_order(language_order)
+            .with_filter(filter)
+            .with_query(query)
+            .with_time_window(time_window)
+            .with_reject_file(reject_file)
+            .with_strict(strict)
+            .with_input_format(input_format)
+            .with_mmap(use_mmap);
+        if let Some(num_chunks) = num_chunks {
+            config = config.with_chunks(num_chunks);
+        }
+        config.validate()?;
+
+        if compact {
+            match format {
+                OutputFormat::Json { .. } => format = OutputFormat::Json { pretty: false },
+                _ => {
+                    return Err(CustomError::ArgumentError(
+                        "'--compact' can only be used with '--format json'".to_string(),
+                    ));
+                }
+            }
+        }
+
+        Ok(Self {
+            inputs,
+            analysis_type,
+            config,
+            format,
+            output_path,
+        })
+    }
+}
+
+/// Returns the value following an option, or an error if it is missing.
+fn next_value<'a>(
+    iter: &mut std::slice::Iter<'a, String>,
+    option: &str,
+) -> Result<&'a str, CustomError> {
+    iter.next()
+        .map(String::as_str)
+        .ok_or_else(|| CustomError::ArgumentError(format!("Missing value for '{option}'")))
+}
+
+/// Parses a positive count given on the command line.
+fn parse_count(name: &str, value: &str) -> Result<usize, CustomError> {
+    value.parse().map_err(|_| {
+        CustomError::ArgumentError(format!("Please provide a valid number for {name}"))
+    })
+}
+
+/// Parses a top-N limit, where `all` keeps the full ranking. A limit of zero is
+/// rejected, as it would leave every ranking empty.
+fn parse_limit(name: &str, value: &str) -> Result<Option<usize>, CustomError> {
+    if value == "all" {
+        return Ok(None);
+    }
+    value
+        .parse::<NonZeroUsize>()
+        .map(|limit| Some(limit.get()))
+        .map_err(|_| {
+            CustomError::ArgumentError(format!(
+                "Please provide a number greater than zero (or 'all') for {name}"
+            ))
+        })
+}
+
+/// Parses an input format, where `auto` detects it from the file contents.
+fn parse_input_format(value: &str) -> Result<Option<InputFormat>, CustomError> {
+    if value == "auto" {
+        return Ok(None);
+    }
+    value.parse().map(Some)
+}
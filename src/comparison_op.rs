@@ -0,0 +1,56 @@
+/// Comparison operator of a filter expression.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ComparisonOp {
+    /// `=` (also written `==`)
+    Eq,
+    /// `!=`
+    Ne,
+    /// `<`
+    Lt,
+    /// `<=`
+    Le,
+    /// `>`
+    Gt,
+    /// `>=`
+    Ge,
+}
+
+impl ComparisonOp {
+    /// Returns the symbol of the operator.
+    #[must_use]
+    pub fn symbol(self) -> &'static str {
+        match self {
+            ComparisonOp::Eq => "=",
+            ComparisonOp::Ne => "!=",
+            ComparisonOp::Lt => "<",
+            ComparisonOp::Le => "<=",
+            ComparisonOp::Gt => ">",
+            ComparisonOp::Ge => ">=",
+        }
+    }
+
+    /// Returns whether the operator compares by order rather than by equality.
+    #[must_use]
+    pub fn is_ordering(self) -> bool {
+        !matches!(self, ComparisonOp::Eq | ComparisonOp::Ne)
+    }
+
+    /// Applies the operator to `left` and `right`.
+    #[must_use]
+    pub fn evaluate<T: Ord + ?Sized>(self, left: &T, right: &T) -> bool {
+        match self {
+            ComparisonOp::Eq => left == right,
+            ComparisonOp::Ne => left != right,
+            ComparisonOp::Lt => left < right,
+            ComparisonOp::Le => left <= right,
+            ComparisonOp::Gt => left > right,
+            ComparisonOp::Ge => left >= right,
+        }
+    }
+}
+
+impl std::fmt::Display for ComparisonOp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.symbol())
+    }
+}
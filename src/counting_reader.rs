@@ -0,0 +1,38 @@
+use std::io::Read;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicU64, Ordering};
+
+/// Reader adapter that counts the bytes read from the inner reader.
+///
+/// The count is shared through an atomic, so another thread can follow the
+/// progress of a sequential read (e.g., the compressed bytes consumed by a
+/// decompressor) while it happens.
+pub struct CountingReader<R> {
+    inner: R,
+    bytes_read: Arc<AtomicU64>,
+}
+
+impl<R: Read> CountingReader<R> {
+    /// Wraps `inner`, starting the count at zero.
+    #[must_use]
+    pub fn new(inner: R) -> Self {
+        Self {
+            inner,
+            bytes_read: Arc::new(AtomicU64::new(0)),
+        }
+    }
+
+    /// Returns a handle to the number of bytes read so far.
+    #[must_use]
+    pub fn bytes_read(&self) -> Arc<AtomicU64> {
+        Arc::clone(&self.bytes_read)
+    }
+}
+
+impl<R: Read> Read for CountingReader<R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let read = self.inner.read(buf)?;
+        self.bytes_read.fetch_add(read as u64, Ordering::Relaxed);
+        Ok(read)
+    }
+}
@@ -0,0 +1,123 @@
+use crate::analysis_report::AnalysisReport;
+use crate::custom_error::CustomError;
+use crate::report_writer::ReportWriter;
+use std::io::Write;
+
+/// Writes the rankings as CSV rows.
+///
+/// Each requested ranking is written as its own table with a header row,
+/// `language,rank,channel,count` and `viewer_range,rank,language,count`,
+/// separated by an empty line when both are present. A group-by result is written
+/// as one row per ranked value, with a column for each group-by field, e.g.
+/// `language,categoryName,rank,channelName,count`, and a time series as one row per
+/// ranked channel of every window, `window_start,window_end,language,rank,channel,count`.
+pub struct CsvWriter;
+
+impl ReportWriter for CsvWriter {
+    fn write_report(
+        &self,
+        report: &AnalysisReport,
+        out: &mut dyn Write,
+    ) -> Result<(), CustomError> {
+        write_csv(report, out).map_err(|e| CustomError::IOError(e.to_string()))
+    }
+}
+
+fn write_csv(report: &AnalysisReport, out: &mut dyn Write) -> std::io::Result<()> {
+    if let Some(top_channels) = &report.top_channels_by_language {
+        writeln!(out, "language,rank,channel,count")?;
+        for result in top_channels {
+            for (i, channel) in result.top_channels.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{},{},{},{}",
+                    escape_field(&result.language),
+                    i + 1,
+                    escape_field(&channel.channel_name),
+                    channel.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(top_languages) = &report.top_languages_by_viewer_range {
+        if report.top_channels_by_language.is_some() {
+            writeln!(out)?;
+        }
+        writeln!(out, "viewer_range,rank,language,count")?;
+        for result in top_languages {
+            for (i, language) in result.top_languages.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{},{},{},{}",
+                    escape_field(&result.viewer_range),
+                    i + 1,
+                    escape_field(&language.language),
+                    language.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(group_by) = &report.group_by {
+        let header: Vec<&str> = group_by
+            .group_by
+            .iter()
+            .map(String::as_str)
+            .chain(["rank", &group_by.top_by, "count"])
+            .collect();
+        writeln!(out, "{}", join_fields(&header))?;
+        for (path, group) in group_by.leaves() {
+            for (i, value) in group.top.iter().enumerate() {
+                writeln!(
+                    out,
+                    "{},{},{},{}",
+                    join_fields(&path),
+                    i + 1,
+                    escape_field(&value.value),
+                    value.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(time_series) = &report.time_series {
+        writeln!(out, "window_start,window_end,language,rank,channel,count")?;
+        for window in &time_series.windows {
+            for result in &window.top_channels_by_language {
+                for (i, channel) in result.top_channels.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "{},{},{},{},{},{}",
+                        window.window_start,
+                        window.window_end,
+                        escape_field(&result.language),
+                        i + 1,
+                        escape_field(&channel.channel_name),
+                        channel.message_count
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Escapes `fields` and joins them with commas.
+fn join_fields(fields: &[&str]) -> String {
+    fields
+        .iter()
+        .map(|field| escape_field(field))
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+/// Quotes a field if it contains a separator, a quote or a line break.
+fn escape_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
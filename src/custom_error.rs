@@ -9,6 +9,7 @@ pub enum CustomError {
     IOError(String),
     UnknownError(String),
     CountLanguagesError(String),
+    ArgumentError(String),
 }
 
 impl std::fmt::Display for CustomError {
@@ -19,6 +20,7 @@ impl std::fmt::Display for CustomError {
             CustomError::IOError(msg) => write!(f, "IO Error: {msg}"),
             CustomError::UnknownError(msg) => write!(f, "Unknown Error: {msg}"),
             CustomError::CountLanguagesError(msg) => write!(f, "Count Languages Error: {msg}"),
+            CustomError::ArgumentError(msg) => write!(f, "Argument Error: {msg}"),
         }
     }
 }
@@ -0,0 +1,166 @@
+use crate::chat_message::ChatMessage;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::comparison_op::ComparisonOp;
+use crate::custom_error::CustomError;
+use crate::filter_field::FilterField;
+use crate::filter_parser::parse_filter;
+use crate::filter_value::FilterValue;
+
+/// Boolean expression over the fields of a chat message, used to select the
+/// messages that are aggregated.
+///
+/// Expressions are usually parsed from text (e.g. `--where` on the command line):
+///
+/// - comparisons: `language = es`, `viewerCount > 1000`, `channelName != 'some channel'`
+/// - lists: `channelName in (a, b)` and `language not in (en, es)`
+/// - `not`, `and` and `or`, from tightest to loosest binding, and parentheses
+///
+/// Fields are named as in the JSON records (see [`FilterField`]). Text values may be
+/// bare words or quoted with `'` or `"`; the ordering operators `<`, `<=`, `>` and
+/// `>=` can only be used with `viewerCount`. Keywords are case-insensitive.
+///
+/// ```
+/// use TP0ProgramacionConcurrente::chat_message::ChatMessage;
+/// use TP0ProgramacionConcurrente::filter_expr::FilterExpr;
+///
+/// let filter: FilterExpr = "language = es and viewerCount > 1000".parse().unwrap();
+/// let message = ChatMessage {
+///     channel_name: "gaming1".to_string(),
+///     language: "es".to_string(),
+///     viewer_count: 1500,
+/// };
+/// assert!(filter.matches_message(&message));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterExpr {
+    /// `field op value`
+    Compare {
+        field: FilterField,
+        op: ComparisonOp,
+        value: FilterValue,
+    },
+    /// `field in (value, ...)`, true if the field equals any of the values
+    In {
+        field: FilterField,
+        values: Vec<FilterValue>,
+    },
+    /// `not expr`
+    Not(Box<FilterExpr>),
+    /// `left and right`
+    And(Box<FilterExpr>, Box<FilterExpr>),
+    /// `left or right`
+    Or(Box<FilterExpr>, Box<FilterExpr>),
+}
+
+impl FilterExpr {
+    /// Combines two expressions into one that matches when both do.
+    #[must_use]
+    pub fn and(self, other: FilterExpr) -> Self {
+        FilterExpr::And(Box::new(self), Box::new(other))
+    }
+
+    /// Returns whether `message` satisfies the expression.
+    ///
+    /// A comparison between a field and a value of a different type (e.g.
+    /// `viewerCount` and text), which the parser rejects, never matches.
+    #[must_use]
+    pub fn matches(&self, message: &ChatMessageRef<'_>) -> bool {
+        match self {
+            FilterExpr::Compare { field, op, value } => compare(message, *field, *op, value),
+            FilterExpr::In { field, values } => values
+                .iter()
+                .any(|value| compare(message, *field, ComparisonOp::Eq, value)),
+            FilterExpr::Not(expr) => !expr.matches(message),
+            FilterExpr::And(left, right) => left.matches(message) && right.matches(message),
+            FilterExpr::Or(left, right) => left.matches(message) || right.matches(message),
+        }
+    }
+
+    /// Returns whether the owned `message` satisfies the expression.
+    #[must_use]
+    pub fn matches_message(&self, message: &ChatMessage) -> bool {
+        self.matches(&ChatMessageRef::from(message))
+    }
+
+    /// Binding strength of the expression's outermost operator, used to decide
+    /// where parentheses are needed when it is written back as text.
+    fn precedence(&self) -> u8 {
+        match self {
+            FilterExpr::Or(..) => 1,
+            FilterExpr::And(..) => 2,
+            FilterExpr::Not(_) => 3,
+            FilterExpr::Compare { .. } | FilterExpr::In { .. } => 4,
+        }
+    }
+
+    /// Writes the expression, between parentheses if it binds looser than `precedence`.
+    fn fmt_operand(&self, f: &mut std::fmt::Formatter<'_>, precedence: u8) -> std::fmt::Result {
+        if self.precedence() < precedence {
+            write!(f, "({self})")
+        } else {
+            write!(f, "{self}")
+        }
+    }
+}
+
+/// Compares `field` of `message` against `value` with `op`.
+fn compare(
+    message: &ChatMessageRef<'_>,
+    field: FilterField,
+    op: ComparisonOp,
+    value: &FilterValue,
+) -> bool {
+    match (field, value) {
+        (FilterField::ChannelName, FilterValue::Text(text)) => {
+            op.evaluate(message.channel_name.as_ref(), text.as_str())
+        }
+        (FilterField::Language, FilterValue::Text(text)) => {
+            op.evaluate(message.language.as_ref(), text.as_str())
+        }
+        (FilterField::ViewerCount, FilterValue::Number(number)) => {
+            op.evaluate(&message.viewer_count, number)
+        }
+        _ => false,
+    }
+}
+
+impl std::str::FromStr for FilterExpr {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        parse_filter(s)
+    }
+}
+
+impl std::fmt::Display for FilterExpr {
+    /// Writes the expression in a form that parses back to the same expression.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterExpr::Compare { field, op, value } => write!(f, "{field} {op} {value}"),
+            FilterExpr::In { field, values } => {
+                write!(f, "{field} in (")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i > 0 {
+                        f.write_str(", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                f.write_str(")")
+            }
+            FilterExpr::Not(expr) => {
+                f.write_str("not ")?;
+                expr.fmt_operand(f, 3)
+            }
+            FilterExpr::And(left, right) => {
+                left.fmt_operand(f, 2)?;
+                f.write_str(" and ")?;
+                right.fmt_operand(f, 3)
+            }
+            FilterExpr::Or(left, right) => {
+                left.fmt_operand(f, 1)?;
+                f.write_str(" or ")?;
+                right.fmt_operand(f, 2)
+            }
+        }
+    }
+}
@@ -0,0 +1,53 @@
+use crate::custom_error::CustomError;
+
+/// Field of a chat message that a filter expression can refer to.
+///
+/// Fields are named as in the JSON records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterField {
+    /// `channelName`, compared as text
+    ChannelName,
+    /// `language`, compared as text
+    Language,
+    /// `viewerCount`, compared as a number
+    ViewerCount,
+}
+
+impl FilterField {
+    /// Returns the name of the field in the JSON records.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        match self {
+            FilterField::ChannelName => "channelName",
+            FilterField::Language => "language",
+            FilterField::ViewerCount => "viewerCount",
+        }
+    }
+
+    /// Returns whether the field holds a number rather than text.
+    #[must_use]
+    pub fn is_numeric(self) -> bool {
+        self == FilterField::ViewerCount
+    }
+}
+
+impl std::str::FromStr for FilterField {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "channelName" => Ok(FilterField::ChannelName),
+            "language" => Ok(FilterField::Language),
+            "viewerCount" => Ok(FilterField::ViewerCount),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Unknown filter field '{s}'. Use: channelName, language or viewerCount"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for FilterField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,292 @@
+use crate::comparison_op::ComparisonOp;
+use crate::custom_error::CustomError;
+use crate::filter_expr::FilterExpr;
+use crate::filter_field::FilterField;
+use crate::filter_value::FilterValue;
+
+/// Token of a filter expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    /// Field name, keyword or unquoted value
+    Word(String),
+    /// Quoted value, without the quotes and with its escapes resolved
+    Text(String),
+    Op(ComparisonOp),
+    LeftParen,
+    RightParen,
+    Comma,
+}
+
+impl std::fmt::Display for Token {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) => write!(f, "'{word}'"),
+            Token::Text(text) => write!(f, "{}", FilterValue::Text(text.clone())),
+            Token::Op(op) => write!(f, "'{op}'"),
+            Token::LeftParen => f.write_str("'('"),
+            Token::RightParen => f.write_str("')'"),
+            Token::Comma => f.write_str("','"),
+        }
+    }
+}
+
+/// Parses a filter expression (see [`FilterExpr`] for the syntax).
+///
+/// # Errors
+///
+/// Returns `CustomError::ArgumentError` describing the first problem found and
+/// its byte offset in `input`.
+pub(crate) fn parse_filter(input: &str) -> Result<FilterExpr, CustomError> {
+    let mut parser = Parser {
+        tokens: tokenize(input)?,
+        position: 0,
+        end: input.len(),
+    };
+    let expr = parser.parse_or()?;
+    match parser.tokens.get(parser.position) {
+        Some((offset, token)) => Err(syntax_error(
+            format!("unexpected {token}, expected 'and', 'or' or the end of the expression"),
+            *offset,
+        )),
+        None => Ok(expr),
+    }
+}
+
+/// Builds the error for a problem found at byte `offset` of the expression.
+fn syntax_error(detail: String, offset: usize) -> CustomError {
+    CustomError::ArgumentError(format!(
+        "Invalid filter expression: {detail} (at offset {offset})"
+    ))
+}
+
+/// Returns whether `c` can be part of a field name, keyword or unquoted value.
+fn is_word_char(c: char) -> bool {
+    c.is_alphanumeric() || matches!(c, '_' | '-' | '.' | ':')
+}
+
+/// Splits `input` into tokens, each with its byte offset.
+fn tokenize(input: &str) -> Result<Vec<(usize, Token)>, CustomError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((offset, c)) = chars.next() {
+        let token = match c {
+            c if c.is_whitespace() => continue,
+            '(' => Token::LeftParen,
+            ')' => Token::RightParen,
+            ',' => Token::Comma,
+            '=' => {
+                chars.next_if(|&(_, next)| next == '=');
+                Token::Op(ComparisonOp::Eq)
+            }
+            '!' => match chars.next_if(|&(_, next)| next == '=') {
+                Some(_) => Token::Op(ComparisonOp::Ne),
+                None => return Err(syntax_error("expected '=' after '!'".to_string(), offset)),
+            },
+            '<' => match chars.next_if(|&(_, next)| next == '=') {
+                Some(_) => Token::Op(ComparisonOp::Le),
+                None => Token::Op(ComparisonOp::Lt),
+            },
+            '>' => match chars.next_if(|&(_, next)| next == '=') {
+                Some(_) => Token::Op(ComparisonOp::Ge),
+                None => Token::Op(ComparisonOp::Gt),
+            },
+            '\'' | '"' => match read_quoted(&mut chars, c) {
+                Some(text) => Token::Text(text),
+                None => {
+                    return Err(syntax_error(
+                        "unterminated quoted value".to_string(),
+                        offset,
+                    ));
+                }
+            },
+            c if is_word_char(c) => {
+                let mut word = c.to_string();
+                while let Some((_, next)) = chars.next_if(|&(_, next)| is_word_char(next)) {
+                    word.push(next);
+                }
+                Token::Word(word)
+            }
+            other => {
+                return Err(syntax_error(
+                    format!("unexpected character '{other}'"),
+                    offset,
+                ));
+            }
+        };
+        tokens.push((offset, token));
+    }
+    Ok(tokens)
+}
+
+/// Reads a value quoted with `quote` up to its closing quote, resolving backslash
+/// escapes. Returns `None` if the value is not terminated.
+fn read_quoted(chars: &mut impl Iterator<Item = (usize, char)>, quote: char) -> Option<String> {
+    let mut text = String::new();
+    while let Some((_, c)) = chars.next() {
+        match c {
+            '\\' => text.push(chars.next()?.1),
+            c if c == quote => return Some(text),
+            c => text.push(c),
+        }
+    }
+    None
+}
+
+/// Recursive descent parser over the tokens of an expression.
+///
+/// `or` binds looser than `and`, which binds looser than `not`; both binary
+/// operators associate to the left.
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+    /// Length of the expression, reported as the offset of errors at its end
+    end: usize,
+}
+
+impl Parser {
+    /// Returns the offset of the next token, or the end of the expression.
+    fn offset(&self) -> usize {
+        self.tokens
+            .get(self.position)
+            .map_or(self.end, |(offset, _)| *offset)
+    }
+
+    /// Returns the next token without advancing past it.
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.position).map(|(_, token)| token)
+    }
+
+    /// Advances past the next token if it is `token`.
+    fn eat(&mut self, token: &Token) -> bool {
+        let found = self.peek() == Some(token);
+        self.position += usize::from(found);
+        found
+    }
+
+    /// Advances past the next token if it is the (case-insensitive) keyword `keyword`.
+    fn eat_keyword(&mut self, keyword: &str) -> bool {
+        let found =
+            matches!(self.peek(), Some(Token::Word(word)) if word.eq_ignore_ascii_case(keyword));
+        self.position += usize::from(found);
+        found
+    }
+
+    /// Consumes the next token, failing with `expected` if it is not `token`.
+    fn expect(&mut self, token: &Token, expected: &str) -> Result<(), CustomError> {
+        if self.eat(token) {
+            Ok(())
+        } else {
+            Err(self.unexpected(expected))
+        }
+    }
+
+    /// Builds the error for a next token that is not `expected`.
+    fn unexpected(&self, expected: &str) -> CustomError {
+        let found = match self.peek() {
+            Some(token) => token.to_string(),
+            None => "end of expression".to_string(),
+        };
+        syntax_error(format!("expected {expected}, found {found}"), self.offset())
+    }
+
+    fn parse_or(&mut self) -> Result<FilterExpr, CustomError> {
+        let mut expr = self.parse_and()?;
+        while self.eat_keyword("or") {
+            expr = FilterExpr::Or(Box::new(expr), Box::new(self.parse_and()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_and(&mut self) -> Result<FilterExpr, CustomError> {
+        let mut expr = self.parse_not()?;
+        while self.eat_keyword("and") {
+            expr = FilterExpr::And(Box::new(expr), Box::new(self.parse_not()?));
+        }
+        Ok(expr)
+    }
+
+    fn parse_not(&mut self) -> Result<FilterExpr, CustomError> {
+        if self.eat_keyword("not") {
+            return Ok(FilterExpr::Not(Box::new(self.parse_not()?)));
+        }
+        self.parse_primary()
+    }
+
+    /// Parses a parenthesized expression, a comparison or an `in` list.
+    fn parse_primary(&mut self) -> Result<FilterExpr, CustomError> {
+        if self.eat(&Token::LeftParen) {
+            let expr = self.parse_or()?;
+            self.expect(&Token::RightParen, "')'")?;
+            return Ok(expr);
+        }
+
+        let field: FilterField = match self.peek() {
+            Some(Token::Word(name)) => name
+                .parse()
+                .map_err(|_| syntax_error(format!("unknown field '{name}'"), self.offset()))?,
+            _ => return Err(self.unexpected("a field name, 'not' or '('")),
+        };
+        self.position += 1;
+
+        if self.eat_keyword("in") {
+            return self.parse_list(field);
+        }
+        if self.eat_keyword("not") {
+            if !self.eat_keyword("in") {
+                return Err(self.unexpected("'in'"));
+            }
+            return Ok(FilterExpr::Not(Box::new(self.parse_list(field)?)));
+        }
+
+        let op = match self.peek() {
+            Some(Token::Op(op)) => *op,
+            _ => return Err(self.unexpected(&format!("an operator or 'in' after '{field}'"))),
+        };
+        if op.is_ordering() && !field.is_numeric() {
+            return Err(syntax_error(
+                format!("operator '{op}' can only be used with viewerCount"),
+                self.offset(),
+            ));
+        }
+        self.position += 1;
+        let value = self.parse_value(field)?;
+        Ok(FilterExpr::Compare { field, op, value })
+    }
+
+    /// Parses the parenthesized, comma-separated values of an `in` list.
+    fn parse_list(&mut self, field: FilterField) -> Result<FilterExpr, CustomError> {
+        self.expect(&Token::LeftParen, "'(' after 'in'")?;
+        let mut values = vec![self.parse_value(field)?];
+        while self.eat(&Token::Comma) {
+            values.push(self.parse_value(field)?);
+        }
+        self.expect(&Token::RightParen, "',' or ')'")?;
+        Ok(FilterExpr::In { field, values })
+    }
+
+    /// Parses a value compared against `field`, checking that it has the field's type.
+    fn parse_value(&mut self, field: FilterField) -> Result<FilterValue, CustomError> {
+        let offset = self.offset();
+        let value = match (self.peek(), field.is_numeric()) {
+            (Some(Token::Word(word)), true) => {
+                word.parse().map(FilterValue::Number).map_err(|_| {
+                    syntax_error(
+                        format!("'{word}' is not a valid number for {field}"),
+                        offset,
+                    )
+                })?
+            }
+            (Some(Token::Text(text)), true) => {
+                return Err(syntax_error(
+                    format!("{field} must be compared with a number, found quoted value '{text}'"),
+                    offset,
+                ));
+            }
+            (Some(Token::Word(text) | Token::Text(text)), false) => FilterValue::Text(text.clone()),
+            _ => return Err(self.unexpected("a value")),
+        };
+        self.position += 1;
+        Ok(value)
+    }
+}
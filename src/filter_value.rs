@@ -0,0 +1,21 @@
+/// Literal a field is compared against in a filter expression.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FilterValue {
+    /// Text, compared with `channelName` or `language`
+    Text(String),
+    /// Non-negative integer, compared with `viewerCount`
+    Number(u64),
+}
+
+impl std::fmt::Display for FilterValue {
+    /// Writes numbers as they are and text between single quotes, escaping
+    /// backslashes and quotes, so the value can be parsed back.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FilterValue::Text(text) => {
+                write!(f, "'{}'", text.replace('\\', "\\\\").replace('\'', "\\'"))
+            }
+            FilterValue::Number(number) => write!(f, "{number}"),
+        }
+    }
+}
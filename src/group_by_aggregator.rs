@@ -0,0 +1,246 @@
+use crate::aggregator::Aggregator;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
+use crate::group_by_query::GroupByQuery;
+use crate::group_by_result::GroupByResult;
+use crate::group_node::GroupNode;
+use crate::group_order::GroupOrder;
+use crate::interner::{InternId, Interner};
+use crate::query_field::QueryField;
+use crate::transformations::{merge_counts, rank_counts};
+use crate::value_count::ValueCount;
+use crate::value_id::ValueId;
+use crate::viewer_buckets::ViewerBuckets;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::HashMap;
+
+/// Counts per group of the values of the ranked field, keyed by the IDs of the
+/// group-by values, outermost first.
+type GroupCounts = HashMap<Vec<ValueId>, HashMap<ValueId, u64>>;
+
+/// One entry of [`GroupCounts`], borrowed while building the nested groups.
+type GroupEntry<'a> = (&'a [ValueId], &'a HashMap<ValueId, u64>);
+
+/// Runs a [`GroupByQuery`] in the parallel pass over the input.
+///
+/// Every field value is interned, so a string is only allocated the first time a
+/// value is seen; messages that lack one of the query fields (or whose viewer count
+/// is outside every range, for `viewerRange`) are not counted.
+///
+/// ```
+/// use TP0ProgramacionConcurrente::aggregator::Aggregator;
+/// use TP0ProgramacionConcurrente::chat_message_ref::ChatMessageRef;
+/// use TP0ProgramacionConcurrente::group_by_aggregator::GroupByAggregator;
+/// use TP0ProgramacionConcurrente::group_by_query::GroupByQuery;
+/// use TP0ProgramacionConcurrente::viewer_buckets::ViewerBuckets;
+/// use std::borrow::Cow;
+///
+/// let query = GroupByQuery::parse("language,viewerRange", "channelName").unwrap();
+/// let mut aggregator = GroupByAggregator::new(query, ViewerBuckets::default());
+/// aggregator.update(&ChatMessageRef {
+///     channel_name: Cow::Borrowed("gaming1"),
+///     language: Cow::Borrowed("en"),
+///     viewer_count: 150,
+/// });
+///
+/// let result = aggregator.finalize();
+/// let (path, group) = &result.leaves()[0];
+/// assert_eq!(path, &vec!["en", "101-500"]);
+/// assert_eq!(group.top[0].value, "gaming1");
+/// ```
+#[derive(Debug, Clone)]
+pub struct GroupByAggregator {
+    query: GroupByQuery,
+    viewer_buckets: ViewerBuckets,
+    values: Interner<ValueId>,
+    counts: GroupCounts,
+    /// Group key of the message being folded, reused to avoid an allocation per message
+    key: Vec<ValueId>,
+}
+
+impl GroupByAggregator {
+    /// Creates an aggregator for `query`, classifying `viewerRange` into `viewer_buckets`.
+    #[must_use]
+    pub fn new(query: GroupByQuery, viewer_buckets: ViewerBuckets) -> Self {
+        Self {
+            query,
+            viewer_buckets,
+            values: Interner::new(),
+            counts: HashMap::new(),
+            key: Vec::new(),
+        }
+    }
+
+    /// Returns the query the aggregator runs.
+    #[must_use]
+    pub fn query(&self) -> &GroupByQuery {
+        &self.query
+    }
+
+    /// Returns the viewer ranges `viewerRange` is classified into.
+    #[must_use]
+    pub fn viewer_buckets(&self) -> &ViewerBuckets {
+        &self.viewer_buckets
+    }
+
+    /// Builds the groups of the `level`-th group-by field from the counts whose keys
+    /// share the values of the previous levels.
+    fn build_groups(&self, level: usize, entries: Vec<GroupEntry<'_>>) -> Vec<GroupNode> {
+        let mut by_value: HashMap<ValueId, Vec<GroupEntry<'_>>> = HashMap::new();
+        for entry in entries {
+            by_value.entry(entry.0[level]).or_default().push(entry);
+        }
+
+        let last_level = level + 1 == self.query.group_by.len();
+        let mut groups: Vec<GroupNode> = by_value
+            .into_iter()
+            .map(|(value, entries)| {
+                let message_count = entries.iter().flat_map(|(_, counts)| counts.values()).sum();
+                let (groups, top) = if last_level {
+                    // The key is complete, so there is a single entry per value.
+                    let top = rank_counts(entries[0].1, &self.values, self.query.limit)
+                        .into_iter()
+                        .map(|(value, message_count)| ValueCount {
+                            value,
+                            message_count,
+                        })
+                        .collect();
+                    (Vec::new(), top)
+                } else {
+                    (self.build_groups(level + 1, entries), Vec::new())
+                };
+                GroupNode {
+                    value: self.values.resolve(value).to_string(),
+                    message_count,
+                    groups,
+                    top,
+                }
+            })
+            .collect();
+
+        let field = self.query.group_by[level];
+        groups.sort_by(|a, b| self.compare_groups(field, a, b));
+        groups
+    }
+
+    /// Orders two groups of `field` according to the query's group order.
+    fn compare_groups(&self, field: QueryField, a: &GroupNode, b: &GroupNode) -> Ordering {
+        if field == QueryField::ViewerRange {
+            let position = |label: &str| {
+                self.viewer_buckets
+                    .ranges()
+                    .iter()
+                    .position(|range| range.label == label)
+            };
+            return position(&a.value).cmp(&position(&b.value));
+        }
+        match self.query.group_order {
+            GroupOrder::Volume => b
+                .message_count
+                .cmp(&a.message_count)
+                .then_with(|| compare_values(field, &a.value, &b.value)),
+            GroupOrder::Alphabetical => compare_values(field, &a.value, &b.value),
+        }
+    }
+}
+
+/// Compares two values of `field`, numerically for `viewerCount`.
+fn compare_values(field: QueryField, a: &str, b: &str) -> Ordering {
+    if field == QueryField::ViewerCount {
+        let number = |value: &str| value.parse::<u64>().ok();
+        number(a).cmp(&number(b)).then_with(|| a.cmp(b))
+    } else {
+        a.cmp(b)
+    }
+}
+
+/// Counts one message, reading its field values with `value_of`.
+fn count_message<'a>(
+    query: &GroupByQuery,
+    values: &mut Interner<ValueId>,
+    counts: &mut GroupCounts,
+    key: &mut Vec<ValueId>,
+    value_of: impl Fn(QueryField) -> Option<Cow<'a, str>>,
+) {
+    key.clear();
+    for &field in &query.group_by {
+        let Some(value) = value_of(field) else {
+            return;
+        };
+        key.push(values.intern(&value));
+    }
+    let Some(top_value) = value_of(query.top_by) else {
+        return;
+    };
+    let top_value = values.intern(&top_value);
+
+    if let Some(group) = counts.get_mut(key.as_slice()) {
+        *group.entry(top_value).or_insert(0) += 1;
+        return;
+    }
+    counts.entry(key.clone()).or_default().insert(top_value, 1);
+}
+
+impl Aggregator for GroupByAggregator {
+    type Output = GroupByResult;
+
+    fn update(&mut self, message: &ChatMessageRef<'_>) {
+        let Self {
+            query,
+            viewer_buckets,
+            values,
+            counts,
+            key,
+        } = self;
+        count_message(query, values, counts, key, |field| {
+            field.message_value(message, viewer_buckets)
+        });
+    }
+
+    fn update_record(&mut self, record: &ChatRecord) {
+        let Self {
+            query,
+            viewer_buckets,
+            values,
+            counts,
+            key,
+        } = self;
+        count_message(query, values, counts, key, |field| {
+            field.record_value(record, viewer_buckets)
+        });
+    }
+
+    fn requires_full_record(&self) -> bool {
+        self.query.requires_full_record()
+    }
+
+    fn merge(&mut self, other: Self) {
+        let value_ids = self.values.absorb(&other.values);
+        let translate = |id: ValueId| value_ids[id.index() as usize];
+        for (key, group) in other.counts {
+            let key: Vec<ValueId> = key.into_iter().map(translate).collect();
+            merge_counts(self.counts.entry(key).or_default(), group, translate);
+        }
+    }
+
+    fn finalize(self) -> GroupByResult {
+        let entries = self
+            .counts
+            .iter()
+            .map(|(key, counts)| (key.as_slice(), counts))
+            .collect();
+        let groups = self.build_groups(0, entries);
+        GroupByResult {
+            group_by: self
+                .query
+                .group_by
+                .iter()
+                .map(|field| field.name().to_string())
+                .collect(),
+            top_by: self.query.top_by.name().to_string(),
+            limit: self.query.limit,
+            groups,
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::custom_error::CustomError;
+use crate::group_order::GroupOrder;
+use crate::language_order::LanguageOrder;
+use crate::query_field::QueryField;
+
+/// Default number of values kept per group.
+pub const DEFAULT_QUERY_LIMIT: usize = 10;
+
+/// A "group by, count, take the top N within each group" query.
+///
+/// Messages are grouped by the values of the `group_by` fields, nested in the given
+/// order, and the values of `top_by` are counted and ranked within each innermost
+/// group. The built-in analyses keep their own typed aggregators, but can be
+/// expressed as this query too (see [`GroupByQuery::top_channels_by_language`] and
+/// [`GroupByQuery::top_languages_by_viewer_range`]).
+///
+/// ```
+/// use TP0ProgramacionConcurrente::group_by_query::GroupByQuery;
+/// use TP0ProgramacionConcurrente::query_field::QueryField;
+///
+/// let query = GroupByQuery::parse("language,categoryName", "channelName")
+///     .unwrap()
+///     .with_limit(Some(5));
+/// assert_eq!(query.group_by, vec![QueryField::Language, QueryField::CategoryName]);
+/// assert!(query.requires_full_record());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GroupByQuery {
+    /// Fields the messages are grouped by, outermost first
+    pub group_by: Vec<QueryField>,
+    /// Field whose values are counted and ranked within each group
+    pub top_by: QueryField,
+    /// Number of values kept per group (`None` keeps the full ranking)
+    pub limit: Option<usize>,
+    /// Order of the groups at every level
+    pub group_order: GroupOrder,
+}
+
+impl GroupByQuery {
+    /// Creates a query that keeps the top [`DEFAULT_QUERY_LIMIT`] values of `top_by`
+    /// within each group of `group_by`, with groups ordered by volume.
+    #[must_use]
+    pub fn new(group_by: Vec<QueryField>, top_by: QueryField) -> Self {
+        Self {
+            group_by,
+            top_by,
+            limit: Some(DEFAULT_QUERY_LIMIT),
+            group_order: GroupOrder::default(),
+        }
+    }
+
+    /// Parses a query from a comma-separated list of group-by fields and a top-by field,
+    /// e.g. `language,categoryName` and `channelName`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::ArgumentError` if a field is unknown or the query is
+    /// invalid (see [`GroupByQuery::validate`]).
+    pub fn parse(group_by: &str, top_by: &str) -> Result<Self, CustomError> {
+        let group_by = group_by
+            .split(',')
+            .map(|field| field.trim().parse())
+            .collect::<Result<Vec<QueryField>, CustomError>>()?;
+        let query = Self::new(group_by, top_by.trim().parse()?);
+        query.validate()?;
+        Ok(query)
+    }
+
+    /// Query equivalent to the top channels by language analysis.
+    #[must_use]
+    pub fn top_channels_by_language(limit: Option<usize>, language_order: LanguageOrder) -> Self {
+        Self::new(vec![QueryField::Language], QueryField::ChannelName)
+            .with_limit(limit)
+            .with_group_order(language_order.into())
+    }
+
+    /// Query equivalent to the top languages by viewer range analysis.
+    #[must_use]
+    pub fn top_languages_by_viewer_range(limit: Option<usize>) -> Self {
+        Self::new(vec![QueryField::ViewerRange], QueryField::Language).with_limit(limit)
+    }
+
+    /// Sets how many values are kept per group (`None` keeps all of them).
+    #[must_use]
+    pub fn with_limit(mut self, limit: Option<usize>) -> Self {
+        self.limit = limit;
+        self
+    }
+
+    /// Sets the order of the groups at every level.
+    #[must_use]
+    pub fn with_group_order(mut self, group_order: GroupOrder) -> Self {
+        self.group_order = group_order;
+        self
+    }
+
+    /// Returns whether some field of the query is only available in full records.
+    #[must_use]
+    pub fn requires_full_record(&self) -> bool {
+        self.group_by
+            .iter()
+            .chain(std::iter::once(&self.top_by))
+            .any(|field| field.requires_full_record())
+    }
+
+    /// Checks that the query can be run.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::ArgumentError` if there are no group-by fields, a field is
+    /// repeated, or the top-by field is also a group-by field.
+    pub fn validate(&self) -> Result<(), CustomError> {
+        if self.group_by.is_empty() {
+            return Err(CustomError::ArgumentError(
+                "At least one group-by field is required".to_string(),
+            ));
+        }
+        for (i, field) in self.group_by.iter().enumerate() {
+            if self.group_by[..i].contains(field) {
+                return Err(CustomError::ArgumentError(format!(
+                    "Group-by field '{field}' is repeated"
+                )));
+            }
+        }
+        if self.group_by.contains(&self.top_by) {
+            return Err(CustomError::ArgumentError(format!(
+                "'{}' cannot be both a group-by and the top-by field",
+                self.top_by
+            )));
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use crate::group_node::GroupNode;
+
+/// Result of a group-by query: nested groups with a ranking in each innermost group.
+///
+/// Serialized as:
+///
+/// ```json
+/// {
+///   "group_by": ["language", "categoryName"],
+///   "top_by": "channelName",
+///   "limit": 10,
+///   "groups": [
+///     {
+///       "value": "en",
+///       "message_count": 3,
+///       "groups": [
+///         {
+///           "value": "Just Chatting",
+///           "message_count": 3,
+///           "top": [{ "value": "gaming1", "message_count": 2 }]
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GroupByResult {
+    /// Names of the fields the messages were grouped by, outermost first
+    pub group_by: Vec<String>,
+    /// Name of the ranked field
+    pub top_by: String,
+    /// Number of values kept per group (`None` for the full ranking)
+    pub limit: Option<usize>,
+    /// Groups of the first group-by field
+    pub groups: Vec<GroupNode>,
+}
+
+impl GroupByResult {
+    /// Returns the innermost groups with the values of their group-by fields,
+    /// outermost first, in result order.
+    #[must_use]
+    pub fn leaves(&self) -> Vec<(Vec<&str>, &GroupNode)> {
+        let mut leaves = Vec::new();
+        let mut path = Vec::new();
+        collect_leaves(&self.groups, &mut path, &mut leaves);
+        leaves
+    }
+}
+
+/// Appends the innermost groups below `groups` to `leaves`, `path` being the values
+/// of the enclosing groups.
+fn collect_leaves<'a>(
+    groups: &'a [GroupNode],
+    path: &mut Vec<&'a str>,
+    leaves: &mut Vec<(Vec<&'a str>, &'a GroupNode)>,
+) {
+    for group in groups {
+        path.push(&group.value);
+        if group.groups.is_empty() {
+            leaves.push((path.clone(), group));
+        } else {
+            collect_leaves(&group.groups, path, leaves);
+        }
+        path.pop();
+    }
+}
@@ -0,0 +1,19 @@
+use crate::value_count::ValueCount;
+
+/// One group of a group-by result: the messages with a given value of a group-by field.
+///
+/// Groups of every level but the last hold the nested groups of the next field; groups
+/// of the last level hold the ranking of the top-by field.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct GroupNode {
+    /// Value of the group-by field
+    pub value: String,
+    /// Number of messages in the group
+    pub message_count: u64,
+    /// Groups of the next group-by field (empty in the last level)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub groups: Vec<GroupNode>,
+    /// Top values of the ranked field (empty except in the last level)
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub top: Vec<ValueCount>,
+}
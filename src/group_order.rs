@@ -0,0 +1,37 @@
+use crate::custom_error::CustomError;
+use crate::language_order::LanguageOrder;
+
+/// Order of the groups at every level of a group-by result.
+///
+/// Groups of `viewerRange` always follow the order of the configured viewer ranges.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum GroupOrder {
+    /// By number of messages (descending), ties broken by value
+    #[default]
+    Volume,
+    /// By value (ascending; numerically for `viewerCount`)
+    Alphabetical,
+}
+
+impl From<LanguageOrder> for GroupOrder {
+    fn from(order: LanguageOrder) -> Self {
+        match order {
+            LanguageOrder::Volume => GroupOrder::Volume,
+            LanguageOrder::Alphabetical => GroupOrder::Alphabetical,
+        }
+    }
+}
+
+impl std::str::FromStr for GroupOrder {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "volume" => Ok(GroupOrder::Volume),
+            "alphabetical" => Ok(GroupOrder::Alphabetical),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Invalid group order '{s}'. Use: volume or alphabetical"
+            ))),
+        }
+    }
+}
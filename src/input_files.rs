@@ -0,0 +1,90 @@
+use crate::custom_error::CustomError;
+use std::path::Path;
+
+/// Expands the input paths given by the user into the list of files to analyze.
+///
+/// Each input may be:
+///
+/// * a directory, which expands to the regular files directly inside it (hidden
+///   files are skipped), sorted by name;
+/// * a glob pattern (containing `*`, `?` or `[`), which expands to the regular
+///   files it matches, sorted by path;
+/// * any other path, which is kept as is.
+///
+/// The order of the inputs is preserved and a file given more than once is only
+/// analyzed once.
+///
+/// # Errors
+///
+/// Returns `CustomError::ArgumentError` if no input is given, a glob pattern is
+/// invalid or a directory or pattern does not match any file, and
+/// `CustomError::IOError` if a directory cannot be read.
+pub fn resolve_input_files(inputs: &[String]) -> Result<Vec<String>, CustomError> {
+    if inputs.is_empty() {
+        return Err(CustomError::ArgumentError(
+            "At least one input file is required".to_string(),
+        ));
+    }
+
+    let mut files: Vec<String> = Vec::new();
+    for input in inputs {
+        let expanded = if Path::new(input).is_dir() {
+            list_directory(input)?
+        } else if is_glob_pattern(input) {
+            expand_glob(input)?
+        } else {
+            vec![input.clone()]
+        };
+        if expanded.is_empty() {
+            return Err(CustomError::ArgumentError(format!(
+                "No input files found in '{input}'"
+            )));
+        }
+        for file in expanded {
+            if !files.contains(&file) {
+                files.push(file);
+            }
+        }
+    }
+    Ok(files)
+}
+
+/// Returns true if `input` contains glob metacharacters.
+fn is_glob_pattern(input: &str) -> bool {
+    input.contains(['*', '?', '['])
+}
+
+/// Lists the regular, non-hidden files directly inside `dir`, sorted by name.
+fn list_directory(dir: &str) -> Result<Vec<String>, CustomError> {
+    let entries = std::fs::read_dir(dir).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|e| CustomError::IOError(e.to_string()))?
+            .path();
+        let hidden = path
+            .file_name()
+            .is_some_and(|name| name.to_string_lossy().starts_with('.'));
+        if path.is_file() && !hidden {
+            files.push(path.to_string_lossy().to_string());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Expands a glob pattern into the regular files it matches, sorted by path.
+fn expand_glob(pattern: &str) -> Result<Vec<String>, CustomError> {
+    let paths = glob::glob(pattern).map_err(|e| {
+        CustomError::ArgumentError(format!("Invalid glob pattern '{pattern}': {e}"))
+    })?;
+    let mut files = Vec::new();
+    for path in paths {
+        let path = path.map_err(|e| CustomError::IOError(e.to_string()))?;
+        if path.is_file() {
+            files.push(path.to_string_lossy().to_string());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
@@ -0,0 +1,34 @@
+use crate::custom_error::CustomError;
+
+/// Layout of the records in an input file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum InputFormat {
+    /// A JSON array of objects (or any other layout), parsed by counting braces
+    #[default]
+    JsonArray,
+    /// One JSON object per line (JSON Lines), parsed line by line
+    Ndjson,
+}
+
+impl std::str::FromStr for InputFormat {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "array" => Ok(InputFormat::JsonArray),
+            "ndjson" => Ok(InputFormat::Ndjson),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Invalid input format '{s}'. Use: array or ndjson"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for InputFormat {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            InputFormat::JsonArray => write!(f, "JSON array"),
+            InputFormat::Ndjson => write!(f, "NDJSON"),
+        }
+    }
+}
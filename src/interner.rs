@@ -0,0 +1,101 @@
+use std::collections::HashMap;
+use std::marker::PhantomData;
+
+/// Compact identifier handed out by an [`Interner`].
+///
+/// Implemented by the ID types of each kind of key (e.g., [`crate::channel_id::ChannelId`]
+/// and [`crate::language_id::LanguageId`]), so IDs of different kinds cannot be mixed up.
+pub trait InternId: Copy + Eq + std::hash::Hash {
+    /// Builds the ID for the `index`-th interned name.
+    fn from_index(index: u32) -> Self;
+
+    /// Returns the position of the name in the interner.
+    fn index(self) -> u32;
+}
+
+/// Maps names to compact integer IDs and back.
+///
+/// Each distinct name is stored once, and IDs are assigned in insertion order
+/// starting at zero. Looking up an existing name does not allocate.
+#[derive(Debug, Clone)]
+pub struct Interner<I> {
+    ids: HashMap<Box<str>, u32>,
+    names: Vec<Box<str>>,
+    kind: PhantomData<I>,
+}
+
+impl<I: InternId> Default for Interner<I> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<I: InternId> Interner<I> {
+    /// Creates an empty interner.
+    #[must_use]
+    pub fn new() -> Self {
+        Self {
+            ids: HashMap::new(),
+            names: Vec::new(),
+            kind: PhantomData,
+        }
+    }
+
+    /// Returns the ID of `name`, interning it if it has not been seen yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics if more than `u32::MAX` distinct names are interned.
+    pub fn intern(&mut self, name: &str) -> I {
+        if let Some(&index) = self.ids.get(name) {
+            return I::from_index(index);
+        }
+        let index = u32::try_from(self.names.len()).expect("too many interned names");
+        self.names.push(name.into());
+        self.ids.insert(name.into(), index);
+        I::from_index(index)
+    }
+
+    /// Returns the ID of `name` if it has been interned.
+    #[must_use]
+    pub fn get(&self, name: &str) -> Option<I> {
+        self.ids.get(name).map(|&index| I::from_index(index))
+    }
+
+    /// Returns the name of an ID handed out by this interner.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `id` was not produced by this interner.
+    #[must_use]
+    pub fn resolve(&self, id: I) -> &str {
+        &self.names[id.index() as usize]
+    }
+
+    /// Returns the number of interned names.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.names.len()
+    }
+
+    /// Returns whether no name has been interned yet.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.names.is_empty()
+    }
+
+    /// Interns every name of `other` and returns the translation of its IDs: the
+    /// `i`-th entry is the ID in this interner of the name with index `i` in `other`.
+    ///
+    /// Used to merge partial results whose maps are keyed by IDs of another interner.
+    pub fn absorb(&mut self, other: &Interner<I>) -> Vec<I> {
+        other.names.iter().map(|name| self.intern(name)).collect()
+    }
+
+    /// Iterates over the interned names and their IDs, in insertion order.
+    pub fn iter(&self) -> impl Iterator<Item = (I, &str)> {
+        (0u32..)
+            .zip(&self.names)
+            .map(|(index, name)| (I::from_index(index), name.as_ref()))
+    }
+}
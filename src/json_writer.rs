@@ -0,0 +1,26 @@
+use crate::analysis_report::AnalysisReport;
+use crate::custom_error::CustomError;
+use crate::report_writer::ReportWriter;
+use std::io::Write;
+
+/// Writes the report as a single JSON document (see `AnalysisReport` for the schema).
+pub struct JsonWriter {
+    /// Whether the JSON is indented over several lines
+    pub pretty: bool,
+}
+
+impl ReportWriter for JsonWriter {
+    fn write_report(
+        &self,
+        report: &AnalysisReport,
+        out: &mut dyn Write,
+    ) -> Result<(), CustomError> {
+        let result = if self.pretty {
+            serde_json::to_writer_pretty(&mut *out, report)
+        } else {
+            serde_json::to_writer(&mut *out, report)
+        };
+        result.map_err(|e| CustomError::TransformationError(e.to_string()))?;
+        writeln!(out).map_err(|e| CustomError::IOError(e.to_string()))
+    }
+}
@@ -0,0 +1,15 @@
+use crate::interner::InternId;
+
+/// Compact identifier of an interned language code.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct LanguageId(pub u32);
+
+impl InternId for LanguageId {
+    fn from_index(index: u32) -> Self {
+        LanguageId(index)
+    }
+
+    fn index(self) -> u32 {
+        self.0
+    }
+}
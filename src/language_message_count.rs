@@ -1,9 +1,10 @@
 /// Represents a language and its message count.
 ///
 /// Used to store the number of messages for a specific language.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct LanguageMessageCount {
     /// The language code (e.g., "en", "es", "fr")
     pub language: String,
     /// The total number of messages in this language
-    pub message_count: i32,
+    pub message_count: u64,
 }
@@ -0,0 +1,25 @@
+use crate::custom_error::CustomError;
+
+/// Order of the languages in the top channels by language ranking.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LanguageOrder {
+    /// By total number of messages (descending), ties broken by language code
+    #[default]
+    Volume,
+    /// By language code (ascending)
+    Alphabetical,
+}
+
+impl std::str::FromStr for LanguageOrder {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "volume" => Ok(LanguageOrder::Volume),
+            "alphabetical" => Ok(LanguageOrder::Alphabetical),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Invalid language order '{s}'. Use: volume or alphabetical"
+            ))),
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+
+/// Deserializes an optional field, treating a value of the wrong type as missing.
+///
+/// The value is still read (so malformed JSON is rejected as usual), but if it
+/// cannot be converted into `T` the field becomes `None` instead of failing the whole
+/// record. Meant for fields that no analysis requires, so an unrelated bad value does
+/// not turn a usable record into a skipped one.
+///
+/// Meant to be used with
+/// `#[serde(default, deserialize_with = "crate::lenient::deserialize_option")]`.
+///
+/// # Errors
+///
+/// Returns the deserializer's error only if the input itself is not valid JSON.
+pub fn deserialize_option<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: DeserializeOwned,
+{
+    let value = serde_json::Value::deserialize(deserializer)?;
+    Ok(Option::<T>::deserialize(value).ok().flatten())
+}
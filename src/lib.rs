@@ -1,10 +1,78 @@
+// The lint only fires on the crate name, which the course requires, and not when the
+// library is built as a test harness.
+#![cfg_attr(
+    not(test),
+    expect(
+        non_snake_case,
+        reason = "the crate name `TP0ProgramacionConcurrente` is not snake case"
+    )
+)]
+
+pub mod aggregation_results;
+pub mod aggregator;
+pub mod aggregator_handle;
+pub mod aggregator_set;
+pub mod analysis_config;
+pub mod analysis_report;
+pub mod analysis_type;
+mod boundary_window;
+pub mod channel_id;
 pub mod channel_message_count;
 pub mod chat_message;
+pub mod chat_message_ref;
+pub mod chat_record;
 pub mod chunk_info;
+pub mod chunk_parse_report;
+pub mod chunk_timings;
+pub mod cli_options;
+pub mod comparison_op;
+pub mod counting_reader;
+pub mod csv_writer;
 pub mod custom_error;
+pub mod filter_expr;
+pub mod filter_field;
+mod filter_parser;
+pub mod filter_value;
+pub mod group_by_aggregator;
+pub mod group_by_query;
+pub mod group_by_result;
+pub mod group_node;
+pub mod group_order;
+pub mod input_files;
+pub mod input_format;
+pub mod interner;
+pub mod json_writer;
+pub mod language_id;
 pub mod language_message_count;
+pub mod language_order;
+pub mod lenient;
+pub mod markdown_writer;
+mod object_scanner;
+pub mod output_format;
+pub mod parse_options;
 pub mod parser;
+pub mod query_field;
+pub mod record_batch;
+pub mod record_batch_reader;
+pub mod report_writer;
+pub mod run_metadata;
+pub mod skip_reason;
+pub mod skipped_record;
+pub mod skipped_records_summary;
 pub mod streaming_aggregators;
+pub mod text_writer;
+pub mod time_series_result;
+pub mod time_window;
+pub mod time_window_aggregator;
+pub mod time_window_result;
+pub mod timestamp;
+pub mod top_channels_aggregator;
 pub mod top_channels_result;
+pub mod top_languages_aggregator;
 pub mod top_languages_result;
 pub mod transformations;
+pub mod value_count;
+pub mod value_id;
+pub mod viewer_buckets;
+pub mod viewer_count;
+pub mod viewer_range;
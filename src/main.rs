@@ -1,100 +1,41 @@
 use ::std::env;
-use TP0ProgramacionConcurrente::transformations::{
-    analyze_both, top_channels_by_language, top_languages_by_viewer_range,
-};
-use std::time::Instant;
+use TP0ProgramacionConcurrente::cli_options::{CliOptions, USAGE};
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::report_writer::writer_for;
+use TP0ProgramacionConcurrente::transformations::run_analysis_on_inputs;
+use std::fs::File;
+use std::io::{BufWriter, Write};
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    if args.len() < 3 || args.len() > 4 {
-        eprintln!(
-            "Usage: {} <file_path> <num_threads> [analysis_type]",
-            args[0]
-        );
-        eprintln!("analysis_type: top_channels | top_languages | both (default: both)");
-        std::process::exit(1);
-    }
-
-    let file_path = &args[1];
-    let num_threads: usize = args[2]
-        .parse()
-        .expect("Please provide a valid number for threads");
-
-    let analysis_type = if args.len() == 4 { &args[3] } else { "both" };
-
-    let start = Instant::now();
+/// Runs the analysis described by `options` and writes the report.
+fn run(options: &CliOptions) -> Result<(), CustomError> {
+    let report = run_analysis_on_inputs(&options.inputs, options.analysis_type, &options.config)?;
+    let writer = writer_for(options.format);
 
-    match analysis_type {
-        "top_channels" => match top_channels_by_language(file_path, num_threads) {
-            Ok(results) => {
-                println!("\n=== TOP 3 CHANNELS BY LANGUAGE ===");
-                for result in results {
-                    println!("\nLanguage: {}", result.language);
-                    for (i, channel) in result.top_channels.iter().enumerate() {
-                        println!(
-                            "  {}. {}: {} messages",
-                            i + 1,
-                            channel.channel_name,
-                            channel.message_count
-                        );
-                    }
-                }
-            }
-            Err(e) => eprintln!("Error: {e}"),
-        },
-        "top_languages" => match top_languages_by_viewer_range(file_path, num_threads) {
-            Ok(results) => {
-                println!("\n=== TOP 5 LANGUAGES BY VIEWER RANGE ===");
-                for result in results {
-                    println!("\nViewer Range: {}", result.viewer_range);
-                    for (i, language) in result.top_languages.iter().enumerate() {
-                        println!(
-                            "  {}. {}: {} messages",
-                            i + 1,
-                            language.language,
-                            language.message_count
-                        );
-                    }
-                }
-            }
-            Err(e) => eprintln!("Error: {e}"),
-        },
-        "both" => match analyze_both(file_path, num_threads) {
-            Ok((top_channels, top_languages)) => {
-                println!("\n=== TOP 3 CHANNELS BY LANGUAGE ===");
-                for result in top_channels {
-                    println!("\nLanguage: {}", result.language);
-                    for (i, channel) in result.top_channels.iter().enumerate() {
-                        println!(
-                            "  {}. {}: {} messages",
-                            i + 1,
-                            channel.channel_name,
-                            channel.message_count
-                        );
-                    }
-                }
+    let mut out: Box<dyn Write> = match &options.output_path {
+        Some(path) => Box::new(BufWriter::new(
+            File::create(path).map_err(|e| CustomError::IOError(e.to_string()))?,
+        )),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    writer.write_report(&report, &mut out)?;
+    out.flush().map_err(|e| CustomError::IOError(e.to_string()))
+}
 
-                println!("\n=== TOP 5 LANGUAGES BY VIEWER RANGE ===");
-                for result in top_languages {
-                    println!("\nViewer Range: {}", result.viewer_range);
-                    for (i, language) in result.top_languages.iter().enumerate() {
-                        println!(
-                            "  {}. {}: {} messages",
-                            i + 1,
-                            language.language,
-                            language.message_count
-                        );
-                    }
-                }
-            }
-            Err(e) => eprintln!("Error: {e}"),
-        },
-        _ => {
-            eprintln!("Invalid analysis type. Use: top_channels, top_languages, or both");
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    let options = match CliOptions::parse(&args[1..]) {
+        Ok(options) => options,
+        Err(e) => {
+            eprintln!("{e}");
+            eprintln!("Usage: {} {USAGE}", args[0]);
+            eprintln!("analysis_type: top_channels | top_languages | both (default: both)");
+            eprintln!("group-by queries and time series run with --group-by and --window instead");
             std::process::exit(1);
         }
-    }
+    };
 
-    let duration = start.elapsed();
-    println!("\nTime elapsed: {duration:?}");
+    if let Err(e) = run(&options) {
+        eprintln!("Error: {e}");
+        std::process::exit(1);
+    }
 }
@@ -0,0 +1,167 @@
+use crate::analysis_report::AnalysisReport;
+use crate::custom_error::CustomError;
+use crate::report_writer::ReportWriter;
+use std::io::Write;
+
+/// Writes the report as a Markdown document with a summary and one table per group.
+pub struct MarkdownWriter;
+
+impl ReportWriter for MarkdownWriter {
+    fn write_report(
+        &self,
+        report: &AnalysisReport,
+        out: &mut dyn Write,
+    ) -> Result<(), CustomError> {
+        write_markdown(report, out).map_err(|e| CustomError::IOError(e.to_string()))
+    }
+}
+
+fn write_markdown(report: &AnalysisReport, out: &mut dyn Write) -> std::io::Result<()> {
+    let metadata = &report.metadata;
+
+    writeln!(out, "# Twitch Chat Analysis Report")?;
+    writeln!(out)?;
+    writeln!(out, "| Field | Value |")?;
+    writeln!(out, "|-------|-------|")?;
+    writeln!(
+        out,
+        "| Input file | `{}` |",
+        escape_cell(&metadata.input_file)
+    )?;
+    if metadata.input_files.len() > 1 {
+        writeln!(out, "| Files analyzed | {} |", metadata.input_files.len())?;
+    }
+    if let Some(filter) = &metadata.filter {
+        writeln!(out, "| Filter | `{}` |", escape_cell(filter))?;
+    }
+    writeln!(out, "| Threads | {} |", metadata.num_threads)?;
+    writeln!(out, "| Chunks | {} |", metadata.num_chunks)?;
+    writeln!(out, "| Chunk timings | {} |", metadata.chunk_timings)?;
+    writeln!(out, "| Total messages | {} |", metadata.total_messages)?;
+    if metadata.filter.is_some() {
+        writeln!(out, "| Matched messages | {} |", metadata.matched_messages)?;
+    }
+    writeln!(out, "| Malformed records | {} |", metadata.skipped_records)?;
+    writeln!(out, "| Elapsed | {:.3} s |", metadata.elapsed_seconds)?;
+
+    if let Some(top_channels) = &report.top_channels_by_language {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "## {}",
+            section_title(metadata.top_channels_limit, "channels by language")
+        )?;
+        for result in top_channels {
+            writeln!(out)?;
+            writeln!(out, "### {}", escape_cell(&result.language))?;
+            writeln!(out)?;
+            writeln!(out, "| Rank | Channel | Messages |")?;
+            writeln!(out, "|-----:|---------|---------:|")?;
+            for (i, channel) in result.top_channels.iter().enumerate() {
+                writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    i + 1,
+                    escape_cell(&channel.channel_name),
+                    channel.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(top_languages) = &report.top_languages_by_viewer_range {
+        writeln!(out)?;
+        writeln!(
+            out,
+            "## {}",
+            section_title(metadata.top_languages_limit, "languages by viewer range")
+        )?;
+        for result in top_languages {
+            writeln!(out)?;
+            writeln!(out, "### {} viewers", escape_cell(&result.viewer_range))?;
+            writeln!(out)?;
+            writeln!(out, "| Rank | Language | Messages |")?;
+            writeln!(out, "|-----:|----------|---------:|")?;
+            for (i, language) in result.top_languages.iter().enumerate() {
+                writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    i + 1,
+                    escape_cell(&language.language),
+                    language.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(group_by) = &report.group_by {
+        writeln!(out)?;
+        let subject = format!("{} by {}", group_by.top_by, group_by.group_by.join(", "));
+        writeln!(out, "## {}", section_title(group_by.limit, &subject))?;
+        for (path, group) in group_by.leaves() {
+            writeln!(out)?;
+            writeln!(out, "### {}", escape_cell(&path.join(" / ")))?;
+            writeln!(out)?;
+            writeln!(out, "| Rank | {} | Messages |", group_by.top_by)?;
+            writeln!(out, "|-----:|------|---------:|")?;
+            for (i, value) in group.top.iter().enumerate() {
+                writeln!(
+                    out,
+                    "| {} | {} | {} |",
+                    i + 1,
+                    escape_cell(&value.value),
+                    value.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(time_series) = &report.time_series {
+        writeln!(out)?;
+        let subject = format!("channels by language per {}", time_series.window);
+        writeln!(
+            out,
+            "## {}",
+            section_title(metadata.top_channels_limit, &subject)
+        )?;
+        for window in &time_series.windows {
+            writeln!(out)?;
+            writeln!(
+                out,
+                "### {} to {} ({} messages)",
+                window.window_start, window.window_end, window.message_count
+            )?;
+            for result in &window.top_channels_by_language {
+                writeln!(out)?;
+                writeln!(out, "#### {}", escape_cell(&result.language))?;
+                writeln!(out)?;
+                writeln!(out, "| Rank | Channel | Messages |")?;
+                writeln!(out, "|-----:|---------|---------:|")?;
+                for (i, channel) in result.top_channels.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "| {} | {} | {} |",
+                        i + 1,
+                        escape_cell(&channel.channel_name),
+                        channel.message_count
+                    )?;
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Builds a section title, e.g. "Top 3 channels by language".
+fn section_title(limit: Option<usize>, subject: &str) -> String {
+    match limit {
+        Some(limit) => format!("Top {limit} {subject}"),
+        None => format!("All {subject}"),
+    }
+}
+
+/// Escapes characters that would break a Markdown table cell.
+fn escape_cell(value: &str) -> String {
+    value.replace('|', "\\|").replace('\n', " ")
+}
@@ -0,0 +1,66 @@
+/// Role of a byte in the input, as reported by [`ObjectScanner::feed`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ScanEvent {
+    /// The byte is outside of any object (separators, whitespace, array brackets)
+    Outside,
+    /// The byte is the opening brace of a top-level object
+    ObjectStart,
+    /// The byte belongs to the top-level object being scanned
+    Inside,
+    /// The byte is the closing brace of the top-level object
+    ObjectEnd,
+}
+
+/// String-aware brace counter that finds top-level JSON objects in a byte stream.
+///
+/// Braces inside string values (including escaped quotes) are ignored, so the
+/// scanner is shared by the chunk boundary finder and the chunk parsers.
+#[derive(Debug, Default)]
+pub(crate) struct ObjectScanner {
+    depth: usize,
+    in_string: bool,
+    escape_next: bool,
+}
+
+impl ObjectScanner {
+    /// Advances the scanner by one byte and reports the role of that byte.
+    pub(crate) fn feed(&mut self, byte: u8) -> ScanEvent {
+        if self.escape_next {
+            self.escape_next = false;
+        } else if self.in_string {
+            match byte {
+                b'\\' => self.escape_next = true,
+                b'"' => self.in_string = false,
+                _ => {}
+            }
+        } else {
+            match byte {
+                b'"' => self.in_string = true,
+                b'{' => {
+                    self.depth += 1;
+                    if self.depth == 1 {
+                        return ScanEvent::ObjectStart;
+                    }
+                }
+                b'}' if self.depth > 0 => {
+                    self.depth -= 1;
+                    if self.depth == 0 {
+                        return ScanEvent::ObjectEnd;
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        if self.depth > 0 {
+            ScanEvent::Inside
+        } else {
+            ScanEvent::Outside
+        }
+    }
+
+    /// Returns whether the scanner is in the middle of a top-level object.
+    pub(crate) fn in_object(&self) -> bool {
+        self.depth > 0
+    }
+}
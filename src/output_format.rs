@@ -0,0 +1,33 @@
+use crate::custom_error::CustomError;
+
+/// Format used to write the analysis report.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable text
+    Text,
+    /// JSON document, pretty-printed or compact
+    Json {
+        /// Whether the JSON is indented over several lines
+        pretty: bool,
+    },
+    /// CSV rows, one table per ranking
+    Csv,
+    /// Markdown report
+    Markdown,
+}
+
+impl std::str::FromStr for OutputFormat {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(OutputFormat::Text),
+            "json" => Ok(OutputFormat::Json { pretty: true }),
+            "csv" => Ok(OutputFormat::Csv),
+            "markdown" => Ok(OutputFormat::Markdown),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Invalid output format '{s}'. Use: text, json, csv or markdown"
+            ))),
+        }
+    }
+}
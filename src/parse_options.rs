@@ -0,0 +1,12 @@
+use crate::input_format::InputFormat;
+
+/// Options controlling how chunks are parsed.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct ParseOptions {
+    /// Keep the raw text of skipped records (needed to write a reject file)
+    pub keep_rejected: bool,
+    /// Fail with `CustomError::ParseError` on the first malformed record instead of skipping it
+    pub strict: bool,
+    /// Layout of the records in the file
+    pub input_format: InputFormat,
+}
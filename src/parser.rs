@@ -1,17 +1,102 @@
+use crate::boundary_window::BoundaryWindow;
 use crate::chat_message::ChatMessage;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
 use crate::chunk_info::ChunkInfo;
+use crate::chunk_parse_report::ChunkParseReport;
 use crate::custom_error::CustomError;
+use crate::input_format::InputFormat;
+use crate::object_scanner::{ObjectScanner, ScanEvent};
+use crate::parse_options::ParseOptions;
+use crate::record_batch::RecordBatch;
+use crate::skip_reason::SkipReason;
+use crate::skipped_record::SkippedRecord;
+use memmap2::Mmap;
 use std::fs::File;
-use std::io::{BufReader, Read, Seek, SeekFrom};
+use std::io::{BufRead, BufReader, Read, Seek, SeekFrom};
+
+/// Largest record, in bytes, accepted as a chunk boundary candidate.
+pub(crate) const MAX_BOUNDARY_RECORD_SIZE: u64 = 1_048_576;
+
+/// First two bytes of every gzip member.
+pub const GZIP_MAGIC: [u8; 2] = [0x1f, 0x8b];
+
+/// Returns true if the file starts with the gzip magic bytes.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read.
+pub fn is_gzip_file(path: &str) -> Result<bool, CustomError> {
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut magic = Vec::with_capacity(GZIP_MAGIC.len());
+    file.take(GZIP_MAGIC.len() as u64)
+        .read_to_end(&mut magic)
+        .map_err(|e| CustomError::IOError(e.to_string()))?;
+    Ok(magic == GZIP_MAGIC)
+}
+
+/// Detects whether a file is a JSON array or NDJSON (one object per line).
+///
+/// A file whose first non-whitespace character is `{` and whose first line holds a
+/// complete object (ends with `}`) is NDJSON. Anything else, including a single-line
+/// sequence of objects, is handled by the brace-counting JSON array parser.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read.
+pub fn detect_input_format(path: &str) -> Result<InputFormat, CustomError> {
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut first_line = Vec::new();
+    BufReader::new(file)
+        .take(MAX_BOUNDARY_RECORD_SIZE)
+        .read_until(b'\n', &mut first_line)
+        .map_err(|e| CustomError::IOError(e.to_string()))?;
+    Ok(detect_format_from_first_line(&first_line))
+}
+
+/// Detects the input format from the first line of the input (see [`detect_input_format`]).
+pub(crate) fn detect_format_from_first_line(first_line: &[u8]) -> InputFormat {
+    let first_line = first_line
+        .strip_prefix(b"\xEF\xBB\xBF")
+        .unwrap_or(first_line)
+        .trim_ascii();
+    if first_line.starts_with(b"{") && first_line.ends_with(b"}") {
+        InputFormat::Ndjson
+    } else {
+        InputFormat::JsonArray
+    }
+}
 
 /// Analyzes a file and divides it into chunks for parallel processing.
 ///
+/// The input format is detected with [`detect_input_format`].
+///
 /// # Errors
 ///
 /// Returns `CustomError::IOError` if the file cannot be opened or read.
 pub fn analize_file_for_chunks(
     path: &str,
     num_of_threads: usize,
+) -> Result<Vec<ChunkInfo>, CustomError> {
+    let input_format = detect_input_format(path)?;
+    analize_file_for_chunks_with_format(path, num_of_threads, input_format)
+}
+
+/// Analyzes a file with a known input format and divides it into chunks for parallel
+/// processing.
+///
+/// NDJSON chunks end right after a newline, so every chunk holds whole lines. For a
+/// JSON array, every chunk except the first starts at the opening brace of a record.
+/// Either way no record is split between two chunks; the chunks are contiguous and
+/// never overlap, and when no further boundary exists the remaining chunks are empty.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read.
+pub fn analize_file_for_chunks_with_format(
+    path: &str,
+    num_of_threads: usize,
+    input_format: InputFormat,
 ) -> Result<Vec<ChunkInfo>, CustomError> {
     let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
     let metadata = file
@@ -26,14 +111,20 @@ pub fn analize_file_for_chunks(
 
     let mut start = 0;
     for i in 0..num_of_threads {
-        let mut end = start + chunk_size;
-        if i == num_of_threads - 1 {
-            end = file_size;
+        let end = if i == num_of_threads - 1 {
+            file_size
         } else {
-            end = find_next_object_boundary(&mut buf_reader, end)?;
-        }
+            let target = (start + chunk_size).max(start + 1);
+            match input_format {
+                InputFormat::JsonArray => {
+                    find_next_object_boundary(&mut buf_reader, target, file_size)?
+                }
+                InputFormat::Ndjson => find_next_line_boundary(&mut buf_reader, target, file_size)?,
+            }
+        };
 
         chunks.push(ChunkInfo {
+            file_id: 0,
             start,
             end,
             size: end.saturating_sub(start),
@@ -45,19 +136,151 @@ pub fn analize_file_for_chunks(
     Ok(chunks)
 }
 
-/// Finds the next JSON object boundary starting from a given position.
+/// Divides several files into chunks that can be processed together in one parallel job.
+///
+/// The chunks are spread over the files in proportion to their size, so that all
+/// chunks have roughly `total_size / num_chunks` bytes, and every file gets at least
+/// one chunk. Each chunk carries the index of its file in `paths` as `file_id`, and
+/// `input_formats[i]` is the layout of `paths[i]`. Empty chunks, left when a file is
+/// too small for its share of chunks, are dropped.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if a file cannot be opened or read.
+pub fn analize_files_for_chunks(
+    paths: &[String],
+    input_formats: &[InputFormat],
+    num_chunks: usize,
+) -> Result<Vec<ChunkInfo>, CustomError> {
+    let sizes = paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .map(|metadata| metadata.len())
+                .map_err(|e| CustomError::IOError(e.to_string()))
+        })
+        .collect::<Result<Vec<u64>, CustomError>>()?;
+    let total_size: u64 = sizes.iter().sum();
+
+    let mut chunks = Vec::new();
+    for (file_id, ((path, &input_format), &size)) in
+        paths.iter().zip(input_formats).zip(&sizes).enumerate()
+    {
+        let file_chunks = if paths.len() == 1 {
+            num_chunks
+        } else {
+            let share = (num_chunks as u64 * size).div_ceil(total_size.max(1));
+            usize::try_from(share).unwrap_or(num_chunks).max(1)
+        };
+        let mut file_chunks = analize_file_for_chunks_with_format(path, file_chunks, input_format)?;
+        for chunk in &mut file_chunks {
+            chunk.file_id = file_id;
+        }
+        chunks.extend(file_chunks.into_iter().filter(|chunk| chunk.size > 0));
+    }
+
+    Ok(chunks)
+}
+
+/// Splits a chunk in two at the first record boundary after its midpoint.
+///
+/// Used for adaptive splitting: the halves are contiguous, never split a record and
+/// keep the `file_id` of `chunk`. Returns `None` if the chunk holds no boundary after
+/// its midpoint (for example, a single large record).
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read.
+pub fn split_chunk(
+    path: &str,
+    chunk: &ChunkInfo,
+    input_format: InputFormat,
+) -> Result<Option<(ChunkInfo, ChunkInfo)>, CustomError> {
+    if chunk.size < 2 {
+        return Ok(None);
+    }
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut buf_reader = BufReader::new(&file);
+    let target = chunk.start + chunk.size / 2;
+    let boundary = match input_format {
+        InputFormat::JsonArray => find_next_object_boundary(&mut buf_reader, target, chunk.end)?,
+        InputFormat::Ndjson => find_next_line_boundary(&mut buf_reader, target, chunk.end)?,
+    };
+    if boundary <= chunk.start || boundary >= chunk.end {
+        return Ok(None);
+    }
+
+    let first = ChunkInfo {
+        file_id: chunk.file_id,
+        start: chunk.start,
+        end: boundary,
+        size: boundary - chunk.start,
+    };
+    let second = ChunkInfo {
+        file_id: chunk.file_id,
+        start: boundary,
+        end: chunk.end,
+        size: chunk.end - boundary,
+    };
+    Ok(Some((first, second)))
+}
+
+/// Finds the first line start at or after a given position.
+///
+/// # Arguments
+///
+/// * `buf_reader` - Mutable reference to the buffered file reader
+/// * `start_pos` - Starting position in the file to search from
+/// * `file_size` - Size of the file in bytes
+///
+/// # Returns
+///
+/// Returns the position of the first line start at or after `start_pos`, or
+/// `file_size` if there is none.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if file seeking or reading operations fail.
+fn find_next_line_boundary(
+    buf_reader: &mut BufReader<&File>,
+    start_pos: u64,
+    file_size: u64,
+) -> Result<u64, CustomError> {
+    let search_from = start_pos.saturating_sub(1);
+    buf_reader
+        .seek(SeekFrom::Start(search_from))
+        .map_err(|e| CustomError::IOError(e.to_string()))?;
+    let skipped = buf_reader
+        .skip_until(b'\n')
+        .map_err(|e| CustomError::IOError(e.to_string()))?;
+
+    Ok((search_from + skipped as u64).min(file_size))
+}
+
+/// Finds the next JSON record boundary starting from a given position.
 ///
-/// This function searches for the end of a JSON object ('}' followed by ',' or ']')
-/// to ensure chunk boundaries don't split JSON objects, enabling safe parallel processing.
+/// The search position may fall anywhere, including inside a string value, so the
+/// function resynchronises on candidate opening braces: a `{` preceded by `,`, `[` or
+/// `}` (or with only whitespace before it since `start_pos`). A candidate is accepted
+/// only if the text from there is a complete record (braces matched outside of
+/// strings) that deserializes into a [`ChatMessage`] and is followed by `,`, `]`, `{`
+/// or the end of the searched range. Braces inside string values (e.g.,
+/// `"text": "},{"`) can never produce an accepted candidate, because the keys of such
+/// an object would need unescaped quotes.
+///
+/// The range is read through a [`BoundaryWindow`], so candidates are checked in
+/// memory, and only candidates that pass the cheap structural checks are deserialized.
 ///
 /// # Arguments
 ///
 /// * `buf_reader` - Mutable reference to the buffered file reader
 /// * `start_pos` - Starting position in the file to search from
+/// * `file_size` - End of the searched range (usually the size of the file)
 ///
 /// # Returns
 ///
-/// Returns the position of the next safe boundary for chunk splitting.
+/// Returns the position of the opening brace of the next record, or `file_size`
+/// if there is no record after `start_pos`.
 ///
 /// # Errors
 ///
@@ -65,74 +288,257 @@ pub fn analize_file_for_chunks(
 fn find_next_object_boundary(
     buf_reader: &mut BufReader<&File>,
     start_pos: u64,
+    file_size: u64,
 ) -> Result<u64, CustomError> {
     buf_reader
         .seek(SeekFrom::Start(start_pos))
         .map_err(|e| CustomError::IOError(e.to_string()))?;
-
+    let mut window =
+        BoundaryWindow::new(buf_reader, start_pos, file_size.saturating_sub(start_pos));
     let mut position = start_pos;
-    let mut buffer = vec![0u8; 1_048_576].into_boxed_slice();
+    let mut previous = None;
 
     loop {
-        let bytes_read = buf_reader
-            .read(&mut buffer)
-            .map_err(|e| CustomError::IOError(e.to_string()))?;
-        if bytes_read == 0 {
-            break;
+        let bytes = window.get(position, 1)?;
+        if bytes.is_empty() {
+            return Ok(file_size);
+        }
+        let mut candidate = None;
+        for (index, &byte) in bytes.iter().enumerate() {
+            if byte == b'{' && matches!(previous, None | Some(b',' | b'[' | b'}')) {
+                candidate = Some(index);
+                break;
+            }
+            if !byte.is_ascii_whitespace() {
+                previous = Some(byte);
+            }
         }
 
-        for i in 0..bytes_read {
-            if buffer[i] == b'}'
-                && i + 1 < bytes_read
-                && (buffer[i + 1] == b',' || buffer[i + 1] == b']')
-            {
-                return Ok(position + i as u64 + 1);
+        match candidate {
+            None => position += bytes.len() as u64,
+            Some(index) => {
+                let candidate_pos = position + index as u64;
+                if is_record_at(&mut window, candidate_pos)? {
+                    return Ok(candidate_pos);
+                }
+                previous = Some(b'{');
+                position = candidate_pos + 1;
             }
         }
+    }
+}
 
-        position += bytes_read as u64;
+/// Checks whether the candidate opening brace at `position` starts a record.
+///
+/// The candidate is first checked with an [`ObjectScanner`]: its first key must
+/// follow the brace, the object must close within [`MAX_BOUNDARY_RECORD_SIZE`] bytes
+/// and be followed by a valid separator. Only then is it deserialized.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if reading from the file fails.
+fn is_record_at<R: Read>(
+    window: &mut BoundaryWindow<R>,
+    position: u64,
+) -> Result<bool, CustomError> {
+    let max_len = usize::try_from(MAX_BOUNDARY_RECORD_SIZE).unwrap_or(usize::MAX);
+    let mut scanner = ObjectScanner::default();
+    let mut scanned = 0;
+    let mut wanted = 4096;
+    let end = 'scan: loop {
+        let bytes = window.get(position, wanted)?;
+        if scanned == 0
+            && bytes[1..]
+                .iter()
+                .find(|byte| !byte.is_ascii_whitespace())
+                .is_some_and(|byte| !matches!(byte, b'"' | b'}'))
+        {
+            return Ok(false);
+        }
+        for (index, &byte) in bytes.iter().enumerate().skip(scanned) {
+            if index >= max_len {
+                return Ok(false);
+            }
+            if scanner.feed(byte) == ScanEvent::ObjectEnd {
+                break 'scan index + 1;
+            }
+        }
+        if bytes.len() < wanted {
+            // The range ends inside the candidate.
+            return Ok(false);
+        }
+        scanned = bytes.len();
+        wanted = bytes.len().saturating_mul(2);
+    };
 
-        if position > start_pos + 1_048_576 {
-            return Ok(start_pos);
+    let mut wanted = end + 64;
+    let separator = loop {
+        let bytes = window.get(position, wanted)?;
+        if let Some(&byte) = bytes[end..].iter().find(|byte| !byte.is_ascii_whitespace()) {
+            break Some(byte);
+        }
+        if bytes.len() < wanted {
+            break None;
         }
+        wanted = bytes.len().saturating_mul(2);
+    };
+    if !matches!(separator, None | Some(b',' | b']' | b'{')) {
+        return Ok(false);
     }
 
-    Ok(position)
+    let record = &window.get(position, end)?[..end];
+    Ok(serde_json::from_slice::<ChatMessageRef>(record).is_ok())
 }
 
 /// Parses a chunk of a JSON file in streaming mode, calling a callback for each valid message.
 ///
+/// Malformed records are skipped; use [`parse_chunk_with_report`] to find out which ones.
+///
 /// # Errors
 ///
 /// Returns `CustomError::IOError` if the file cannot be opened or read.
 pub fn parse_chunk_streaming<F>(
     path: &str,
     chunk: &ChunkInfo,
+    callback: F,
+) -> Result<u64, CustomError>
+where
+    F: FnMut(ChatMessage),
+{
+    parse_chunk_with_report(path, chunk, ParseOptions::default(), callback)
+        .map(|report| report.processed_count)
+}
+
+/// Parses a chunk of a JSON file in streaming mode, calling a callback for each valid message
+/// and reporting every malformed record that was skipped.
+///
+/// Each skipped record carries the byte offset where it starts and the reason it could
+/// not be deserialized. An object left incomplete at the end of the chunk is reported
+/// as invalid JSON. With `options.strict`, the first malformed record aborts the parse.
+///
+/// Records are accumulated as raw bytes and deserialized with `serde_json::from_slice`,
+/// so multi-byte UTF-8 text (e.g., Cyrillic or Japanese channel names) is kept intact.
+/// With `options.input_format` set to NDJSON, the chunk is parsed line by line instead
+/// and skipped records also carry their line number.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to parse
+/// * `chunk` - Byte range of the file to parse
+/// * `options` - Parsing options (e.g., whether to keep the raw text of skipped records)
+/// * `callback` - Function called with every valid message
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read, or
+/// `CustomError::ParseError` with the byte offset and a snippet of the record if
+/// `options.strict` is set and a malformed record is found.
+pub fn parse_chunk_with_report<F>(
+    path: &str,
+    chunk: &ChunkInfo,
+    options: ParseOptions,
     mut callback: F,
-) -> Result<usize, CustomError>
+) -> Result<ChunkParseReport, CustomError>
 where
     F: FnMut(ChatMessage),
 {
+    parse_chunk_borrowed(path, chunk, options, |message| {
+        callback(message.into_owned());
+    })
+}
+
+/// Parses a chunk like [`parse_chunk_with_report`], but passes every valid message to the
+/// callback as a [`ChatMessageRef`] borrowing from the parser's read buffer.
+///
+/// No string is allocated for a message unless its fields contain escape sequences, so
+/// the callback only pays for the keys it decides to keep. The borrowed message is only
+/// valid during the callback call.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read, or
+/// `CustomError::ParseError` with the byte offset and a snippet of the record if
+/// `options.strict` is set and a malformed record is found.
+pub fn parse_chunk_borrowed<F>(
+    path: &str,
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    mut callback: F,
+) -> Result<ChunkParseReport, CustomError>
+where
+    F: FnMut(ChatMessageRef<'_>),
+{
+    parse_chunk_records(path, chunk, options, |record| {
+        callback(serde_json::from_slice(record)?);
+        Ok(())
+    })
+}
+
+/// Parses a chunk like [`parse_chunk_with_report`], but deserializes every record as a
+/// full [`ChatRecord`] with all the fields of the dataset.
+///
+/// A record is only valid if, besides deserializing, it has the fields required by
+/// [`ChatMessage`] (`channelName`, `language` and `viewerCount`); otherwise it is
+/// skipped as a missing field, exactly like in the other parse functions. Full records
+/// are noticeably slower to deserialize, so only use this when those fields are needed.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read, or
+/// `CustomError::ParseError` with the byte offset and a snippet of the record if
+/// `options.strict` is set and a malformed record is found.
+pub fn parse_chunk_full<F>(
+    path: &str,
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    mut callback: F,
+) -> Result<ChunkParseReport, CustomError>
+where
+    F: FnMut(ChatRecord),
+{
+    parse_chunk_records(path, chunk, options, |record| {
+        callback(decode_full_record(record)?);
+        Ok(())
+    })
+}
+
+/// Finds the records of a chunk of a file and passes their raw bytes to `decode`.
+///
+/// A record is counted as processed if `decode` succeeds, and skipped with the
+/// reason of its error otherwise.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read, or
+/// `CustomError::ParseError` if `options.strict` is set and a record is malformed.
+fn parse_chunk_records<D>(
+    path: &str,
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    mut decode: D,
+) -> Result<ChunkParseReport, CustomError>
+where
+    D: FnMut(&[u8]) -> Result<(), serde_json::Error>,
+{
+    if options.input_format == InputFormat::Ndjson {
+        return parse_ndjson_chunk(path, chunk, options, decode);
+    }
+
     let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
     let mut buf_reader = BufReader::new(&file);
     buf_reader
         .seek(SeekFrom::Start(chunk.start))
         .map_err(|e| CustomError::IOError(e.to_string()))?;
 
-    let mut processed_count = 0;
-    let mut current_object = String::new();
-    let mut brace_count = 0;
+    let mut report = ChunkParseReport::default();
+    let mut scanner = ObjectScanner::default();
+    let mut current_object: Vec<u8> = Vec::new();
+    let mut object_start = chunk.start;
     let mut bytes_read = 0;
-    let mut in_string = false;
-    let mut escape_next = false;
 
     let mut buffer = vec![0u8; 65536];
 
-    loop {
-        if bytes_read >= chunk.size {
-            break;
-        }
-
+    while bytes_read < chunk.size {
         let remaining_bytes = usize::try_from((chunk.size - bytes_read).min(buffer.len() as u64))
             .unwrap_or(buffer.len());
         let bytes_in_buffer = buf_reader
@@ -143,61 +549,506 @@ where
             break;
         }
 
-        for &byte in buffer.iter().take(bytes_in_buffer) {
-            let ch = byte as char;
+        for &byte in &buffer[..bytes_in_buffer] {
+            match scanner.feed(byte) {
+                ScanEvent::Outside => {}
+                ScanEvent::ObjectStart => {
+                    current_object.clear();
+                    current_object.push(byte);
+                    object_start = chunk.start + bytes_read;
+                }
+                ScanEvent::Inside => current_object.push(byte),
+                ScanEvent::ObjectEnd => {
+                    current_object.push(byte);
+                    process_record(
+                        &mut report,
+                        options,
+                        &current_object,
+                        object_start,
+                        || Ok(None),
+                        &mut decode,
+                    )?;
+                    current_object.clear();
+                }
+            }
             bytes_read += 1;
+        }
+    }
 
-            if escape_next {
-                current_object.push(ch);
-                escape_next = false;
-                continue;
-            }
+    if scanner.in_object() {
+        skip_truncated_record(&mut report, options, &current_object, object_start)?;
+    }
 
-            if ch == '\\' {
-                escape_next = true;
-                current_object.push(ch);
-                continue;
-            }
+    Ok(report)
+}
 
-            if ch == '"' {
-                in_string = !in_string;
-                current_object.push(ch);
-                continue;
-            }
+/// Parses a chunk of an NDJSON file line by line.
+///
+/// Blank lines are ignored. The line number of a malformed record is resolved from
+/// the number of lines before the chunk, which is only counted once a record fails.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read, or
+/// `CustomError::ParseError` if `options.strict` is set and a malformed line is found.
+fn parse_ndjson_chunk<D>(
+    path: &str,
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    mut decode: D,
+) -> Result<ChunkParseReport, CustomError>
+where
+    D: FnMut(&[u8]) -> Result<(), serde_json::Error>,
+{
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut buf_reader = BufReader::with_capacity(65536, file);
+    buf_reader
+        .seek(SeekFrom::Start(chunk.start))
+        .map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut lines = buf_reader.take(chunk.size);
 
-            if !in_string {
-                if (ch == '[' || ch == ']' || ch == ',') && brace_count == 0 {
-                    continue;
-                }
+    let mut report = ChunkParseReport::default();
+    let mut line = Vec::new();
+    let mut line_start = chunk.start;
+    let mut line_index = 0;
+    let mut first_line_number = None;
 
-                if ch == '{' {
-                    brace_count += 1;
-                } else if ch == '}' {
-                    brace_count -= 1;
-                }
-            }
+    loop {
+        line.clear();
+        let bytes_in_line = lines
+            .read_until(b'\n', &mut line)
+            .map_err(|e| CustomError::IOError(e.to_string()))?;
+        if bytes_in_line == 0 {
+            break;
+        }
 
-            if brace_count > 0 || (ch == '}' && !current_object.is_empty()) {
-                current_object.push(ch);
-            }
+        let record = line.trim_ascii();
+        if !record.is_empty() {
+            let indent = line.len() - line.trim_ascii_start().len();
+            process_record(
+                &mut report,
+                options,
+                record,
+                line_start + indent as u64,
+                || {
+                    let first_line = match first_line_number {
+                        Some(number) => number,
+                        None => {
+                            *first_line_number.insert(count_lines_before(path, chunk.start)? + 1)
+                        }
+                    };
+                    Ok(Some(first_line + line_index))
+                },
+                &mut decode,
+            )?;
+        }
 
-            if brace_count == 0
-                && !current_object.trim().is_empty()
-                && current_object.trim().starts_with('{')
-            {
-                if let Ok(message) = serde_json::from_str::<ChatMessage>(current_object.trim()) {
-                    callback(message);
-                    processed_count += 1;
-                }
+        line_start += bytes_in_line as u64;
+        line_index += 1;
+    }
 
-                current_object.clear();
-            }
+    Ok(report)
+}
 
-            if bytes_read >= chunk.size {
-                break;
+/// Counts the newlines in the first `offset` bytes of a file.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or read.
+fn count_lines_before(path: &str, offset: u64) -> Result<u64, CustomError> {
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut reader = BufReader::new(file).take(offset);
+    let mut buffer = vec![0u8; 65536];
+    let mut lines = 0;
+
+    loop {
+        let bytes_read = reader
+            .read(&mut buffer)
+            .map_err(|e| CustomError::IOError(e.to_string()))?;
+        if bytes_read == 0 {
+            break;
+        }
+        lines += count_newlines(&buffer[..bytes_read]);
+    }
+
+    Ok(lines)
+}
+
+/// Counts the newline bytes in a slice.
+fn count_newlines(bytes: &[u8]) -> u64 {
+    bytes.iter().filter(|&&b| b == b'\n').count() as u64
+}
+
+/// Maps a file into memory, for use with [`parse_chunk_from_slice`].
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be opened or mapped.
+pub fn map_file(path: &str) -> Result<Mmap, CustomError> {
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    // SAFETY: the mapping is only read. As with the buffered path, the file must not be
+    // truncated or modified while it is being analyzed.
+    unsafe { Mmap::map(&file) }.map_err(|e| CustomError::IOError(e.to_string()))
+}
+
+/// Parses a chunk directly from the contents of the whole file, calling a callback for each
+/// valid message and reporting every malformed record that was skipped.
+///
+/// This is the memory-mapped counterpart of [`parse_chunk_borrowed`]: `data` is usually
+/// a [`map_file`] mapping shared by all workers, and records are deserialized straight from
+/// it without being copied into an intermediate buffer. The messages borrow from `data`,
+/// so they may outlive the callback call. Offsets in `chunk` and in the skipped records
+/// are positions in `data`.
+///
+/// # Arguments
+///
+/// * `data` - Contents of the whole file
+/// * `chunk` - Byte range of `data` to parse
+/// * `options` - Parsing options (e.g., whether to keep the raw text of skipped records)
+/// * `callback` - Function called with every valid message
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if the chunk lies outside of `data`, or with the byte
+/// offset and a snippet of the record if `options.strict` is set and a malformed record
+/// is found.
+pub fn parse_chunk_from_slice<'a, F>(
+    data: &'a [u8],
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    mut callback: F,
+) -> Result<ChunkParseReport, CustomError>
+where
+    F: FnMut(ChatMessageRef<'a>),
+{
+    parse_slice_records(data, chunk, options, |record| {
+        callback(serde_json::from_slice(record)?);
+        Ok(())
+    })
+}
+
+/// Parses a chunk like [`parse_chunk_from_slice`], but deserializes every record as a
+/// full [`ChatRecord`] (see [`parse_chunk_full`]).
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if the chunk lies outside of `data`, or with the byte
+/// offset and a snippet of the record if `options.strict` is set and a malformed record
+/// is found.
+pub fn parse_chunk_from_slice_full<F>(
+    data: &[u8],
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    mut callback: F,
+) -> Result<ChunkParseReport, CustomError>
+where
+    F: FnMut(ChatRecord),
+{
+    parse_slice_records(data, chunk, options, |record| {
+        callback(decode_full_record(record)?);
+        Ok(())
+    })
+}
+
+/// Finds the records of a chunk of `data` and passes their raw bytes to `decode`.
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if the chunk lies outside of `data`, or if
+/// `options.strict` is set and a record is malformed.
+fn parse_slice_records<'a, D>(
+    data: &'a [u8],
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    decode: D,
+) -> Result<ChunkParseReport, CustomError>
+where
+    D: FnMut(&'a [u8]) -> Result<(), serde_json::Error>,
+{
+    let start = usize::try_from(chunk.start).unwrap_or(usize::MAX);
+    let end = usize::try_from(chunk.end).unwrap_or(usize::MAX);
+    let bytes = data.get(start..end).ok_or_else(|| {
+        CustomError::ParseError(format!(
+            "chunk {}..{} is outside of the {} bytes of input",
+            chunk.start,
+            chunk.end,
+            data.len()
+        ))
+    })?;
+
+    parse_records_in_slice(
+        bytes,
+        chunk.start,
+        || count_newlines(&data[..start]) + 1,
+        options,
+        decode,
+    )
+}
+
+/// Parses a batch of records read from a stream, calling a callback for each valid message.
+///
+/// The batch is parsed like a chunk of a file with the batch's input format; skipped
+/// records and strict mode errors report their offset (and line, for NDJSON) in the
+/// whole stream.
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if `options.strict` is set and a record is malformed.
+pub fn parse_record_batch<'a, F>(
+    batch: &'a RecordBatch,
+    options: ParseOptions,
+    mut callback: F,
+) -> Result<ChunkParseReport, CustomError>
+where
+    F: FnMut(ChatMessageRef<'a>),
+{
+    parse_batch_records(batch, options, |record| {
+        callback(serde_json::from_slice(record)?);
+        Ok(())
+    })
+}
+
+/// Parses a batch like [`parse_record_batch`], but deserializes every record as a
+/// full [`ChatRecord`] (see [`parse_chunk_full`]).
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if `options.strict` is set and a record is malformed.
+pub fn parse_record_batch_full<F>(
+    batch: &RecordBatch,
+    options: ParseOptions,
+    mut callback: F,
+) -> Result<ChunkParseReport, CustomError>
+where
+    F: FnMut(ChatRecord),
+{
+    parse_batch_records(batch, options, |record| {
+        callback(decode_full_record(record)?);
+        Ok(())
+    })
+}
+
+/// Finds the records of a batch and passes their raw bytes to `decode`.
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if `options.strict` is set and a record is malformed.
+fn parse_batch_records<'a, D>(
+    batch: &'a RecordBatch,
+    options: ParseOptions,
+    decode: D,
+) -> Result<ChunkParseReport, CustomError>
+where
+    D: FnMut(&'a [u8]) -> Result<(), serde_json::Error>,
+{
+    let options = ParseOptions {
+        input_format: batch.input_format,
+        ..options
+    };
+    parse_records_in_slice(
+        &batch.data,
+        batch.offset,
+        || batch.first_line,
+        options,
+        decode,
+    )
+}
+
+/// Parses the records in `bytes`, which start at byte `offset` of the input, passing
+/// the raw bytes of each one to `decode`.
+///
+/// `first_line` returns the 1-based line number of the first byte of `bytes`; it is
+/// only called for NDJSON input, at most once, when a malformed record is found.
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if `options.strict` is set and a record is malformed.
+fn parse_records_in_slice<'a, D, L>(
+    bytes: &'a [u8],
+    offset: u64,
+    first_line: L,
+    options: ParseOptions,
+    mut decode: D,
+) -> Result<ChunkParseReport, CustomError>
+where
+    D: FnMut(&'a [u8]) -> Result<(), serde_json::Error>,
+    L: Fn() -> u64,
+{
+    let mut report = ChunkParseReport::default();
+
+    if options.input_format == InputFormat::Ndjson {
+        let mut line_start = offset;
+        let mut first_line_number = None;
+        for (line_index, line) in bytes.split_inclusive(|&b| b == b'\n').enumerate() {
+            let record = line.trim_ascii();
+            if !record.is_empty() {
+                let indent = line.len() - line.trim_ascii_start().len();
+                process_record(
+                    &mut report,
+                    options,
+                    record,
+                    line_start + indent as u64,
+                    || {
+                        let first_line = *first_line_number.get_or_insert_with(&first_line);
+                        Ok(Some(first_line + line_index as u64))
+                    },
+                    &mut decode,
+                )?;
             }
+            line_start += line.len() as u64;
+        }
+        return Ok(report);
+    }
+
+    let mut scanner = ObjectScanner::default();
+    let mut object_start = 0;
+    for (index, &byte) in bytes.iter().enumerate() {
+        match scanner.feed(byte) {
+            ScanEvent::ObjectStart => object_start = index,
+            ScanEvent::ObjectEnd => process_record(
+                &mut report,
+                options,
+                &bytes[object_start..=index],
+                offset + object_start as u64,
+                || Ok(None),
+                &mut decode,
+            )?,
+            ScanEvent::Outside | ScanEvent::Inside => {}
+        }
+    }
+
+    if scanner.in_object() {
+        skip_truncated_record(
+            &mut report,
+            options,
+            &bytes[object_start..],
+            offset + object_start as u64,
+        )?;
+    }
+
+    Ok(report)
+}
+
+/// Decodes one record with `decode`, or records it as skipped if decoding fails.
+///
+/// `line` is only called when the record is malformed, so line numbers can be resolved
+/// lazily.
+///
+/// # Errors
+///
+/// Returns the error of `line`, or `CustomError::ParseError` if `options.strict` is set
+/// and the record is malformed.
+fn process_record<'r, D, L>(
+    report: &mut ChunkParseReport,
+    options: ParseOptions,
+    record: &'r [u8],
+    offset: u64,
+    line: L,
+    decode: &mut D,
+) -> Result<(), CustomError>
+where
+    D: FnMut(&'r [u8]) -> Result<(), serde_json::Error>,
+    L: FnOnce() -> Result<Option<u64>, CustomError>,
+{
+    match decode(record) {
+        Ok(()) => {
+            report.processed_count += 1;
+            Ok(())
+        }
+        Err(e) => skip_record(
+            report,
+            options,
+            record,
+            offset,
+            line()?,
+            SkipReason::from_serde_error(&e),
+            e.to_string(),
+        ),
+    }
+}
+
+/// Deserializes a full record, requiring the fields of [`ChatMessage`].
+///
+/// # Errors
+///
+/// Returns the `serde_json` error if the record cannot be deserialized, or a missing
+/// field error if it lacks `channelName`, `language` or `viewerCount`.
+fn decode_full_record(bytes: &[u8]) -> Result<ChatRecord, serde_json::Error> {
+    let record: ChatRecord = serde_json::from_slice(bytes)?;
+    let missing = if record.channel_name.is_none() {
+        "channelName"
+    } else if record.language.is_none() {
+        "language"
+    } else if record.viewer_count.is_none() {
+        "viewerCount"
+    } else {
+        return Ok(record);
+    };
+    Err(serde::de::Error::missing_field(missing))
+}
+
+/// Records an object left incomplete at the end of a chunk as invalid JSON.
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` if `options.strict` is set.
+fn skip_truncated_record(
+    report: &mut ChunkParseReport,
+    options: ParseOptions,
+    record: &[u8],
+    offset: u64,
+) -> Result<(), CustomError> {
+    skip_record(
+        report,
+        options,
+        record.trim_ascii(),
+        offset,
+        None,
+        SkipReason::InvalidJson,
+        "record truncated at the end of the chunk".to_string(),
+    )
+}
+
+/// Maximum number of characters of a malformed record quoted in a strict mode error.
+const SNIPPET_MAX_CHARS: usize = 80;
+
+/// Records a malformed record in the report, or fails with it in strict mode.
+///
+/// # Errors
+///
+/// Returns `CustomError::ParseError` with the offset (and line, if known), the reason
+/// and a snippet of the record if `options.strict` is set.
+fn skip_record(
+    report: &mut ChunkParseReport,
+    options: ParseOptions,
+    record: &[u8],
+    offset: u64,
+    line: Option<u64>,
+    reason: SkipReason,
+    error: String,
+) -> Result<(), CustomError> {
+    let record = String::from_utf8_lossy(record);
+    if options.strict {
+        let mut snippet: String = record.chars().take(SNIPPET_MAX_CHARS).collect();
+        if record.chars().nth(SNIPPET_MAX_CHARS).is_some() {
+            snippet.push_str("...");
         }
+        let location = match line {
+            Some(line) => format!("line {line} (byte offset {offset})"),
+            None => format!("byte offset {offset}"),
+        };
+        return Err(CustomError::ParseError(format!(
+            "malformed record at {location} ({reason}): {error}; record: {snippet}"
+        )));
     }
 
-    Ok(processed_count)
+    report.skipped_records.push(SkippedRecord {
+        file: None,
+        offset,
+        line,
+        reason,
+        error,
+        raw: options.keep_rejected.then(|| record.to_string()),
+    });
+    Ok(())
 }
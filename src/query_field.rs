@@ -0,0 +1,192 @@
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
+use crate::custom_error::CustomError;
+use crate::viewer_buckets::ViewerBuckets;
+use std::borrow::Cow;
+
+/// Field of a chat record that a group-by query can group or rank by.
+///
+/// Fields are named as in the JSON records, plus `viewerRange`, the label of the
+/// configured viewer range the viewer count falls in. Flags take the values `true`
+/// and `false`. Only `channelName`, `language`, `viewerCount` and `viewerRange` are
+/// read from the narrow message model; the other fields need full records.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueryField {
+    /// `channelName`
+    ChannelName,
+    /// `language`
+    Language,
+    /// `viewerCount`
+    ViewerCount,
+    /// `viewerRange`, derived from the viewer count
+    ViewerRange,
+    /// `categoryName`
+    CategoryName,
+    /// `authorName`
+    AuthorName,
+    /// `streamTitle`
+    StreamTitle,
+    /// `streamId`
+    StreamId,
+    /// `channel`
+    Channel,
+    /// `author`
+    Author,
+    /// `category`
+    Category,
+    /// `hasEmotes`
+    HasEmotes,
+    /// `isFirstMessage`
+    IsFirstMessage,
+    /// `isMod`
+    IsMod,
+    /// `isSubscriber`
+    IsSubscriber,
+    /// `hasMalware`
+    HasMalware,
+    /// `hasUrl`
+    HasUrl,
+    /// `hasBadWords`
+    HasBadWords,
+    /// `hasBadDomain`
+    HasBadDomain,
+    /// `spamingText`
+    SpamingText,
+    /// `isMature`
+    IsMature,
+}
+
+/// Every field with its name, in the order they are listed in error messages.
+const FIELDS: [(QueryField, &str); 21] = [
+    (QueryField::ChannelName, "channelName"),
+    (QueryField::Language, "language"),
+    (QueryField::ViewerCount, "viewerCount"),
+    (QueryField::ViewerRange, "viewerRange"),
+    (QueryField::CategoryName, "categoryName"),
+    (QueryField::AuthorName, "authorName"),
+    (QueryField::StreamTitle, "streamTitle"),
+    (QueryField::StreamId, "streamId"),
+    (QueryField::Channel, "channel"),
+    (QueryField::Author, "author"),
+    (QueryField::Category, "category"),
+    (QueryField::HasEmotes, "hasEmotes"),
+    (QueryField::IsFirstMessage, "isFirstMessage"),
+    (QueryField::IsMod, "isMod"),
+    (QueryField::IsSubscriber, "isSubscriber"),
+    (QueryField::HasMalware, "hasMalware"),
+    (QueryField::HasUrl, "hasUrl"),
+    (QueryField::HasBadWords, "hasBadWords"),
+    (QueryField::HasBadDomain, "hasBadDomain"),
+    (QueryField::SpamingText, "spamingText"),
+    (QueryField::IsMature, "isMature"),
+];
+
+impl QueryField {
+    /// Returns the name of the field.
+    #[must_use]
+    pub fn name(self) -> &'static str {
+        FIELDS
+            .iter()
+            .find(|(field, _)| *field == self)
+            .map(|(_, name)| *name)
+            .expect("every field has a name")
+    }
+
+    /// Returns whether the field is only available in full records.
+    #[must_use]
+    pub fn requires_full_record(self) -> bool {
+        !matches!(
+            self,
+            QueryField::ChannelName
+                | QueryField::Language
+                | QueryField::ViewerCount
+                | QueryField::ViewerRange
+        )
+    }
+
+    /// Returns the value of the field in `message`, or `None` if the message does not
+    /// have it (a field of the full record, or a viewer count outside every range).
+    #[must_use]
+    pub fn message_value<'a>(
+        self,
+        message: &'a ChatMessageRef<'_>,
+        viewer_buckets: &'a ViewerBuckets,
+    ) -> Option<Cow<'a, str>> {
+        match self {
+            QueryField::ChannelName => Some(Cow::Borrowed(&message.channel_name)),
+            QueryField::Language => Some(Cow::Borrowed(&message.language)),
+            QueryField::ViewerCount => Some(Cow::Owned(message.viewer_count.to_string())),
+            QueryField::ViewerRange => viewer_range(message.viewer_count, viewer_buckets),
+            _ => None,
+        }
+    }
+
+    /// Returns the value of the field in `record`, or `None` if the record does not
+    /// have it (or its viewer count is outside every range, for `viewerRange`).
+    #[must_use]
+    pub fn record_value<'a>(
+        self,
+        record: &'a ChatRecord,
+        viewer_buckets: &'a ViewerBuckets,
+    ) -> Option<Cow<'a, str>> {
+        let text = |value: &'a Option<String>| value.as_deref().map(Cow::Borrowed);
+        let flag = |value: Option<bool>| {
+            value.map(|flag| Cow::Borrowed(if flag { "true" } else { "false" }))
+        };
+        match self {
+            QueryField::ChannelName => text(&record.channel_name),
+            QueryField::Language => text(&record.language),
+            QueryField::ViewerCount => record
+                .viewer_count
+                .map(|count| Cow::Owned(count.to_string())),
+            QueryField::ViewerRange => viewer_range(record.viewer_count?, viewer_buckets),
+            QueryField::CategoryName => text(&record.category_name),
+            QueryField::AuthorName => text(&record.author_name),
+            QueryField::StreamTitle => text(&record.stream_title),
+            QueryField::StreamId => text(&record.stream_id),
+            QueryField::Channel => text(&record.channel),
+            QueryField::Author => text(&record.author),
+            QueryField::Category => text(&record.category),
+            QueryField::HasEmotes => flag(record.has_emotes),
+            QueryField::IsFirstMessage => flag(record.is_first_message),
+            QueryField::IsMod => flag(record.is_mod),
+            QueryField::IsSubscriber => flag(record.is_subscriber),
+            QueryField::HasMalware => flag(record.has_malware),
+            QueryField::HasUrl => flag(record.has_url),
+            QueryField::HasBadWords => flag(record.has_bad_words),
+            QueryField::HasBadDomain => flag(record.has_bad_domain),
+            QueryField::SpamingText => flag(record.spaming_text),
+            QueryField::IsMature => flag(record.is_mature),
+        }
+    }
+}
+
+/// Returns the label of the range `viewer_count` falls in.
+fn viewer_range(viewer_count: u64, viewer_buckets: &ViewerBuckets) -> Option<Cow<'_, str>> {
+    let range_index = viewer_buckets.range_index(viewer_count)?;
+    Some(Cow::Borrowed(&viewer_buckets.ranges()[range_index].label))
+}
+
+impl std::str::FromStr for QueryField {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FIELDS
+            .iter()
+            .find(|(_, name)| *name == s)
+            .map(|(field, _)| *field)
+            .ok_or_else(|| {
+                let names: Vec<&str> = FIELDS.iter().map(|(_, name)| *name).collect();
+                CustomError::ArgumentError(format!(
+                    "Unknown query field '{s}'. Use: {}",
+                    names.join(", ")
+                ))
+            })
+    }
+}
+
+impl std::fmt::Display for QueryField {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,18 @@
+use crate::input_format::InputFormat;
+
+/// A run of complete records read from a stream, parsed as a unit by a worker.
+///
+/// Produced by [`crate::record_batch_reader::RecordBatchReader`]. A batch never
+/// splits a record, so it can be parsed on its own; `offset` and `first_line`
+/// place it in the stream, so skipped records report positions in the whole input.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RecordBatch {
+    /// Layout of the stream the batch was read from
+    pub input_format: InputFormat,
+    /// Byte offset of the first byte of the batch in the stream
+    pub offset: u64,
+    /// Line number (1-based) of the first byte of the batch in the stream
+    pub first_line: u64,
+    /// Raw bytes of the records
+    pub data: Vec<u8>,
+}
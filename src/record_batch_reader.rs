@@ -0,0 +1,135 @@
+use crate::custom_error::CustomError;
+use crate::input_format::InputFormat;
+use crate::object_scanner::{ObjectScanner, ScanEvent};
+use crate::parser::{MAX_BOUNDARY_RECORD_SIZE, detect_format_from_first_line};
+use crate::record_batch::RecordBatch;
+use std::io::{BufRead, BufReader, Read};
+
+/// Default size, in bytes, after which a batch is closed at the next record boundary.
+pub const DEFAULT_BATCH_SIZE: usize = 4 * 1024 * 1024;
+
+/// Splits a non-seekable stream into [`RecordBatch`]es of whole records.
+///
+/// The stream is read sequentially, once. A batch is closed at the first record
+/// boundary after it reaches `batch_size` bytes: after a newline for NDJSON, and
+/// after the closing brace of a top-level object for a JSON array (braces inside
+/// strings are ignored). The last batch holds whatever is left, including a
+/// truncated record, which is then reported by the parser.
+pub struct RecordBatchReader<R: Read> {
+    reader: BufReader<R>,
+    input_format: InputFormat,
+    batch_size: usize,
+    scanner: ObjectScanner,
+    pending: Vec<u8>,
+    scanned: usize,
+    offset: u64,
+    line: u64,
+    finished: bool,
+}
+
+impl<R: Read> RecordBatchReader<R> {
+    /// Creates a reader that closes batches after `batch_size` bytes.
+    ///
+    /// With `input_format` set to `None`, the format is detected from the first line
+    /// of the stream like [`crate::parser::detect_input_format`] does for files.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::IOError` if the first line cannot be read.
+    pub fn new(
+        reader: R,
+        input_format: Option<InputFormat>,
+        batch_size: usize,
+    ) -> Result<Self, CustomError> {
+        let mut reader = BufReader::with_capacity(64 * 1024, reader);
+        let mut pending = Vec::new();
+        (&mut reader)
+            .take(MAX_BOUNDARY_RECORD_SIZE)
+            .read_until(b'\n', &mut pending)
+            .map_err(|e| CustomError::IOError(e.to_string()))?;
+        let input_format = input_format.unwrap_or_else(|| detect_format_from_first_line(&pending));
+
+        Ok(Self {
+            reader,
+            input_format,
+            batch_size: batch_size.max(1),
+            scanner: ObjectScanner::default(),
+            pending,
+            scanned: 0,
+            offset: 0,
+            line: 1,
+            finished: false,
+        })
+    }
+
+    /// Returns the layout of the stream, given or detected.
+    #[must_use]
+    pub fn input_format(&self) -> InputFormat {
+        self.input_format
+    }
+
+    /// Feeds the pending bytes to the boundary detection and returns the length of
+    /// the batch to close, if a boundary was found after `batch_size` bytes.
+    fn find_batch_end(&mut self) -> Option<usize> {
+        while self.scanned < self.pending.len() {
+            let byte = self.pending[self.scanned];
+            self.scanned += 1;
+            let boundary = match self.input_format {
+                InputFormat::Ndjson => byte == b'\n',
+                InputFormat::JsonArray => self.scanner.feed(byte) == ScanEvent::ObjectEnd,
+            };
+            if boundary && self.scanned >= self.batch_size {
+                return Some(self.scanned);
+            }
+        }
+        None
+    }
+
+    /// Removes the first `len` pending bytes and returns them as a batch.
+    fn take_batch(&mut self, len: usize) -> RecordBatch {
+        let rest = self.pending.split_off(len);
+        let data = std::mem::replace(&mut self.pending, rest);
+        self.scanned -= len;
+
+        let batch = RecordBatch {
+            input_format: self.input_format,
+            offset: self.offset,
+            first_line: self.line,
+            data,
+        };
+        self.offset += batch.data.len() as u64;
+        self.line += batch.data.iter().filter(|&&b| b == b'\n').count() as u64;
+        batch
+    }
+}
+
+impl<R: Read> Iterator for RecordBatchReader<R> {
+    type Item = Result<RecordBatch, CustomError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.finished {
+            return None;
+        }
+        loop {
+            if let Some(len) = self.find_batch_end() {
+                return Some(Ok(self.take_batch(len)));
+            }
+
+            let buffer = match self.reader.fill_buf() {
+                Ok(buffer) => buffer,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+                Err(e) => {
+                    self.finished = true;
+                    return Some(Err(CustomError::IOError(e.to_string())));
+                }
+            };
+            if buffer.is_empty() {
+                self.finished = true;
+                return (!self.pending.is_empty()).then(|| Ok(self.take_batch(self.pending.len())));
+            }
+            let read = buffer.len();
+            self.pending.extend_from_slice(buffer);
+            self.reader.consume(read);
+        }
+    }
+}
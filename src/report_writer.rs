@@ -0,0 +1,42 @@
+use crate::analysis_report::AnalysisReport;
+use crate::csv_writer::CsvWriter;
+use crate::custom_error::CustomError;
+use crate::json_writer::JsonWriter;
+use crate::markdown_writer::MarkdownWriter;
+use crate::output_format::OutputFormat;
+use crate::text_writer::TextWriter;
+use std::io::Write;
+
+/// Writes an `AnalysisReport` in a specific output format.
+///
+/// Implementations only read the report, so the same report can be written
+/// several times in different formats.
+pub trait ReportWriter {
+    /// Writes the report to `out`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::IOError` if writing to `out` fails, or
+    /// `CustomError::TransformationError` if the report cannot be serialized.
+    fn write_report(&self, report: &AnalysisReport, out: &mut dyn Write)
+    -> Result<(), CustomError>;
+}
+
+/// Returns the writer for the given output format.
+#[must_use]
+pub fn writer_for(format: OutputFormat) -> Box<dyn ReportWriter> {
+    match format {
+        OutputFormat::Text => Box::new(TextWriter),
+        OutputFormat::Json { pretty } => Box::new(JsonWriter { pretty }),
+        OutputFormat::Csv => Box::new(CsvWriter),
+        OutputFormat::Markdown => Box::new(MarkdownWriter),
+    }
+}
+
+/// Builds the title for a ranking, e.g. "TOP 3 CHANNELS BY LANGUAGE".
+pub(crate) fn ranking_title(limit: Option<usize>, subject: &str) -> String {
+    match limit {
+        Some(limit) => format!("TOP {limit} {subject}"),
+        None => format!("ALL {subject}"),
+    }
+}
@@ -0,0 +1,36 @@
+use crate::analysis_type::AnalysisType;
+use crate::chunk_timings::ChunkTimings;
+use crate::skipped_records_summary::SkippedRecordsSummary;
+
+/// Information about a single analysis run, included in every report.
+#[derive(Debug, Clone, PartialEq, serde::Serialize)]
+pub struct RunMetadata {
+    /// First input given for the run (a path, directory or glob pattern); every
+    /// analyzed file is listed in `input_files`
+    pub input_file: String,
+    /// Files that were analyzed, after expanding directories and glob patterns
+    pub input_files: Vec<String>,
+    /// Analysis that was run
+    pub analysis_type: AnalysisType,
+    /// Expression messages had to match to be aggregated (`None` if every message was)
+    pub filter: Option<String>,
+    /// Number of worker threads used
+    pub num_threads: usize,
+    /// Number of chunks (or stream batches) the input was processed in
+    pub num_chunks: usize,
+    /// Time spent on each chunk, summarized
+    pub chunk_timings: ChunkTimings,
+    /// Number of channels kept per language (`None` for the full ranking)
+    pub top_channels_limit: Option<usize>,
+    /// Number of languages kept per viewer range (`None` for the full ranking)
+    pub top_languages_limit: Option<usize>,
+    /// Total number of messages read
+    pub total_messages: u64,
+    /// Number of messages that matched the filter and were aggregated (equal to
+    /// `total_messages` when there is no filter)
+    pub matched_messages: u64,
+    /// Malformed records skipped during the run, by reason
+    pub skipped_records: SkippedRecordsSummary,
+    /// Wall-clock time of the run in seconds
+    pub elapsed_seconds: f64,
+}
@@ -0,0 +1,35 @@
+/// Reason why a record was skipped by the parser.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SkipReason {
+    /// The record is valid JSON but lacks a required field
+    MissingField,
+    /// The record is valid JSON but a field has the wrong type
+    WrongType,
+    /// The record is not valid JSON (syntax error or truncated object)
+    InvalidJson,
+}
+
+impl SkipReason {
+    /// Classifies a `serde_json` deserialization error.
+    #[must_use]
+    pub fn from_serde_error(error: &serde_json::Error) -> Self {
+        match error.classify() {
+            serde_json::error::Category::Data if error.to_string().starts_with("missing field") => {
+                SkipReason::MissingField
+            }
+            serde_json::error::Category::Data => SkipReason::WrongType,
+            _ => SkipReason::InvalidJson,
+        }
+    }
+}
+
+impl std::fmt::Display for SkipReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SkipReason::MissingField => write!(f, "missing field"),
+            SkipReason::WrongType => write!(f, "wrong type"),
+            SkipReason::InvalidJson => write!(f, "invalid JSON"),
+        }
+    }
+}
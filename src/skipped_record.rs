@@ -0,0 +1,21 @@
+use crate::skip_reason::SkipReason;
+
+/// A malformed record that was skipped by the parser.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct SkippedRecord {
+    /// Input file containing the record, set when several files are analyzed together
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub file: Option<String>,
+    /// Byte offset in the file where the record starts
+    pub offset: u64,
+    /// Line number (1-based) where the record starts, known for NDJSON input
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<u64>,
+    /// Why the record was skipped
+    pub reason: SkipReason,
+    /// Error message reported by the JSON parser
+    pub error: String,
+    /// Raw text of the record, only kept when requested
+    #[serde(rename = "record", skip_serializing_if = "Option::is_none")]
+    pub raw: Option<String>,
+}
@@ -0,0 +1,42 @@
+use crate::skip_reason::SkipReason;
+use crate::skipped_record::SkippedRecord;
+
+/// Number of malformed records skipped during a run, by reason.
+#[derive(Debug, Clone, Default, PartialEq, Eq, serde::Serialize)]
+pub struct SkippedRecordsSummary {
+    /// Total number of skipped records
+    pub total: usize,
+    /// Records lacking a required field
+    pub missing_field: usize,
+    /// Records with a field of the wrong type
+    pub wrong_type: usize,
+    /// Records that are not valid JSON
+    pub invalid_json: usize,
+}
+
+impl SkippedRecordsSummary {
+    /// Builds the summary for a list of skipped records.
+    #[must_use]
+    pub fn from_records(records: &[SkippedRecord]) -> Self {
+        let mut summary = Self::default();
+        for record in records {
+            summary.total += 1;
+            match record.reason {
+                SkipReason::MissingField => summary.missing_field += 1,
+                SkipReason::WrongType => summary.wrong_type += 1,
+                SkipReason::InvalidJson => summary.invalid_json += 1,
+            }
+        }
+        summary
+    }
+}
+
+impl std::fmt::Display for SkippedRecordsSummary {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} skipped (missing field: {}, wrong type: {}, invalid JSON: {})",
+            self.total, self.missing_field, self.wrong_type, self.invalid_json
+        )
+    }
+}
@@ -1,16 +1,27 @@
-/// Thread-safe aggregators for streaming data processing.
+use crate::channel_id::ChannelId;
+use crate::interner::Interner;
+use crate::language_id::LanguageId;
+use crate::viewer_buckets::ViewerBuckets;
+
+/// Per-chunk aggregators for streaming data processing.
 ///
-/// This struct contains all the data structures needed to aggregate
-/// streaming data across multiple threads using Arc<Mutex<>> for thread safety.
+/// Each chunk is folded into its own `StreamingAggregators` without any locking,
+/// and the partial aggregators are merged into a single one once all chunks finish.
+/// Channel names and language codes are interned, so the maps are keyed by compact
+/// IDs that are only resolved back to names when building the results.
+#[derive(Debug, Clone)]
 pub struct StreamingAggregators {
     /// Language to channel message counts mapping
-    pub language_channel_counts: std::sync::Arc<
-        std::sync::Mutex<std::collections::HashMap<String, std::collections::HashMap<String, i32>>>,
-    >,
-    /// Viewer range to language message counts mapping
-    pub range_language_counts: std::sync::Arc<
-        std::sync::Mutex<std::collections::HashMap<String, std::collections::HashMap<String, i32>>>,
-    >,
+    pub language_channel_counts:
+        std::collections::HashMap<LanguageId, std::collections::HashMap<ChannelId, u64>>,
+    /// Language message counts for each viewer range, indexed like `viewer_buckets.ranges()`
+    pub range_language_counts: Vec<std::collections::HashMap<LanguageId, u64>>,
+    /// Interned channel names
+    pub channels: Interner<ChannelId>,
+    /// Interned language codes
+    pub languages: Interner<LanguageId>,
+    /// Viewer ranges used to classify messages
+    pub viewer_buckets: ViewerBuckets,
     /// Total number of processed messages
-    pub total_messages: std::sync::Arc<std::sync::Mutex<usize>>,
+    pub total_messages: u64,
 }
@@ -0,0 +1,150 @@
+use crate::analysis_report::AnalysisReport;
+use crate::custom_error::CustomError;
+use crate::group_node::GroupNode;
+use crate::report_writer::{ReportWriter, ranking_title};
+use std::io::Write;
+use std::time::Duration;
+
+/// Writes the report in the human-readable console format.
+pub struct TextWriter;
+
+impl ReportWriter for TextWriter {
+    fn write_report(
+        &self,
+        report: &AnalysisReport,
+        out: &mut dyn Write,
+    ) -> Result<(), CustomError> {
+        write_text(report, out).map_err(|e| CustomError::IOError(e.to_string()))
+    }
+}
+
+fn write_text(report: &AnalysisReport, out: &mut dyn Write) -> std::io::Result<()> {
+    let metadata = &report.metadata;
+
+    if let Some(top_channels) = &report.top_channels_by_language {
+        writeln!(
+            out,
+            "\n=== {} ===",
+            ranking_title(metadata.top_channels_limit, "CHANNELS BY LANGUAGE")
+        )?;
+        for result in top_channels {
+            writeln!(out, "\nLanguage: {}", result.language)?;
+            for (i, channel) in result.top_channels.iter().enumerate() {
+                writeln!(
+                    out,
+                    "  {}. {}: {} messages",
+                    i + 1,
+                    channel.channel_name,
+                    channel.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(top_languages) = &report.top_languages_by_viewer_range {
+        writeln!(
+            out,
+            "\n=== {} ===",
+            ranking_title(metadata.top_languages_limit, "LANGUAGES BY VIEWER RANGE")
+        )?;
+        for result in top_languages {
+            writeln!(out, "\nViewer Range: {}", result.viewer_range)?;
+            for (i, language) in result.top_languages.iter().enumerate() {
+                writeln!(
+                    out,
+                    "  {}. {}: {} messages",
+                    i + 1,
+                    language.language,
+                    language.message_count
+                )?;
+            }
+        }
+    }
+
+    if let Some(group_by) = &report.group_by {
+        let subject = format!("{} BY {}", group_by.top_by, group_by.group_by.join(", "));
+        writeln!(out, "\n=== {} ===", ranking_title(group_by.limit, &subject))?;
+        write_groups(out, &group_by.group_by, &group_by.groups, 0)?;
+    }
+
+    if let Some(time_series) = &report.time_series {
+        let subject = format!(
+            "CHANNELS BY LANGUAGE PER {}",
+            time_series.window.name().to_uppercase()
+        );
+        writeln!(
+            out,
+            "\n=== {} ===",
+            ranking_title(metadata.top_channels_limit, &subject)
+        )?;
+        for window in &time_series.windows {
+            writeln!(
+                out,
+                "\n[{} - {}] ({} messages)",
+                window.window_start, window.window_end, window.message_count
+            )?;
+            for result in &window.top_channels_by_language {
+                writeln!(out, "  Language: {}", result.language)?;
+                for (i, channel) in result.top_channels.iter().enumerate() {
+                    writeln!(
+                        out,
+                        "    {}. {}: {} messages",
+                        i + 1,
+                        channel.channel_name,
+                        channel.message_count
+                    )?;
+                }
+            }
+        }
+    }
+
+    if let Some(filter) = &metadata.filter {
+        writeln!(
+            out,
+            "\nFilter: {filter} ({} of {} messages matched)",
+            metadata.matched_messages, metadata.total_messages
+        )?;
+    }
+
+    if metadata.skipped_records.total > 0 {
+        writeln!(out, "\nMalformed records: {}", metadata.skipped_records)?;
+    }
+
+    writeln!(
+        out,
+        "\nTime elapsed: {:?}",
+        Duration::from_secs_f64(metadata.elapsed_seconds)
+    )
+}
+
+/// Writes the groups of the `level`-th group-by field, indented by level, with the
+/// ranking under each innermost group.
+fn write_groups(
+    out: &mut dyn Write,
+    fields: &[String],
+    groups: &[GroupNode],
+    level: usize,
+) -> std::io::Result<()> {
+    let indent = "  ".repeat(level);
+    for group in groups {
+        if level == 0 {
+            writeln!(out)?;
+        }
+        writeln!(
+            out,
+            "{indent}{}: {} ({} messages)",
+            fields[level], group.value, group.message_count
+        )?;
+        write_groups(out, fields, &group.groups, level + 1)?;
+        for (i, value) in group.top.iter().enumerate() {
+            writeln!(
+                out,
+                "{indent}  {}. {}: {} messages",
+                i + 1,
+                value.value,
+                value.message_count
+            )?;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,35 @@
+use crate::time_window::TimeWindow;
+use crate::time_window_result::TimeWindowResult;
+
+/// Result of the time series analysis: the top channels by language of every time
+/// window, in chronological order.
+///
+/// Serialized as:
+///
+/// ```json
+/// {
+///   "window": "hour",
+///   "windows": [
+///     {
+///       "window_start": "2021-11-05T14:00:00.000Z",
+///       "window_end": "2021-11-05T15:00:00.000Z",
+///       "message_count": 2,
+///       "top_channels_by_language": [
+///         {
+///           "language": "en",
+///           "top_channels": [{ "channel_name": "gaming1", "message_count": 2 }]
+///         }
+///       ]
+///     }
+///   ]
+/// }
+/// ```
+///
+/// Windows without messages are omitted.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TimeSeriesResult {
+    /// Width of the windows
+    pub window: TimeWindow,
+    /// Windows with at least one message, oldest first
+    pub windows: Vec<TimeWindowResult>,
+}
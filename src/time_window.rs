@@ -0,0 +1,68 @@
+use crate::custom_error::CustomError;
+use crate::timestamp::Timestamp;
+
+/// Width of the time windows messages are bucketed into by their `createAt` timestamp.
+///
+/// Windows are aligned to UTC: an hour window starts at minute zero and a day
+/// window at midnight.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TimeWindow {
+    /// One minute
+    Minute,
+    /// One hour
+    Hour,
+    /// One day
+    Day,
+}
+
+impl TimeWindow {
+    /// Returns the name of the window, as accepted by `--window`.
+    #[must_use]
+    pub fn name(&self) -> &'static str {
+        match self {
+            TimeWindow::Minute => "minute",
+            TimeWindow::Hour => "hour",
+            TimeWindow::Day => "day",
+        }
+    }
+
+    /// Returns the width of the window in milliseconds.
+    #[must_use]
+    pub fn millis(&self) -> i64 {
+        match self {
+            TimeWindow::Minute => 60 * 1000,
+            TimeWindow::Hour => 60 * 60 * 1000,
+            TimeWindow::Day => 24 * 60 * 60 * 1000,
+        }
+    }
+
+    /// Returns the start, in milliseconds since the Unix epoch, of the window that
+    /// contains `timestamp`.
+    #[must_use]
+    pub fn start_millis(&self, timestamp: &Timestamp) -> i64 {
+        let millis = timestamp.millis();
+        millis - millis.rem_euclid(self.millis())
+    }
+}
+
+impl std::str::FromStr for TimeWindow {
+    type Err = CustomError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "minute" => Ok(TimeWindow::Minute),
+            "hour" => Ok(TimeWindow::Hour),
+            "day" => Ok(TimeWindow::Day),
+            _ => Err(CustomError::ArgumentError(format!(
+                "Invalid time window '{s}'. Use: minute, hour or day"
+            ))),
+        }
+    }
+}
+
+impl std::fmt::Display for TimeWindow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.name())
+    }
+}
@@ -0,0 +1,146 @@
+use crate::aggregator::Aggregator;
+use crate::analysis_config::AnalysisConfig;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
+use crate::language_order::LanguageOrder;
+use crate::time_series_result::TimeSeriesResult;
+use crate::time_window::TimeWindow;
+use crate::time_window_result::TimeWindowResult;
+use crate::timestamp::Timestamp;
+use crate::top_channels_aggregator::TopChannelsAggregator;
+use chrono::{DateTime, Utc};
+use std::collections::HashMap;
+
+/// Buckets messages into time windows by their `createAt` timestamp and ranks the
+/// channels of every language within each window (the `time_series` analysis).
+///
+/// Every window is counted by its own [`TopChannelsAggregator`], so a window's
+/// ranking is exactly the `top_channels` analysis of the messages sent within it.
+/// The timestamp is only available in full records; messages without `createAt`
+/// are not counted.
+///
+/// ```
+/// use TP0ProgramacionConcurrente::aggregator::Aggregator;
+/// use TP0ProgramacionConcurrente::chat_record::ChatRecord;
+/// use TP0ProgramacionConcurrente::language_order::LanguageOrder;
+/// use TP0ProgramacionConcurrente::time_window::TimeWindow;
+/// use TP0ProgramacionConcurrente::time_window_aggregator::TimeWindowAggregator;
+///
+/// let record: ChatRecord = serde_json::from_str(
+///     r#"{"channelName": "gaming1", "language": "en", "viewerCount": 150,
+///         "createAt": {"$date": "2021-11-05T14:59:41.071Z"}}"#,
+/// )
+/// .unwrap();
+///
+/// let mut aggregator =
+///     TimeWindowAggregator::new(TimeWindow::Hour, Some(3), LanguageOrder::Volume);
+/// aggregator.update_record(&record);
+///
+/// let series = aggregator.finalize();
+/// assert_eq!(series.windows[0].window_start.to_string(), "2021-11-05T14:00:00.000Z");
+/// ```
+#[derive(Debug, Clone)]
+pub struct TimeWindowAggregator {
+    window: TimeWindow,
+    /// Empty ranking cloned for every new window
+    empty: TopChannelsAggregator,
+    /// Number of messages and ranking of every window, keyed by its start in
+    /// milliseconds since the Unix epoch
+    windows: HashMap<i64, (u64, TopChannelsAggregator)>,
+}
+
+impl TimeWindowAggregator {
+    /// Creates an aggregator that buckets messages into windows of `window` and keeps
+    /// `limit` channels per language (`None` keeps all of them) in every window,
+    /// ordering the languages by `language_order`.
+    #[must_use]
+    pub fn new(window: TimeWindow, limit: Option<usize>, language_order: LanguageOrder) -> Self {
+        Self {
+            window,
+            empty: TopChannelsAggregator::new(limit, language_order),
+            windows: HashMap::new(),
+        }
+    }
+
+    /// Creates an aggregator with the ranking settings of `config`, bucketing messages
+    /// into windows of `window`.
+    #[must_use]
+    pub fn from_config(window: TimeWindow, config: &AnalysisConfig) -> Self {
+        Self::new(window, config.top_channels, config.language_order)
+    }
+
+    /// Returns the width of the windows.
+    #[must_use]
+    pub fn window(&self) -> TimeWindow {
+        self.window
+    }
+}
+
+impl Aggregator for TimeWindowAggregator {
+    type Output = TimeSeriesResult;
+
+    /// Messages without a timestamp cannot be placed in a window, so they are ignored.
+    fn update(&mut self, _message: &ChatMessageRef<'_>) {}
+
+    fn update_record(&mut self, record: &ChatRecord) {
+        let (Some(created_at), Some(message)) = (&record.created_at, record.as_message_ref())
+        else {
+            return;
+        };
+        let (message_count, top_channels) = self
+            .windows
+            .entry(self.window.start_millis(created_at))
+            .or_insert_with(|| (0, self.empty.clone()));
+        *message_count += 1;
+        top_channels.update(&message);
+    }
+
+    fn requires_full_record(&self) -> bool {
+        true
+    }
+
+    fn merge(&mut self, other: Self) {
+        for (start, (message_count, top_channels)) in other.windows {
+            match self.windows.get_mut(&start) {
+                Some((count, existing)) => {
+                    *count += message_count;
+                    existing.merge(top_channels);
+                }
+                None => {
+                    self.windows.insert(start, (message_count, top_channels));
+                }
+            }
+        }
+    }
+
+    fn finalize(self) -> TimeSeriesResult {
+        let mut windows: Vec<_> = self.windows.into_iter().collect();
+        windows.sort_unstable_by_key(|(start, _)| *start);
+        let width = self.window.millis();
+        TimeSeriesResult {
+            window: self.window,
+            windows: windows
+                .into_iter()
+                .map(|(start, (message_count, top_channels))| TimeWindowResult {
+                    window_start: window_bound(start),
+                    window_end: window_bound(start.saturating_add(width)),
+                    message_count,
+                    top_channels_by_language: top_channels.finalize(),
+                })
+                .collect(),
+        }
+    }
+}
+
+/// Converts a window bound into a timestamp, clamped to the range supported by
+/// [`Timestamp`] for the windows at its very edges.
+fn window_bound(millis: i64) -> Timestamp {
+    Timestamp::from_millis(millis).unwrap_or_else(|| {
+        let bound = if millis < 0 {
+            DateTime::<Utc>::MIN_UTC
+        } else {
+            DateTime::<Utc>::MAX_UTC
+        };
+        Timestamp::from(bound)
+    })
+}
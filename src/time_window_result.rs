@@ -0,0 +1,15 @@
+use crate::timestamp::Timestamp;
+use crate::top_channels_result::TopChannelsByLanguageResult;
+
+/// Top channels for each language within a single time window.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct TimeWindowResult {
+    /// Start of the window (inclusive)
+    pub window_start: Timestamp,
+    /// End of the window (exclusive)
+    pub window_end: Timestamp,
+    /// Number of messages sent within the window
+    pub message_count: u64,
+    /// Top channels for each language within the window
+    pub top_channels_by_language: Vec<TopChannelsByLanguageResult>,
+}
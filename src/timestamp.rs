@@ -0,0 +1,123 @@
+use crate::custom_error::CustomError;
+use chrono::{DateTime, SecondsFormat, Utc};
+use serde::de::{self, Deserializer};
+use std::str::FromStr;
+
+/// A point in time, in UTC with millisecond precision.
+///
+/// Deserializes from the timestamp layouts found in the dataset:
+///
+/// - an RFC 3339 string, such as `"2021-11-05T14:03:26Z"` (`streamStartedAt`)
+/// - a Mongo extended JSON date, such as `{"$date": "2021-11-05T14:59:41.071Z"}`
+///   (`createAt`), whose value may also be milliseconds since the Unix epoch or
+///   `{"$numberLong": "<milliseconds>"}`
+/// - an integer number of milliseconds since the Unix epoch
+///
+/// Serializes as an RFC 3339 string with milliseconds.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Timestamp(DateTime<Utc>);
+
+impl Timestamp {
+    /// Creates a timestamp from milliseconds since the Unix epoch.
+    ///
+    /// Returns `None` if the value is out of the supported range.
+    #[must_use]
+    pub fn from_millis(millis: i64) -> Option<Self> {
+        DateTime::from_timestamp_millis(millis).map(Self)
+    }
+
+    /// Returns the milliseconds since the Unix epoch.
+    #[must_use]
+    pub fn millis(&self) -> i64 {
+        self.0.timestamp_millis()
+    }
+
+    /// Returns the timestamp as a `chrono` date and time.
+    #[must_use]
+    pub fn datetime(&self) -> DateTime<Utc> {
+        self.0
+    }
+}
+
+impl From<DateTime<Utc>> for Timestamp {
+    fn from(datetime: DateTime<Utc>) -> Self {
+        Self(datetime)
+    }
+}
+
+impl FromStr for Timestamp {
+    type Err = CustomError;
+
+    /// Parses an RFC 3339 timestamp, converting it to UTC.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        DateTime::parse_from_rfc3339(s)
+            .map(|datetime| Self(datetime.with_timezone(&Utc)))
+            .map_err(|e| CustomError::ParseError(format!("Invalid timestamp '{s}': {e}")))
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0.to_rfc3339_opts(SecondsFormat::Millis, true))
+    }
+}
+
+impl serde::Serialize for Timestamp {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> serde::Deserialize<'de> for Timestamp {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match RawTimestamp::deserialize(deserializer)? {
+            RawTimestamp::Value(value) => value.into_timestamp(),
+            RawTimestamp::MongoDate { date } => date.into_timestamp(),
+        }
+    }
+}
+
+/// Any of the accepted timestamp layouts, before conversion.
+#[derive(serde::Deserialize)]
+#[serde(
+    untagged,
+    expecting = "an RFC 3339 string, epoch milliseconds or a $date object"
+)]
+enum RawTimestamp {
+    Value(DateValue),
+    MongoDate {
+        #[serde(rename = "$date")]
+        date: DateValue,
+    },
+}
+
+/// The value of a timestamp, on its own or inside a `$date` wrapper.
+#[derive(serde::Deserialize)]
+#[serde(untagged)]
+enum DateValue {
+    Text(String),
+    Millis(i64),
+    NumberLong {
+        #[serde(rename = "$numberLong")]
+        millis: String,
+    },
+}
+
+impl DateValue {
+    /// Converts the value into a timestamp, reporting invalid values as `E`.
+    fn into_timestamp<E: de::Error>(self) -> Result<Timestamp, E> {
+        let millis = match self {
+            Self::Text(text) => {
+                return DateTime::parse_from_rfc3339(&text)
+                    .map(|datetime| Timestamp(datetime.with_timezone(&Utc)))
+                    .map_err(|e| E::custom(format!("invalid timestamp '{text}': {e}")));
+            }
+            Self::Millis(millis) => millis,
+            Self::NumberLong { millis } => millis
+                .parse()
+                .map_err(|_| E::custom(format!("invalid $numberLong '{millis}'")))?,
+        };
+        Timestamp::from_millis(millis)
+            .ok_or_else(|| E::custom(format!("timestamp {millis} is out of range")))
+    }
+}
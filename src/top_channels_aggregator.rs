@@ -0,0 +1,90 @@
+use crate::aggregator::Aggregator;
+use crate::analysis_config::{AnalysisConfig, DEFAULT_TOP_CHANNELS};
+use crate::channel_id::ChannelId;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::interner::{InternId, Interner};
+use crate::language_id::LanguageId;
+use crate::language_order::LanguageOrder;
+use crate::top_channels_result::TopChannelsByLanguageResult;
+use crate::transformations::{merge_counts, rank_channels_by_language};
+use std::collections::HashMap;
+
+/// Counts the messages of every channel in every language and ranks the channels
+/// of each language (the `top_channels` analysis).
+///
+/// Reference implementation of [`Aggregator`]: channel names and language codes are
+/// interned, and the IDs of a merged partial are translated into this aggregator's.
+#[derive(Debug, Clone)]
+pub struct TopChannelsAggregator {
+    limit: Option<usize>,
+    language_order: LanguageOrder,
+    language_channel_counts: HashMap<LanguageId, HashMap<ChannelId, u64>>,
+    channels: Interner<ChannelId>,
+    languages: Interner<LanguageId>,
+}
+
+impl Default for TopChannelsAggregator {
+    fn default() -> Self {
+        Self::new(Some(DEFAULT_TOP_CHANNELS), LanguageOrder::default())
+    }
+}
+
+impl TopChannelsAggregator {
+    /// Creates an aggregator that keeps `limit` channels per language (`None` keeps
+    /// all of them) and orders the languages by `language_order`.
+    #[must_use]
+    pub fn new(limit: Option<usize>, language_order: LanguageOrder) -> Self {
+        Self {
+            limit,
+            language_order,
+            language_channel_counts: HashMap::new(),
+            channels: Interner::new(),
+            languages: Interner::new(),
+        }
+    }
+
+    /// Creates an aggregator with the ranking settings of `config`.
+    #[must_use]
+    pub fn from_config(config: &AnalysisConfig) -> Self {
+        Self::new(config.top_channels, config.language_order)
+    }
+}
+
+impl Aggregator for TopChannelsAggregator {
+    type Output = Vec<TopChannelsByLanguageResult>;
+
+    fn update(&mut self, message: &ChatMessageRef<'_>) {
+        let language = self.languages.intern(&message.language);
+        let channel = self.channels.intern(&message.channel_name);
+        *self
+            .language_channel_counts
+            .entry(language)
+            .or_default()
+            .entry(channel)
+            .or_insert(0) += 1;
+    }
+
+    fn merge(&mut self, other: Self) {
+        let channel_ids = self.channels.absorb(&other.channels);
+        let language_ids = self.languages.absorb(&other.languages);
+        for (language, channel_counts) in other.language_channel_counts {
+            let target = self
+                .language_channel_counts
+                .entry(language_ids[language.index() as usize])
+                .or_default();
+            merge_counts(target, channel_counts, |channel| {
+                channel_ids[channel.index() as usize]
+            });
+        }
+    }
+
+    fn finalize(self) -> Self::Output {
+        rank_channels_by_language(
+            &self.language_channel_counts,
+            &self.languages,
+            &self.channels,
+            self.limit,
+            self.language_order,
+        )
+    }
+}
@@ -2,7 +2,8 @@ use crate::channel_message_count::ChannelMessageCount;
 
 /// Result structure for the top channels by language transformation.
 ///
-/// Contains the top 3 channels with the most messages for a specific language.
+/// Contains the channels with the most messages for a specific language.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct TopChannelsByLanguageResult {
     /// The language code (e.g., "en", "es", "fr")
     pub language: String,
@@ -0,0 +1,82 @@
+use crate::aggregator::Aggregator;
+use crate::analysis_config::{AnalysisConfig, DEFAULT_TOP_LANGUAGES};
+use crate::chat_message_ref::ChatMessageRef;
+use crate::interner::{InternId, Interner};
+use crate::language_id::LanguageId;
+use crate::top_languages_result::TopLanguagesByViewerRangeResult;
+use crate::transformations::{merge_counts, rank_languages_by_viewer_range};
+use crate::viewer_buckets::ViewerBuckets;
+use std::collections::HashMap;
+
+/// Counts the messages of every language in every viewer range and ranks the
+/// languages of each range (the `top_languages` analysis).
+///
+/// Messages whose viewer count falls outside every range are not counted.
+#[derive(Debug, Clone)]
+pub struct TopLanguagesAggregator {
+    limit: Option<usize>,
+    viewer_buckets: ViewerBuckets,
+    range_language_counts: Vec<HashMap<LanguageId, u64>>,
+    languages: Interner<LanguageId>,
+}
+
+impl Default for TopLanguagesAggregator {
+    fn default() -> Self {
+        Self::new(ViewerBuckets::default(), Some(DEFAULT_TOP_LANGUAGES))
+    }
+}
+
+impl TopLanguagesAggregator {
+    /// Creates an aggregator that classifies messages into `viewer_buckets` and keeps
+    /// `limit` languages per range (`None` keeps all of them).
+    #[must_use]
+    pub fn new(viewer_buckets: ViewerBuckets, limit: Option<usize>) -> Self {
+        Self {
+            limit,
+            range_language_counts: vec![HashMap::new(); viewer_buckets.ranges().len()],
+            viewer_buckets,
+            languages: Interner::new(),
+        }
+    }
+
+    /// Creates an aggregator with the viewer ranges and ranking settings of `config`.
+    #[must_use]
+    pub fn from_config(config: &AnalysisConfig) -> Self {
+        Self::new(config.viewer_buckets.clone(), config.top_languages)
+    }
+}
+
+impl Aggregator for TopLanguagesAggregator {
+    type Output = Vec<TopLanguagesByViewerRangeResult>;
+
+    fn update(&mut self, message: &ChatMessageRef<'_>) {
+        if let Some(range_index) = self.viewer_buckets.range_index(message.viewer_count) {
+            let language = self.languages.intern(&message.language);
+            *self.range_language_counts[range_index]
+                .entry(language)
+                .or_insert(0) += 1;
+        }
+    }
+
+    fn merge(&mut self, other: Self) {
+        let language_ids = self.languages.absorb(&other.languages);
+        for (target, source) in self
+            .range_language_counts
+            .iter_mut()
+            .zip(other.range_language_counts)
+        {
+            merge_counts(target, source, |language| {
+                language_ids[language.index() as usize]
+            });
+        }
+    }
+
+    fn finalize(self) -> Self::Output {
+        rank_languages_by_viewer_range(
+            &self.viewer_buckets,
+            &self.range_language_counts,
+            &self.languages,
+            self.limit,
+        )
+    }
+}
@@ -2,10 +2,15 @@ use crate::language_message_count::LanguageMessageCount;
 
 /// Result structure for the top languages by viewer range transformation.
 ///
-/// Contains the top 5 languages with the most messages for a specific viewer range.
+/// Contains the languages with the most messages for a specific viewer range.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
 pub struct TopLanguagesByViewerRangeResult {
-    /// The viewer count range (e.g., "0-100", "101-500", "501-1000", "1000+")
+    /// The viewer count range label (e.g., "0-100", "101-500", "501-1000", "1000+")
     pub viewer_range: String,
+    /// Smallest viewer count included in the range
+    pub lower_bound: u64,
+    /// Largest viewer count included in the range (`None` for the open-ended last range)
+    pub upper_bound: Option<u64>,
     /// Vector of the top languages ordered by message count (descending)
     pub top_languages: Vec<LanguageMessageCount>,
 }
@@ -1,17 +1,69 @@
+use crate::aggregation_results::AggregationResults;
+use crate::aggregator::Aggregator;
+use crate::aggregator_handle::AggregatorHandle;
+use crate::aggregator_set::AggregatorSet;
+use crate::analysis_config::AnalysisConfig;
+use crate::analysis_report::{AnalysisReport, REPORT_SCHEMA_VERSION};
+use crate::analysis_type::AnalysisType;
+use crate::channel_id::ChannelId;
 use crate::channel_message_count::ChannelMessageCount;
 use crate::chat_message::ChatMessage;
+use crate::chat_message_ref::ChatMessageRef;
+use crate::chat_record::ChatRecord;
+use crate::chunk_info::ChunkInfo;
+use crate::chunk_parse_report::ChunkParseReport;
+use crate::chunk_timings::ChunkTimings;
+use crate::counting_reader::CountingReader;
 use crate::custom_error::CustomError;
+use crate::filter_expr::FilterExpr;
+use crate::group_by_aggregator::GroupByAggregator;
+use crate::group_by_query::GroupByQuery;
+use crate::group_by_result::GroupByResult;
+use crate::input_files::resolve_input_files;
+use crate::input_format::InputFormat;
+use crate::interner::{InternId, Interner};
+use crate::language_id::LanguageId;
 use crate::language_message_count::LanguageMessageCount;
-use crate::parser::{analize_file_for_chunks, parse_chunk_streaming};
+use crate::language_order::LanguageOrder;
+use crate::parse_options::ParseOptions;
+use crate::parser::{
+    GZIP_MAGIC, analize_files_for_chunks, detect_input_format, is_gzip_file, map_file,
+    parse_chunk_borrowed, parse_chunk_from_slice, parse_chunk_from_slice_full, parse_chunk_full,
+    parse_record_batch, parse_record_batch_full, split_chunk,
+};
+use crate::record_batch::RecordBatch;
+use crate::record_batch_reader::RecordBatchReader;
+use crate::run_metadata::RunMetadata;
+use crate::skipped_record::SkippedRecord;
+use crate::skipped_records_summary::SkippedRecordsSummary;
 use crate::streaming_aggregators::StreamingAggregators;
+use crate::time_series_result::TimeSeriesResult;
+use crate::time_window::TimeWindow;
+use crate::time_window_aggregator::TimeWindowAggregator;
+use crate::top_channels_aggregator::TopChannelsAggregator;
 use crate::top_channels_result::TopChannelsByLanguageResult;
+use crate::top_languages_aggregator::TopLanguagesAggregator;
 use crate::top_languages_result::TopLanguagesByViewerRangeResult;
+use crate::viewer_buckets::ViewerBuckets;
+use flate2::read::MultiGzDecoder;
+use memmap2::Mmap;
+use rayon::iter::Either;
 use rayon::prelude::*;
 use std::collections::HashMap;
+use std::fs::File;
+use std::io::{BufRead, BufReader, BufWriter, Read, Write};
+use std::sync::mpsc::sync_channel;
 use std::sync::{
-    Arc, Mutex,
-    atomic::{AtomicUsize, Ordering},
+    Condvar, Mutex,
+    atomic::{AtomicU64, AtomicUsize, Ordering},
 };
+use std::time::Instant;
+
+/// Input name that reads the dataset from stdin instead of a file.
+pub const STDIN_INPUT: &str = "-";
+
+/// Skipped records, each with the index of the input file it was found in.
+type FileSkippedRecords = Vec<(usize, SkippedRecord)>;
 
 impl Default for StreamingAggregators {
     fn default() -> Self {
@@ -20,143 +72,1121 @@ impl Default for StreamingAggregators {
 }
 
 impl StreamingAggregators {
-    /// Creates a new instance of `StreamingAggregators` with empty collections.
-    ///
-    /// Initializes all the thread-safe data structures (`Arc<Mutex<HashMap>>`)
-    /// needed for parallel data aggregation.
+    /// Creates a new instance of `StreamingAggregators` with empty collections
+    /// and the default viewer ranges.
     #[must_use]
     pub fn new() -> Self {
+        Self::with_viewer_buckets(ViewerBuckets::default())
+    }
+
+    /// Creates an empty `StreamingAggregators` that classifies messages into `viewer_buckets`.
+    #[must_use]
+    pub fn with_viewer_buckets(viewer_buckets: ViewerBuckets) -> Self {
         Self {
-            language_channel_counts: Arc::new(Mutex::new(HashMap::new())),
-            range_language_counts: Arc::new(Mutex::new(HashMap::new())),
-            total_messages: Arc::new(Mutex::new(0)),
+            language_channel_counts: HashMap::new(),
+            range_language_counts: vec![HashMap::new(); viewer_buckets.ranges().len()],
+            channels: Interner::new(),
+            languages: Interner::new(),
+            viewer_buckets,
+            total_messages: 0,
         }
     }
 
     /// Processes a single chat message and updates the aggregated counts.
     ///
-    /// This method is thread-safe and updates both language-channel counts
-    /// and viewer range-language counts atomically. It categorizes viewer counts
-    /// into ranges: 0-100, 101-500, 501-1000, and 1000+.
+    /// Updates both language-channel counts and viewer range-language counts.
+    /// Viewer counts are classified with the configured `ViewerBuckets`; messages
+    /// whose viewer count falls outside every range only count towards the channels.
     ///
     /// # Arguments
     ///
     /// * `message` - The `ChatMessage` to process and aggregate
+    pub fn process_message(&mut self, message: ChatMessage) {
+        self.process_message_ref(ChatMessageRef::from(&message));
+    }
+
+    /// Processes a borrowed chat message and updates the aggregated counts.
     ///
-    /// # Panics
+    /// Behaves like [`StreamingAggregators::process_message`]. Names are interned, so
+    /// a string is only allocated when a channel or language is seen for the first time.
     ///
-    /// Panics if any of the internal mutexes are poisoned due to panic in another thread.
-    pub fn process_message(&self, message: ChatMessage) {
-        {
-            let mut lang_channel_counts = self.language_channel_counts.lock().unwrap();
-            let channel_counts = lang_channel_counts
-                .entry(message.language.clone())
-                .or_default();
-            *channel_counts
-                .entry(message.channel_name.clone())
+    /// # Arguments
+    ///
+    /// * `message` - The `ChatMessageRef` to process and aggregate
+    pub fn process_message_ref(&mut self, message: ChatMessageRef<'_>) {
+        let language = self.languages.intern(&message.language);
+        let channel = self.channels.intern(&message.channel_name);
+        *self
+            .language_channel_counts
+            .entry(language)
+            .or_default()
+            .entry(channel)
+            .or_insert(0) += 1;
+
+        if let Some(range_index) = self.viewer_buckets.range_index(message.viewer_count) {
+            *self.range_language_counts[range_index]
+                .entry(language)
                 .or_insert(0) += 1;
         }
 
-        let range = match message.viewer_count {
-            0..=100 => "0-100",
-            101..=500 => "101-500",
-            501..=1000 => "501-1000",
-            _ => "1000+",
-        };
+        self.total_messages += 1;
+    }
 
-        {
-            let mut range_lang_counts = self.range_language_counts.lock().unwrap();
-            let language_counts = range_lang_counts.entry(range.to_string()).or_default();
-            *language_counts.entry(message.language).or_insert(0) += 1;
-        }
+    /// Merges the counts of another partial aggregator into this one.
+    ///
+    /// Both aggregators must have been created with the same viewer ranges. The IDs
+    /// of `other` are translated into IDs of this aggregator's interners.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - The partial aggregator to absorb
+    pub fn merge(&mut self, other: StreamingAggregators) {
+        let channel_ids = self.channels.absorb(&other.channels);
+        let language_ids = self.languages.absorb(&other.languages);
+        let channel_id = |id: ChannelId| channel_ids[id.index() as usize];
+        let language_id = |id: LanguageId| language_ids[id.index() as usize];
 
+        for (language, channel_counts) in other.language_channel_counts {
+            let target = self
+                .language_channel_counts
+                .entry(language_id(language))
+                .or_default();
+            merge_counts(target, channel_counts, channel_id);
+        }
+        for (target, source) in self
+            .range_language_counts
+            .iter_mut()
+            .zip(other.range_language_counts)
         {
-            let mut total = self.total_messages.lock().unwrap();
-            *total += 1;
+            merge_counts(target, source, language_id);
         }
+        self.total_messages += other.total_messages;
+    }
+
+    /// Returns the message count of `channel` in `language`, or zero if it was never seen.
+    #[must_use]
+    pub fn channel_count(&self, language: &str, channel: &str) -> u64 {
+        self.languages
+            .get(language)
+            .zip(self.channels.get(channel))
+            .and_then(|(language, channel)| {
+                self.language_channel_counts
+                    .get(&language)?
+                    .get(&channel)
+                    .copied()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the message count of `language` in the `range_index`-th viewer range,
+    /// or zero if it was never seen there.
+    #[must_use]
+    pub fn range_language_count(&self, range_index: usize, language: &str) -> u64 {
+        self.languages
+            .get(language)
+            .and_then(|language| {
+                self.range_language_counts
+                    .get(range_index)?
+                    .get(&language)
+                    .copied()
+            })
+            .unwrap_or(0)
+    }
+
+    /// Returns the language-channel counts keyed by name instead of by ID.
+    #[must_use]
+    pub fn language_channel_counts_by_name(&self) -> HashMap<String, HashMap<String, u64>> {
+        self.language_channel_counts
+            .iter()
+            .map(|(&language, channel_counts)| {
+                let channel_counts = channel_counts
+                    .iter()
+                    .map(|(&channel, &count)| (self.channels.resolve(channel).to_string(), count))
+                    .collect();
+                (self.languages.resolve(language).to_string(), channel_counts)
+            })
+            .collect()
+    }
+
+    /// Returns the per-range language counts keyed by language code instead of by ID.
+    #[must_use]
+    pub fn range_language_counts_by_name(&self) -> Vec<HashMap<String, u64>> {
+        self.range_language_counts
+            .iter()
+            .map(|language_counts| {
+                language_counts
+                    .iter()
+                    .map(|(&language, &count)| {
+                        (self.languages.resolve(language).to_string(), count)
+                    })
+                    .collect()
+            })
+            .collect()
+    }
+}
+
+/// Adds every count of `source` into `target`, translating its keys with `translate`.
+pub(crate) fn merge_counts<K, F>(
+    target: &mut HashMap<K, u64>,
+    source: HashMap<K, u64>,
+    translate: F,
+) where
+    K: Eq + std::hash::Hash,
+    F: Fn(K) -> K,
+{
+    for (key, count) in source {
+        *target.entry(translate(key)).or_insert(0) += count;
     }
 }
 
-/// Processes a file using streaming approach with parallel chunk processing.
+/// Builds a dedicated rayon thread pool with exactly `config.num_threads` workers.
 ///
-/// This function divides the file into chunks and processes them in parallel using Rayon,
-/// maintaining real-time progress updates and thread-safe aggregation.
+/// # Errors
+///
+/// Returns `CustomError::TransformationError` if the configuration is invalid
+/// or the thread pool cannot be created.
+pub fn build_thread_pool(config: &AnalysisConfig) -> Result<rayon::ThreadPool, CustomError> {
+    config.validate()?;
+    rayon::ThreadPoolBuilder::new()
+        .num_threads(config.num_threads)
+        .build()
+        .map_err(|e| CustomError::TransformationError(e.to_string()))
+}
+
+/// Processes one or more files using streaming approach with parallel chunk processing.
+///
+/// This function divides the files into about `config.num_chunks` chunks in total and
+/// processes them in parallel on a dedicated Rayon pool with `config.num_threads` workers,
+/// maintaining real-time progress updates on stderr. Chunks of all files are scheduled
+/// together as a single job, so the result is the same as for one file holding all the
+/// records. Every chunk is folded into its own clone of `aggregators`, and the partial
+/// results are merged with a parallel reduce. Having more chunks than workers lets idle
+/// workers steal the remaining chunks, and with `config.adaptive_split` large chunks are
+/// also split while workers are idle. The time spent on each chunk is summarized in the
+/// returned [`ChunkTimings`].
+/// Gzip-compressed files (detected by their magic bytes) cannot be split by byte
+/// offset; each of them is one task of the same job, which decompresses it and
+/// parses it in batches like a stream (see [`aggregate_gzip_file`]). Offsets of
+/// their skipped records refer to the decompressed data.
+/// Malformed records are counted per chunk and, if `config.reject_file` is set,
+/// written to that file in file order. In strict mode (`config.strict`) the run
+/// fails on the first malformed record found by any chunk instead. With
+/// `config.use_mmap`, every uncompressed file is mapped once and the workers parse their
+/// chunks straight from the shared mappings instead of opening their own buffered readers.
 ///
 /// # Arguments
 ///
-/// * `path` - Path to the file to process
-/// * `num_threads` - Number of threads to use for parallel processing
+/// * `paths` - Paths of the files to process
+/// * `aggregators` - Empty aggregators cloned for every chunk
+/// * `config` - Worker and chunk configuration for the run
 ///
 /// # Returns
 ///
-/// Returns a `StreamingAggregators` containing all aggregated data from the file processing.
+/// Returns the merged aggregators with all aggregated data from the file processing,
+/// together with the summary of the skipped records and of the time spent on each chunk.
 ///
 /// # Errors
 ///
-/// Returns `CustomError` if file analysis or chunk processing fails.
+/// Returns `CustomError` if the thread pool cannot be built, if file analysis
+/// or chunk processing fails, or if the reject file cannot be written. In strict
+/// mode, returns `CustomError::ParseError` for the first malformed record found.
 fn process_file_streaming(
-    path: &str,
-    num_threads: usize,
-) -> Result<StreamingAggregators, CustomError> {
-    println!("Starting streaming analysis process...");
-    println!("File: {path}");
-    println!("Using {num_threads} threads");
+    paths: &[String],
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<(AggregatorSet, SkippedRecordsSummary, ChunkTimings), CustomError> {
+    let pool = build_thread_pool(config)?;
+
+    eprintln!("Starting streaming analysis process...");
+    for path in paths {
+        eprintln!("File: {path}");
+    }
+    eprintln!("Using {} threads", config.num_threads);
+    log_record_fields(aggregators, config);
+
+    let mut gzip_ids = Vec::new();
+    let mut plain_ids = Vec::new();
+    for (file_id, path) in paths.iter().enumerate() {
+        if is_gzip_file(path)? {
+            gzip_ids.push(file_id);
+        } else {
+            plain_ids.push(file_id);
+        }
+    }
+
+    let (merged, mut skipped_records, chunk_seconds) =
+        aggregate_files(&pool, paths, &plain_ids, &gzip_ids, aggregators, config)?;
+
+    skipped_records.sort_by_key(|(file_id, record)| (*file_id, record.offset));
+    let skipped_records: Vec<SkippedRecord> = skipped_records
+        .into_iter()
+        .map(|(_, record)| record)
+        .collect();
+    let skipped = finish_skipped_records(merged.total_messages(), &skipped_records, config)?;
+    let timings = ChunkTimings::from_durations(&chunk_seconds);
+    eprintln!("Chunk timings: {timings}");
+    Ok((merged, skipped, timings))
+}
+
+/// Input of one task of the parallel job over the files of a run.
+#[derive(Debug, Clone, Copy)]
+enum FileTask {
+    /// A chunk of an uncompressed file
+    Chunk(ChunkInfo),
+    /// A whole gzip-compressed file, by its index in the paths of the run
+    Gzip(usize),
+}
+
+/// Position of a task in the input of a run: the index of its file and the byte
+/// offset where it starts.
+type TaskPosition = (usize, u64);
+
+/// Earliest failure among the tasks of a parallel job.
+///
+/// Tasks finish in no particular order, so in strict mode several of them may fail.
+/// The error kept is the one of the task that starts first in the input, which is the
+/// error a sequential run would report. Tasks that start after a known failure are
+/// skipped, as they can no longer change the outcome.
+struct FirstFailure {
+    failure: Mutex<Option<(TaskPosition, CustomError)>>,
+}
+
+impl FirstFailure {
+    /// Creates a tracker with no failure recorded.
+    fn new() -> Self {
+        Self {
+            failure: Mutex::new(None),
+        }
+    }
+
+    /// Returns whether a task that starts at `position` can be skipped, because a
+    /// task that starts before it already failed.
+    fn is_before(&self, position: TaskPosition) -> bool {
+        self.failure.lock().map_or(true, |failure| {
+            failure
+                .as_ref()
+                .is_some_and(|(failed_at, _)| *failed_at < position)
+        })
+    }
+
+    /// Records the error of the task that starts at `position`, unless an earlier
+    /// task already failed.
+    fn record(&self, position: TaskPosition, error: CustomError) {
+        if let Ok(mut failure) = self.failure.lock()
+            && failure
+                .as_ref()
+                .is_none_or(|(failed_at, _)| position < *failed_at)
+        {
+            *failure = Some((position, error));
+        }
+    }
+
+    /// Returns the earliest error recorded, if any.
+    fn into_result(self) -> Result<(), CustomError> {
+        match self
+            .failure
+            .into_inner()
+            .map_err(|_| CustomError::TransformationError("task failures poisoned".to_string()))?
+        {
+            Some((_, error)) => Err(error),
+            None => Ok(()),
+        }
+    }
+}
+
+/// Aggregates the files of a run on `pool` as a single parallel job, folding every
+/// task into a clone of `aggregators`.
+///
+/// The uncompressed files `paths[plain_ids]` are split into chunks, and every
+/// gzip-compressed file `paths[gzip_ids]` is one more task of the same job (see
+/// [`aggregate_gzip_file`]), so several compressed files are decompressed at the same
+/// time as the chunks of the other files are parsed. With `config.adaptive_split`,
+/// chunks are split further while workers are idle.
+///
+/// # Returns
+///
+/// Returns the merged aggregators, the skipped records, each with the index in
+/// `paths` of the file it was found in, and the time in seconds spent on each chunk
+/// or batch.
+///
+/// # Errors
+///
+/// Returns `CustomError` if file analysis or chunk processing fails. In strict
+/// mode, returns `CustomError::ParseError` for the first malformed record of the
+/// input, even if a later chunk failed sooner.
+fn aggregate_files(
+    pool: &rayon::ThreadPool,
+    paths: &[String],
+    plain_ids: &[usize],
+    gzip_ids: &[usize],
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<(AggregatorSet, FileSkippedRecords, Vec<f64>), CustomError> {
+    let multiple_files = paths.len() > 1;
+    let chunk_paths: Vec<String> = plain_ids.iter().map(|&id| paths[id].clone()).collect();
+
+    let input_formats = chunk_paths
+        .iter()
+        .map(|path| match config.input_format {
+            Some(input_format) => Ok(input_format),
+            None => detect_input_format(path),
+        })
+        .collect::<Result<Vec<InputFormat>, CustomError>>()?;
+    for (path, input_format) in chunk_paths.iter().zip(&input_formats) {
+        if multiple_files {
+            eprintln!("Input format of {path}: {input_format}");
+        } else {
+            eprintln!("Input format: {input_format}");
+        }
+    }
 
-    let chunks = analize_file_for_chunks(path, num_threads)?;
+    let chunks = if chunk_paths.is_empty() {
+        Vec::new()
+    } else {
+        analize_files_for_chunks(&chunk_paths, &input_formats, config.num_chunks)?
+    };
+    let mapped_files = if config.use_mmap && !chunk_paths.is_empty() {
+        eprintln!("Reading the input through memory maps");
+        Some(
+            chunk_paths
+                .iter()
+                .map(|path| map_file(path))
+                .collect::<Result<Vec<Mmap>, CustomError>>()?,
+        )
+    } else {
+        None
+    };
     let chunks_len = chunks.len();
-    println!("File divided into {chunks_len} chunks for parallel processing");
-
-    let aggregators = StreamingAggregators::new();
-    let processed_chunks = Arc::new(AtomicUsize::new(0));
-    let total_chunks = chunks.len();
-
-    chunks
-        .par_iter()
-        .try_for_each(|chunk| -> Result<(), CustomError> {
-            let aggregators_ref = &aggregators;
-            let processed_count = parse_chunk_streaming(path, chunk, |message| {
-                aggregators_ref.process_message(message);
-            })?;
+    if !chunk_paths.is_empty() {
+        eprintln!("Input divided into {chunks_len} chunks for parallel processing");
+    }
+    if let Some(min_split_size) = config.adaptive_split {
+        eprintln!("Adaptive splitting of chunks of at least {min_split_size} bytes");
+    }
+    for &file_id in gzip_ids {
+        eprintln!(
+            "Decompressing {} (gzip) and reading it sequentially",
+            paths[file_id]
+        );
+    }
 
+    let processed_chunks = AtomicUsize::new(0);
+    let processed_bytes = AtomicU64::new(0);
+    let total_bytes: u64 = chunks.iter().map(|chunk| chunk.size).sum();
+    let chunk_seconds = Mutex::new(Vec::with_capacity(chunks_len));
+    let base_options = ParseOptions {
+        keep_rejected: config.reject_file.is_some(),
+        strict: config.strict,
+        ..ParseOptions::default()
+    };
+    let empty_partial = || (aggregators.clone(), Vec::new());
+
+    let process_chunk =
+        |chunk: ChunkInfo| -> Result<(AggregatorSet, FileSkippedRecords), CustomError> {
+            let started = Instant::now();
+            let path = &chunk_paths[chunk.file_id];
+            let parse_options = ParseOptions {
+                input_format: input_formats[chunk.file_id],
+                ..base_options
+            };
+            let mut partial = aggregators.clone();
+            let data = mapped_files.as_ref().map(|data| &data[chunk.file_id][..]);
+            let report = parse_chunk_into(
+                &mut partial,
+                path,
+                data,
+                &chunk,
+                parse_options,
+                config.filter.as_ref(),
+            )
+            .map_err(|e| with_file_context(e, path, multiple_files))?;
+
+            let seconds = started.elapsed().as_secs_f64();
+            chunk_seconds
+                .lock()
+                .map_err(|_| {
+                    CustomError::TransformationError("chunk timings poisoned".to_string())
+                })?
+                .push(seconds);
             let completed = processed_chunks.fetch_add(1, Ordering::SeqCst) + 1;
-            let percentage = (completed * 100) / total_chunks;
-            println!("Chunk {completed}/{total_chunks} completed ({processed_count} messages processed) - {percentage}% done");
+            let done = processed_bytes.fetch_add(chunk.size, Ordering::SeqCst) + chunk.size;
+            let percentage = done * 100 / total_bytes.max(1);
+            eprintln!(
+                "Chunk {completed} completed in {seconds:.3}s ({} messages processed, \
+             {} malformed records skipped) - {percentage}% done",
+                report.processed_count,
+                report.skipped_records.len()
+            );
+            let file_id = plain_ids[chunk.file_id];
+            let skipped = report
+                .skipped_records
+                .into_iter()
+                .map(|record| (file_id, tag_record(record, path, multiple_files)))
+                .collect();
+            Ok((partial, skipped))
+        };
+    let process_gzip =
+        |file_id: usize| -> Result<(AggregatorSet, FileSkippedRecords), CustomError> {
+            let path = &paths[file_id];
+            let (partial, skipped, batch_seconds) = aggregate_gzip_file(path, aggregators, config)
+                .map_err(|e| with_file_context(e, path, multiple_files))?;
+            chunk_seconds
+                .lock()
+                .map_err(|_| {
+                    CustomError::TransformationError("chunk timings poisoned".to_string())
+                })?
+                .extend(batch_seconds);
+            let skipped = skipped
+                .into_iter()
+                .map(|record| (file_id, tag_record(record, path, multiple_files)))
+                .collect();
+            Ok((partial, skipped))
+        };
+    let first_failure = FirstFailure::new();
+    let process_task = |task: FileTask| {
+        let position = match task {
+            FileTask::Chunk(chunk) => (plain_ids[chunk.file_id], chunk.start),
+            FileTask::Gzip(file_id) => (file_id, 0),
+        };
+        if first_failure.is_before(position) {
+            return empty_partial();
+        }
+        let result = match task {
+            FileTask::Chunk(chunk) => process_chunk(chunk),
+            FileTask::Gzip(file_id) => process_gzip(file_id),
+        };
+        result.unwrap_or_else(|error| {
+            first_failure.record(position, error);
+            empty_partial()
+        })
+    };
+    let merge_partials =
+        |mut merged: (AggregatorSet, FileSkippedRecords),
+         mut partial: (AggregatorSet, FileSkippedRecords)| {
+            merged.0.merge(partial.0);
+            merged.1.append(&mut partial.1);
+            merged
+        };
+
+    // Compressed files go first, as each of them is a single long task.
+    let tasks: Vec<FileTask> = gzip_ids
+        .iter()
+        .map(|&file_id| FileTask::Gzip(file_id))
+        .chain(chunks.into_iter().map(FileTask::Chunk))
+        .collect();
+    let (merged, skipped_records) = pool.install(|| match config.adaptive_split {
+        Some(min_split_size) => tasks
+            .par_iter()
+            .flat_map(|&task| match task {
+                FileTask::Chunk(chunk) => Either::Left(
+                    rayon::iter::split(chunk, |chunk| {
+                        split_in_half(&chunk_paths, &input_formats, chunk, min_split_size)
+                    })
+                    .map(FileTask::Chunk),
+                ),
+                FileTask::Gzip(_) => Either::Right(rayon::iter::once(task)),
+            })
+            .map(process_task)
+            .reduce(empty_partial, merge_partials),
+        None => tasks
+            .par_iter()
+            .copied()
+            .map(process_task)
+            .reduce(empty_partial, merge_partials),
+    });
+    first_failure.into_result()?;
+
+    let chunk_seconds = chunk_seconds
+        .into_inner()
+        .map_err(|_| CustomError::TransformationError("chunk timings poisoned".to_string()))?;
+    Ok((merged, skipped_records, chunk_seconds))
+}
+
+/// Aggregates a gzip-compressed file from a task of the parallel job over the files.
+///
+/// The calling worker decompresses the file and cuts it into batches, which are
+/// parsed as tasks of the same pool. At most two batches per worker are in flight
+/// (see [`acquire_batch_slot`]): while waiting for one to finish, the calling worker
+/// runs pending tasks itself, so the file is processed even with a single worker, and
+/// sleeps when there are none. Progress is reported as the share
+/// of the compressed file read, and offsets of skipped records refer to the
+/// decompressed data.
+///
+/// # Returns
+///
+/// Returns the merged aggregators, the skipped records (in no particular order)
+/// and the time in seconds spent on each batch.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be read or decompressed. In
+/// strict mode, returns `CustomError::ParseError` for the first malformed record of the
+/// file.
+fn aggregate_gzip_file(
+    path: &str,
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<(AggregatorSet, Vec<SkippedRecord>, Vec<f64>), CustomError> {
+    let file = File::open(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let compressed_size = file
+        .metadata()
+        .map_err(|e| CustomError::IOError(e.to_string()))?
+        .len();
+    let counting_reader = CountingReader::new(file);
+    let compressed_read = counting_reader.bytes_read();
+    let batches = RecordBatchReader::new(
+        MultiGzDecoder::new(counting_reader),
+        config.input_format,
+        config.stream_batch_size,
+    )?;
+    eprintln!("Input format of {path}: {}", batches.input_format());
+
+    let parse_options = ParseOptions {
+        keep_rejected: config.reject_file.is_some(),
+        strict: config.strict,
+        ..ParseOptions::default()
+    };
+    let max_in_flight = config.num_threads.saturating_mul(2).max(1);
+    let in_flight = Mutex::new(0);
+    let batch_done = Condvar::new();
+    let processed_batches = AtomicUsize::new(0);
+    let merged = Mutex::new((aggregators.clone(), Vec::new(), Vec::new()));
+    let first_failure = FirstFailure::new();
+
+    let read_result = rayon::in_place_scope(|scope| -> Result<(), CustomError> {
+        for batch in batches {
+            let batch = batch?;
+            if first_failure.is_before((0, batch.offset)) {
+                // An earlier batch failed in strict mode, nothing left to do.
+                break;
+            }
+            acquire_batch_slot(&in_flight, &batch_done, max_in_flight)?;
+            let (in_flight, batch_done, processed_batches, merged, first_failure, compressed_read) = (
+                &in_flight,
+                &batch_done,
+                &processed_batches,
+                &merged,
+                &first_failure,
+                &compressed_read,
+            );
+            scope.spawn(move |_| {
+                let started = Instant::now();
+                let mut partial = aggregators.clone();
+                let report =
+                    parse_batch_into(&mut partial, &batch, parse_options, config.filter.as_ref());
+                let seconds = started.elapsed().as_secs_f64();
+                if let Ok(report) = &report {
+                    let completed = processed_batches.fetch_add(1, Ordering::SeqCst) + 1;
+                    let read = compressed_read.load(Ordering::Relaxed).min(compressed_size);
+                    eprintln!(
+                        "Batch {completed} of {path} completed in {seconds:.3}s ({} messages \
+                         processed, {} malformed records skipped) - {}% of the compressed \
+                         input read",
+                        report.processed_count,
+                        report.skipped_records.len(),
+                        read * 100 / compressed_size.max(1)
+                    );
+                }
+                match report {
+                    Ok(report) => {
+                        if let Ok(mut merged) = merged.lock() {
+                            let (aggregators, skipped, batch_seconds) = &mut *merged;
+                            aggregators.merge(partial);
+                            skipped.extend(report.skipped_records);
+                            batch_seconds.push(seconds);
+                        }
+                    }
+                    Err(error) => first_failure.record((0, batch.offset), error),
+                }
+                if let Ok(mut in_flight) = in_flight.lock() {
+                    *in_flight -= 1;
+                }
+                batch_done.notify_one();
+            });
+        }
+        Ok(())
+    });
+    let merged = merged
+        .into_inner()
+        .map_err(|_| CustomError::TransformationError("batch results poisoned".to_string()))?;
+    first_failure.into_result()?;
+    read_result?;
+    Ok(merged)
+}
+
+/// Reserves one of the `max_in_flight` slots for a batch of a gzip-compressed file.
+///
+/// While every slot is taken, the calling worker runs pending tasks of the pool, so a
+/// single worker still makes progress. Once there are none left, the batches in flight
+/// are running on other workers, and the caller sleeps until one of them finishes
+/// (they signal `batch_done` after releasing their slot).
+///
+/// # Errors
+///
+/// Returns `CustomError::TransformationError` if the slot count is poisoned.
+fn acquire_batch_slot(
+    in_flight: &Mutex<usize>,
+    batch_done: &Condvar,
+    max_in_flight: usize,
+) -> Result<(), CustomError> {
+    let poisoned = |_| CustomError::TransformationError("batch slots poisoned".to_string());
+    loop {
+        let mut count = in_flight.lock().map_err(poisoned)?;
+        if *count < max_in_flight {
+            *count += 1;
+            return Ok(());
+        }
+        drop(count);
+        if rayon::yield_now() == Some(rayon::Yield::Executed) {
+            continue;
+        }
+
+        let mut count = batch_done
+            .wait_while(in_flight.lock().map_err(poisoned)?, |count| {
+                *count >= max_in_flight
+            })
+            .map_err(poisoned)?;
+        *count += 1;
+        return Ok(());
+    }
+}
+
+/// Splits `chunk` at a record boundary near its middle, for [`rayon::iter::split`].
+///
+/// Rayon only asks for a split while there are idle workers, so chunks are only
+/// divided when the work is unbalanced. Chunks smaller than twice `min_split_size`,
+/// or that cannot be split, are kept whole.
+fn split_in_half(
+    paths: &[String],
+    input_formats: &[InputFormat],
+    chunk: ChunkInfo,
+    min_split_size: u64,
+) -> (ChunkInfo, Option<ChunkInfo>) {
+    if chunk.size < min_split_size.saturating_mul(2) {
+        return (chunk, None);
+    }
+    match split_chunk(&paths[chunk.file_id], &chunk, input_formats[chunk.file_id]) {
+        Ok(Some((first, second))) => (first, Some(second)),
+        // An unreadable file is reported when the chunk is parsed.
+        _ => (chunk, None),
+    }
+}
+
+/// Parses a chunk into `aggregators`, from `data` (the mapped file) if given and from a
+/// buffered reader of `path` otherwise. Full records are read only if some aggregator
+/// requires them, and only the messages matching `filter` are folded.
+///
+/// # Errors
+///
+/// Returns the error of the parse function (see [`parse_chunk_borrowed`]).
+fn parse_chunk_into(
+    aggregators: &mut AggregatorSet,
+    path: &str,
+    data: Option<&[u8]>,
+    chunk: &ChunkInfo,
+    options: ParseOptions,
+    filter: Option<&FilterExpr>,
+) -> Result<ChunkParseReport, CustomError> {
+    match (data, aggregators.requires_full_record()) {
+        (Some(data), true) => parse_chunk_from_slice_full(data, chunk, options, |record| {
+            update_record_filtered(aggregators, filter, &record);
+        }),
+        (None, true) => parse_chunk_full(path, chunk, options, |record| {
+            update_record_filtered(aggregators, filter, &record);
+        }),
+        (Some(data), false) => parse_chunk_from_slice(data, chunk, options, |message| {
+            update_filtered(aggregators, filter, &message);
+        }),
+        (None, false) => parse_chunk_borrowed(path, chunk, options, |message| {
+            update_filtered(aggregators, filter, &message);
+        }),
+    }
+}
+
+/// Parses a stream batch into `aggregators`, reading full records only if some
+/// aggregator requires them and folding only the messages matching `filter`.
+///
+/// # Errors
+///
+/// Returns the error of the parse function (see [`parse_record_batch`]).
+fn parse_batch_into(
+    aggregators: &mut AggregatorSet,
+    batch: &RecordBatch,
+    options: ParseOptions,
+    filter: Option<&FilterExpr>,
+) -> Result<ChunkParseReport, CustomError> {
+    if aggregators.requires_full_record() {
+        parse_record_batch_full(batch, options, |record| {
+            update_record_filtered(aggregators, filter, &record);
+        })
+    } else {
+        parse_record_batch(batch, options, |message| {
+            update_filtered(aggregators, filter, &message);
+        })
+    }
+}
+
+/// Folds `message` into `aggregators` if it matches `filter` (or there is no filter).
+fn update_filtered(
+    aggregators: &mut AggregatorSet,
+    filter: Option<&FilterExpr>,
+    message: &ChatMessageRef<'_>,
+) {
+    if filter.is_none_or(|filter| filter.matches(message)) {
+        aggregators.update(message);
+    } else {
+        aggregators.skip();
+    }
+}
+
+/// Folds `record` into `aggregators` if its core fields match `filter` (or there is no filter).
+fn update_record_filtered(
+    aggregators: &mut AggregatorSet,
+    filter: Option<&FilterExpr>,
+    record: &ChatRecord,
+) {
+    let matches = match filter {
+        Some(filter) => record
+            .as_message_ref()
+            .is_some_and(|message| filter.matches(&message)),
+        None => true,
+    };
+    if matches {
+        aggregators.update_record(record);
+    } else {
+        aggregators.skip();
+    }
+}
+
+/// Tells on stderr whether the run reads full records or only the core fields, and
+/// which messages are selected by the filter of `config`.
+fn log_record_fields(aggregators: &AggregatorSet, config: &AnalysisConfig) {
+    if aggregators.requires_full_record() {
+        eprintln!("Reading full records for {} aggregators", aggregators.len());
+    } else {
+        eprintln!(
+            "Reading channelName, language and viewerCount for {} aggregators",
+            aggregators.len()
+        );
+    }
+    if let Some(filter) = &config.filter {
+        eprintln!("Aggregating only messages where {filter}");
+    }
+}
+
+/// Processes a non-seekable stream, such as stdin, with parallel batch processing.
+///
+/// A reader thread splits the stream into [`RecordBatch`]es of whole records (see
+/// [`RecordBatchReader`]) and hands them to the workers of the dedicated Rayon pool
+/// through a bounded channel, so at most a few batches per worker are held in memory.
+/// Each batch is folded into its own clone of `aggregators`, and the partial results
+/// are merged like the chunks of a file. A gzip-compressed stream (detected by its
+/// magic bytes) is decompressed on the fly by the reader thread. Skipped records, the
+/// reject file and strict mode behave as in [`process_file_streaming`], with offsets
+/// in the whole (decompressed) stream.
+///
+/// # Arguments
+///
+/// * `reader` - Stream to read the records from
+/// * `config` - Worker and parsing configuration for the run
+///
+/// # Returns
+///
+/// Returns the aggregated data, the summary of the skipped records and the time
+/// spent on each batch.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the thread pool cannot be built, if the stream cannot be
+/// read, or if the reject file cannot be written. In strict mode, returns
+/// `CustomError::ParseError` for the first malformed record found.
+fn process_reader_streaming<R: Read + Send>(
+    reader: R,
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<(AggregatorSet, SkippedRecordsSummary, ChunkTimings), CustomError> {
+    let pool = build_thread_pool(config)?;
+
+    eprintln!("Starting streaming analysis process...");
+    eprintln!("Reading from stream");
+    eprintln!("Using {} threads", config.num_threads);
+    log_record_fields(aggregators, config);
+    if config.use_mmap {
+        eprintln!("Memory maps are not available for streams, reading sequentially");
+    }
+
+    let mut reader = BufReader::new(reader);
+    let compressed = reader
+        .fill_buf()
+        .map_err(|e| CustomError::IOError(e.to_string()))?
+        .starts_with(&GZIP_MAGIC);
+    let (merged, mut skipped_records, batch_seconds) = if compressed {
+        eprintln!("Decompressing gzip stream");
+        let counting_reader = CountingReader::new(reader);
+        let compressed_read = counting_reader.bytes_read();
+        aggregate_stream(
+            &pool,
+            MultiGzDecoder::new(counting_reader),
+            Some(&compressed_read),
+            aggregators,
+            config,
+        )?
+    } else {
+        aggregate_stream(&pool, reader, None, aggregators, config)?
+    };
+
+    skipped_records.sort_by_key(|record| record.offset);
+    let skipped = finish_skipped_records(merged.total_messages(), &skipped_records, config)?;
+    let timings = ChunkTimings::from_durations(&batch_seconds);
+    eprintln!("Batch timings: {timings}");
+    Ok((merged, skipped, timings))
+}
+
+/// Aggregates a stream on `pool`, folding the batches read by a reader thread into
+/// clones of `aggregators`.
+///
+/// For a decompressed stream, `compressed_read` counts the compressed bytes consumed
+/// so far, and is reported with the progress of every batch.
+///
+/// # Returns
+///
+/// Returns the merged aggregators, the skipped records (in no particular order)
+/// and the time in seconds spent on each batch.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the stream cannot be read. In strict mode,
+/// returns `CustomError::ParseError` for the first malformed record of the stream.
+fn aggregate_stream<R: Read + Send>(
+    pool: &rayon::ThreadPool,
+    reader: R,
+    compressed_read: Option<&AtomicU64>,
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<(AggregatorSet, Vec<SkippedRecord>, Vec<f64>), CustomError> {
+    let batches = RecordBatchReader::new(reader, config.input_format, config.stream_batch_size)?;
+    eprintln!("Input format: {}", batches.input_format());
+
+    let processed_batches = AtomicUsize::new(0);
+    let batch_seconds = Mutex::new(Vec::new());
+    let parse_options = ParseOptions {
+        keep_rejected: config.reject_file.is_some(),
+        strict: config.strict,
+        ..ParseOptions::default()
+    };
+    let filter = config.filter.as_ref();
+    let empty_partial = || (aggregators.clone(), Vec::new());
+    let process_batch =
+        |batch: RecordBatch| -> Result<(AggregatorSet, Vec<SkippedRecord>), CustomError> {
+            let started = Instant::now();
+            let mut partial = aggregators.clone();
+            let report = parse_batch_into(&mut partial, &batch, parse_options, filter)?;
+
+            let seconds = started.elapsed().as_secs_f64();
+            batch_seconds
+                .lock()
+                .map_err(|_| {
+                    CustomError::TransformationError("batch timings poisoned".to_string())
+                })?
+                .push(seconds);
+            let completed = processed_batches.fetch_add(1, Ordering::SeqCst) + 1;
+            let compressed_progress = compressed_read
+                .map(|read| {
+                    let read = read.load(Ordering::Relaxed);
+                    format!(
+                        " - {:.1} MiB of compressed input read",
+                        read as f64 / 1_048_576.0
+                    )
+                })
+                .unwrap_or_default();
+            eprintln!(
+                "Batch {completed} completed in {seconds:.3}s ({} messages processed, \
+                 {} malformed records skipped){compressed_progress}",
+                report.processed_count,
+                report.skipped_records.len()
+            );
+            Ok((partial, report.skipped_records))
+        };
+    let first_failure = FirstFailure::new();
+    let (sender, receiver) = sync_channel::<RecordBatch>(config.num_threads * 2);
+
+    let (merged, reader_result) = std::thread::scope(|scope| {
+        let first_failure = &first_failure;
+        let reader_thread = scope.spawn(move || -> Result<(), CustomError> {
+            for batch in batches {
+                let batch = batch?;
+                if first_failure.is_before((0, batch.offset)) {
+                    // An earlier batch failed in strict mode, nothing left to do.
+                    break;
+                }
+                if sender.send(batch).is_err() {
+                    break;
+                }
+            }
             Ok(())
-        })?;
+        });
+
+        let merged = pool.install(|| {
+            receiver
+                .into_iter()
+                .par_bridge()
+                .map(|batch| {
+                    let position = (0, batch.offset);
+                    if first_failure.is_before(position) {
+                        return empty_partial();
+                    }
+                    process_batch(batch).unwrap_or_else(|error| {
+                        first_failure.record(position, error);
+                        empty_partial()
+                    })
+                })
+                .reduce(empty_partial, |mut merged, mut partial| {
+                    merged.0.merge(partial.0);
+                    merged.1.append(&mut partial.1);
+                    merged
+                })
+        });
+        let reader_result = reader_thread
+            .join()
+            .unwrap_or_else(|_| Err(CustomError::IOError("reader thread panicked".to_string())));
+        (merged, reader_result)
+    });
+    first_failure.into_result()?;
+    reader_result?;
+    let (merged, skipped_records) = merged;
+    let batch_seconds = batch_seconds
+        .into_inner()
+        .map_err(|_| CustomError::TransformationError("batch timings poisoned".to_string()))?;
+    Ok((merged, skipped_records, batch_seconds))
+}
+
+/// Names the file of a skipped record when several files are analyzed together.
+fn tag_record(mut record: SkippedRecord, path: &str, multiple_files: bool) -> SkippedRecord {
+    if multiple_files {
+        record.file = Some(path.to_string());
+    }
+    record
+}
+
+/// Prefixes strict mode errors with the file they come from when several files
+/// are analyzed together.
+fn with_file_context(error: CustomError, path: &str, multiple_files: bool) -> CustomError {
+    match error {
+        CustomError::ParseError(message) if multiple_files => {
+            CustomError::ParseError(format!("{path}: {message}"))
+        }
+        other => other,
+    }
+}
+
+/// Summarizes the skipped records of a run, logs the totals (`total_messages` is the
+/// number of processed messages) and writes the reject file if `config.reject_file`
+/// is set. `skipped_records` must be in input order.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the reject file cannot be written.
+fn finish_skipped_records(
+    total_messages: u64,
+    skipped_records: &[SkippedRecord],
+    config: &AnalysisConfig,
+) -> Result<SkippedRecordsSummary, CustomError> {
+    let skipped = SkippedRecordsSummary::from_records(skipped_records);
+    eprintln!("Processing completed! Total messages processed: {total_messages}, {skipped}");
+
+    if let Some(reject_file) = &config.reject_file {
+        write_reject_file(reject_file, skipped_records)?;
+        eprintln!("Malformed records written to {reject_file}");
+    }
+
+    Ok(skipped)
+}
+
+/// Writes the skipped records to `path`, one JSON object per line.
+///
+/// # Errors
+///
+/// Returns `CustomError::IOError` if the file cannot be created or written.
+fn write_reject_file(path: &str, records: &[SkippedRecord]) -> Result<(), CustomError> {
+    let file = File::create(path).map_err(|e| CustomError::IOError(e.to_string()))?;
+    let mut writer = BufWriter::new(file);
+    for record in records {
+        serde_json::to_writer(&mut writer, record)
+            .map_err(|e| CustomError::IOError(e.to_string()))?;
+        writeln!(writer).map_err(|e| CustomError::IOError(e.to_string()))?;
+    }
+    writer
+        .flush()
+        .map_err(|e| CustomError::IOError(e.to_string()))
+}
 
-    let total = *aggregators.total_messages.lock().unwrap();
-    println!("Processing completed! Total messages processed: {total}");
-    Ok(aggregators)
+/// Ranks the entries of `counts` by message count (descending), breaking ties by name
+/// (ascending), and keeps the first `limit` entries with their names resolved.
+pub(crate) fn rank_counts<I: InternId>(
+    counts: &HashMap<I, u64>,
+    interner: &Interner<I>,
+    limit: Option<usize>,
+) -> Vec<(String, u64)> {
+    let mut ranked: Vec<(&str, u64)> = counts
+        .iter()
+        .map(|(&id, &count)| (interner.resolve(id), count))
+        .collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(b.0)));
+    if let Some(limit) = limit {
+        ranked.truncate(limit);
+    }
+    ranked
+        .into_iter()
+        .map(|(name, count)| (name.to_string(), count))
+        .collect()
 }
 
 /// Generates results for top channels by language from aggregated data.
 ///
-/// Processes the language-channel counts from the aggregators and returns
-/// the top 3 channels for each language, sorted by message count in descending order.
+/// Processes the language-channel counts and returns the top `limit` channels for each
+/// language, sorted by message count in descending order with ties broken by channel
+/// name. Languages are ordered according to `language_order`, so the output is the same
+/// regardless of the number of threads or chunks.
 ///
 /// # Arguments
 ///
-/// * `aggregators` - Reference to the `StreamingAggregators` containing the data
+/// * `language_channel_counts` - Message counts per channel, per language
+/// * `languages` - Interner that resolves the language IDs
+/// * `channels` - Interner that resolves the channel IDs
+/// * `limit` - Number of channels kept per language (`None` keeps all of them)
+/// * `language_order` - Order of the languages in the result
 ///
 /// # Returns
 ///
 /// A vector of `TopChannelsByLanguageResult` containing the top channels for each language.
-fn generate_top_channels_results(
-    aggregators: &StreamingAggregators,
+pub(crate) fn rank_channels_by_language(
+    language_channel_counts: &HashMap<LanguageId, HashMap<ChannelId, u64>>,
+    languages: &Interner<LanguageId>,
+    channels: &Interner<ChannelId>,
+    limit: Option<usize>,
+    language_order: LanguageOrder,
 ) -> Vec<TopChannelsByLanguageResult> {
-    let language_channel_counts = aggregators.language_channel_counts.lock().unwrap();
-
-    language_channel_counts
+    let mut ranked_languages: Vec<(&str, &HashMap<ChannelId, u64>, u64)> = language_channel_counts
         .iter()
-        .map(|(language, channel_counts)| {
-            let mut channels: Vec<(String, i32)> = channel_counts
-                .iter()
-                .map(|(k, v)| (k.clone(), *v))
-                .collect();
-            channels.sort_by(|a, b| b.1.cmp(&a.1));
-            channels.truncate(3);
+        .map(|(&language, channel_counts)| {
+            (
+                languages.resolve(language),
+                channel_counts,
+                channel_counts.values().sum(),
+            )
+        })
+        .collect();
+    match language_order {
+        LanguageOrder::Volume => {
+            ranked_languages.sort_by(|a, b| b.2.cmp(&a.2).then_with(|| a.0.cmp(b.0)));
+        }
+        LanguageOrder::Alphabetical => ranked_languages.sort_by(|a, b| a.0.cmp(b.0)),
+    }
 
-            let top_channels = channels
+    ranked_languages
+        .into_iter()
+        .map(|(language, channel_counts, _)| {
+            let top_channels = rank_counts(channel_counts, channels, limit)
                 .into_iter()
                 .map(|(channel_name, message_count)| ChannelMessageCount {
                     channel_name,
@@ -165,7 +1195,7 @@ fn generate_top_channels_results(
                 .collect();
 
             TopChannelsByLanguageResult {
-                language: language.clone(),
+                language: language.to_string(),
                 top_channels,
             }
         })
@@ -174,33 +1204,35 @@ fn generate_top_channels_results(
 
 /// Generates results for top languages by viewer range from aggregated data.
 ///
-/// Processes the viewer range-language counts from the aggregators and returns
-/// the top 5 languages for each viewer range, sorted by message count in descending order.
-/// Results are sorted by viewer range in logical order (0-100, 101-500, 501-1000, 1000+).
+/// Processes the viewer range-language counts and returns the top `limit` languages for
+/// each viewer range, sorted by message count in descending order with ties broken by
+/// language code. Results follow the order of the configured viewer ranges; ranges
+/// without messages are omitted.
 ///
 /// # Arguments
 ///
-/// * `aggregators` - Reference to the `StreamingAggregators` containing the data
+/// * `viewer_buckets` - Viewer ranges the counts are indexed by
+/// * `range_language_counts` - Message counts per language, per viewer range
+/// * `languages` - Interner that resolves the language IDs
+/// * `limit` - Number of languages kept per viewer range (`None` keeps all of them)
 ///
 /// # Returns
 ///
-/// A vector of `TopLanguagesByViewerRangeResult` containing the top languages for each viewer range.
-fn generate_top_languages_results(
-    aggregators: &StreamingAggregators,
+/// A vector of `TopLanguagesByViewerRangeResult` containing the top languages for
+/// each viewer range.
+pub(crate) fn rank_languages_by_viewer_range(
+    viewer_buckets: &ViewerBuckets,
+    range_language_counts: &[HashMap<LanguageId, u64>],
+    languages: &Interner<LanguageId>,
+    limit: Option<usize>,
 ) -> Vec<TopLanguagesByViewerRangeResult> {
-    let range_language_counts = aggregators.range_language_counts.lock().unwrap();
-
-    let mut results: Vec<TopLanguagesByViewerRangeResult> = range_language_counts
+    viewer_buckets
+        .ranges()
         .iter()
+        .zip(range_language_counts)
+        .filter(|(_, language_counts)| !language_counts.is_empty())
         .map(|(viewer_range, language_counts)| {
-            let mut languages: Vec<(String, i32)> = language_counts
-                .iter()
-                .map(|(k, v)| (k.clone(), *v))
-                .collect();
-            languages.sort_by(|a, b| b.1.cmp(&a.1));
-            languages.truncate(5);
-
-            let top_languages = languages
+            let top_languages = rank_counts(language_counts, languages, limit)
                 .into_iter()
                 .map(|(language, message_count)| LanguageMessageCount {
                     language,
@@ -209,31 +1241,108 @@ fn generate_top_languages_results(
                 .collect();
 
             TopLanguagesByViewerRangeResult {
-                viewer_range: viewer_range.clone(),
+                viewer_range: viewer_range.label.clone(),
+                lower_bound: viewer_range.lower_bound,
+                upper_bound: viewer_range.upper_bound,
                 top_languages,
             }
         })
-        .collect();
+        .collect()
+}
 
-    results.sort_by(|a, b| {
-        let order_a = match a.viewer_range.as_str() {
-            "0-100" => 0,
-            "101-500" => 1,
-            "501-1000" => 2,
-            "1000+" => 3,
-            _ => 999,
+/// Handles of the built-in rankings added to a set for an analysis type.
+struct BuiltInHandles {
+    top_channels: Option<AggregatorHandle<TopChannelsAggregator>>,
+    top_languages: Option<AggregatorHandle<TopLanguagesAggregator>>,
+    group_by: Option<AggregatorHandle<GroupByAggregator>>,
+    time_series: Option<AggregatorHandle<TimeWindowAggregator>>,
+}
+
+impl BuiltInHandles {
+    /// Adds the aggregators of the rankings requested by `analysis_type` to `aggregators`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::ArgumentError` if a group-by analysis is requested and
+    /// `config` has no query, or a time series analysis and `config` has no time window.
+    fn add(
+        aggregators: &mut AggregatorSet,
+        analysis_type: AnalysisType,
+        config: &AnalysisConfig,
+    ) -> Result<Self, CustomError> {
+        let top_channels = matches!(
+            analysis_type,
+            AnalysisType::TopChannels | AnalysisType::Both
+        )
+        .then(|| aggregators.add(TopChannelsAggregator::from_config(config)));
+        let top_languages = matches!(
+            analysis_type,
+            AnalysisType::TopLanguages | AnalysisType::Both
+        )
+        .then(|| aggregators.add(TopLanguagesAggregator::from_config(config)));
+        let group_by = if analysis_type == AnalysisType::GroupBy {
+            let query = config.query.clone().ok_or_else(|| {
+                CustomError::ArgumentError(
+                    "The group_by analysis requires a group-by query".to_string(),
+                )
+            })?;
+            Some(aggregators.add(GroupByAggregator::new(query, config.viewer_buckets.clone())))
+        } else {
+            None
         };
-        let order_b = match b.viewer_range.as_str() {
-            "0-100" => 0,
-            "101-500" => 1,
-            "501-1000" => 2,
-            "1000+" => 3,
-            _ => 999,
+        let time_series = if analysis_type == AnalysisType::TimeSeries {
+            let window = config.time_window.ok_or_else(|| {
+                CustomError::ArgumentError(
+                    "The time_series analysis requires a time window".to_string(),
+                )
+            })?;
+            Some(aggregators.add(TimeWindowAggregator::from_config(window, config)))
+        } else {
+            None
         };
-        order_a.cmp(&order_b)
-    });
+        Ok(Self {
+            top_channels,
+            top_languages,
+            group_by,
+            time_series,
+        })
+    }
+}
 
+/// Takes the output of an aggregator that is known to be in `results`.
+///
+/// # Panics
+///
+/// Panics if the output was already taken or `handle` belongs to another set.
+fn take_output<A: Aggregator>(
+    results: &mut AggregationResults,
+    handle: AggregatorHandle<A>,
+) -> A::Output {
     results
+        .take(handle)
+        .expect("every aggregator of the set has an output")
+}
+
+/// Runs `aggregators` over already resolved files, without expanding directories
+/// or patterns, and finalizes them with the statistics of the run.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, file processing fails
+/// or if a file cannot be read.
+fn run_aggregators_on_files(
+    input_files: Vec<String>,
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<AggregationResults, CustomError> {
+    let (merged, skipped_records, chunk_timings) =
+        process_file_streaming(&input_files, aggregators, config)?;
+    eprintln!("Generating results...");
+    let mut results = merged.finalize();
+    results.input_files = input_files;
+    results.skipped_records = skipped_records;
+    results.chunk_timings = chunk_timings;
+    Ok(results)
 }
 
 /// Analyzes a dataset and returns both top channels by language and top languages by viewer range.
@@ -251,17 +1360,34 @@ pub fn analyze_both(
     ),
     CustomError,
 > {
-    let aggregators = process_file_streaming(path, num_threads)?;
-
-    println!("Generating results...");
-    println!("Processing top channels by language...");
-    let top_channels = generate_top_channels_results(&aggregators);
-
-    println!("Processing top languages by viewer range...");
-    let top_languages = generate_top_languages_results(&aggregators);
+    analyze_both_with_config(path, &AnalysisConfig::new(num_threads))
+}
 
-    println!("Analysis complete!");
-    Ok((top_channels, top_languages))
+/// Same as [`analyze_both`], but with explicit worker, chunk and top-N configuration.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, file processing fails
+/// or if the file cannot be read.
+pub fn analyze_both_with_config(
+    path: &str,
+    config: &AnalysisConfig,
+) -> Result<
+    (
+        Vec<TopChannelsByLanguageResult>,
+        Vec<TopLanguagesByViewerRangeResult>,
+    ),
+    CustomError,
+> {
+    let mut aggregators = AggregatorSet::new();
+    let top_channels = aggregators.add(TopChannelsAggregator::from_config(config));
+    let top_languages = aggregators.add(TopLanguagesAggregator::from_config(config));
+    let mut results = run_aggregators_on_files(vec![path.to_string()], &aggregators, config)?;
+    eprintln!("Analysis complete!");
+    Ok((
+        take_output(&mut results, top_channels),
+        take_output(&mut results, top_languages),
+    ))
 }
 
 /// Analyzes a dataset and returns the top 3 channels by language.
@@ -273,11 +1399,25 @@ pub fn top_channels_by_language(
     path: &str,
     num_threads: usize,
 ) -> Result<Vec<TopChannelsByLanguageResult>, CustomError> {
-    let aggregators = process_file_streaming(path, num_threads)?;
-    println!("Generating top channels by language results...");
-    let results = generate_top_channels_results(&aggregators);
-    println!("Analysis complete!");
-    Ok(results)
+    top_channels_by_language_with_config(path, &AnalysisConfig::new(num_threads))
+}
+
+/// Analyzes a dataset and returns the top `config.top_channels` channels by language,
+/// using the worker and chunk configuration in `config`.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, file processing fails
+/// or if the file cannot be read.
+pub fn top_channels_by_language_with_config(
+    path: &str,
+    config: &AnalysisConfig,
+) -> Result<Vec<TopChannelsByLanguageResult>, CustomError> {
+    let mut aggregators = AggregatorSet::new();
+    let top_channels = aggregators.add(TopChannelsAggregator::from_config(config));
+    let mut results = run_aggregators_on_files(vec![path.to_string()], &aggregators, config)?;
+    eprintln!("Analysis complete!");
+    Ok(take_output(&mut results, top_channels))
 }
 
 /// Analyzes a dataset and returns the top 5 languages by viewer range.
@@ -289,9 +1429,275 @@ pub fn top_languages_by_viewer_range(
     path: &str,
     num_threads: usize,
 ) -> Result<Vec<TopLanguagesByViewerRangeResult>, CustomError> {
-    let aggregators = process_file_streaming(path, num_threads)?;
-    println!("Generating top languages by viewer range results...");
-    let results = generate_top_languages_results(&aggregators);
-    println!("Analysis complete!");
+    top_languages_by_viewer_range_with_config(path, &AnalysisConfig::new(num_threads))
+}
+
+/// Analyzes a dataset and returns the top `config.top_languages` languages by viewer range,
+/// using the worker and chunk configuration in `config`.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, file processing fails
+/// or if the file cannot be read.
+pub fn top_languages_by_viewer_range_with_config(
+    path: &str,
+    config: &AnalysisConfig,
+) -> Result<Vec<TopLanguagesByViewerRangeResult>, CustomError> {
+    let mut aggregators = AggregatorSet::new();
+    let top_languages = aggregators.add(TopLanguagesAggregator::from_config(config));
+    let mut results = run_aggregators_on_files(vec![path.to_string()], &aggregators, config)?;
+    eprintln!("Analysis complete!");
+    Ok(take_output(&mut results, top_languages))
+}
+
+/// Runs a group-by query over a dataset, using the worker and chunk configuration
+/// in `config`.
+///
+/// The built-in rankings can also be expressed as this query (see
+/// [`GroupByQuery::top_channels_by_language`]).
+///
+/// # Errors
+///
+/// Returns `CustomError::ArgumentError` if the query is invalid, and `CustomError`
+/// if the configuration is invalid, file processing fails or if the file cannot be read.
+pub fn group_by_with_config(
+    path: &str,
+    query: &GroupByQuery,
+    config: &AnalysisConfig,
+) -> Result<GroupByResult, CustomError> {
+    query.validate()?;
+    let mut aggregators = AggregatorSet::new();
+    let group_by = aggregators.add(GroupByAggregator::new(
+        query.clone(),
+        config.viewer_buckets.clone(),
+    ));
+    let mut results = run_aggregators_on_files(vec![path.to_string()], &aggregators, config)?;
+    eprintln!("Analysis complete!");
+    Ok(take_output(&mut results, group_by))
+}
+
+/// Ranks the channels of every language within each time window of a dataset,
+/// using the worker, chunk and ranking configuration in `config`.
+///
+/// Messages are placed in windows of `window` by their `createAt` timestamp, so the
+/// full records are parsed; messages without one are not counted.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, file processing fails
+/// or if the file cannot be read.
+pub fn time_series_with_config(
+    path: &str,
+    window: TimeWindow,
+    config: &AnalysisConfig,
+) -> Result<TimeSeriesResult, CustomError> {
+    let mut aggregators = AggregatorSet::new();
+    let time_series = aggregators.add(TimeWindowAggregator::from_config(window, config));
+    let mut results = run_aggregators_on_files(vec![path.to_string()], &aggregators, config)?;
+    eprintln!("Analysis complete!");
+    Ok(take_output(&mut results, time_series))
+}
+
+/// Runs any number of aggregators over several inputs in a single parallel pass.
+///
+/// Inputs are handled like in [`run_analysis_on_inputs`]: files, directories and glob
+/// patterns are expanded with [`resolve_input_files`], and `-` reads stdin. Every
+/// chunk is folded into a clone of `aggregators`, so the input is read and parsed only
+/// once; full records are only deserialized if some aggregator requires them.
+///
+/// # Arguments
+///
+/// * `inputs` - Files, directories or glob patterns to process
+/// * `aggregators` - Empty aggregators to run, added with [`AggregatorSet::add`]
+/// * `config` - Worker, chunk and parsing configuration for the run
+///
+/// # Returns
+///
+/// The output of every aggregator, taken with its handle, and the statistics of the run.
+///
+/// # Errors
+///
+/// Returns `CustomError::ArgumentError` if the inputs do not match any file, and
+/// `CustomError` if the configuration is invalid, file processing fails or if a
+/// file cannot be read.
+pub fn run_aggregators(
+    inputs: &[String],
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<AggregationResults, CustomError> {
+    if inputs.iter().any(|input| input == STDIN_INPUT) {
+        if inputs.len() > 1 {
+            return Err(CustomError::ArgumentError(format!(
+                "'{STDIN_INPUT}' (stdin) cannot be combined with other inputs"
+            )));
+        }
+        return run_aggregators_from_reader(std::io::stdin(), aggregators, config);
+    }
+
+    run_aggregators_on_files(resolve_input_files(inputs)?, aggregators, config)
+}
+
+/// Runs any number of aggregators over a stream, such as stdin or a decompressor.
+///
+/// The stream is read once, like in [`run_analysis_from_reader`], and the chunk
+/// timings of the results describe the batches it was split into.
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, the stream cannot be read
+/// or, in strict mode, a record is malformed.
+pub fn run_aggregators_from_reader<R: Read + Send>(
+    reader: R,
+    aggregators: &AggregatorSet,
+    config: &AnalysisConfig,
+) -> Result<AggregationResults, CustomError> {
+    let (merged, skipped_records, chunk_timings) =
+        process_reader_streaming(reader, aggregators, config)?;
+    eprintln!("Generating results...");
+    let mut results = merged.finalize();
+    results.skipped_records = skipped_records;
+    results.chunk_timings = chunk_timings;
     Ok(results)
 }
+
+/// Runs the requested analysis and returns its results together with the run metadata.
+///
+/// # Arguments
+///
+/// * `path` - Path to the file to process
+/// * `analysis_type` - Which rankings to compute
+/// * `config` - Worker, chunk and ranking configuration for the run
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, file processing fails
+/// or if the file cannot be read.
+pub fn run_analysis(
+    path: &str,
+    analysis_type: AnalysisType,
+    config: &AnalysisConfig,
+) -> Result<AnalysisReport, CustomError> {
+    run_analysis_on_inputs(&[path.to_string()], analysis_type, config)
+}
+
+/// Runs the requested analysis over several inputs, aggregated as a single dataset.
+///
+/// Inputs may be files, directories or glob patterns; they are expanded with
+/// [`resolve_input_files`] and all their chunks are processed in one parallel job.
+/// Only the aggregators of the requested rankings are run (see [`run_aggregators`]).
+/// The report's `input_file` is the first input, and `input_files` lists every
+/// analyzed file.
+///
+/// # Arguments
+///
+/// * `inputs` - Files, directories or glob patterns to process
+/// * `analysis_type` - Which rankings to compute
+/// * `config` - Worker, chunk and ranking configuration for the run
+///
+/// # Errors
+///
+/// Returns `CustomError::ArgumentError` if the inputs do not match any file, and
+/// `CustomError` if the configuration is invalid, file processing fails or if a
+/// file cannot be read.
+pub fn run_analysis_on_inputs(
+    inputs: &[String],
+    analysis_type: AnalysisType,
+    config: &AnalysisConfig,
+) -> Result<AnalysisReport, CustomError> {
+    let start = Instant::now();
+    let mut aggregators = AggregatorSet::new();
+    let handles = BuiltInHandles::add(&mut aggregators, analysis_type, config)?;
+    let results = run_aggregators(inputs, &aggregators, config)?;
+    Ok(build_report(
+        results,
+        handles,
+        inputs.first().cloned().unwrap_or_default(),
+        analysis_type,
+        config,
+        start,
+    ))
+}
+
+/// Runs the requested analysis over a stream, such as stdin or a decompressor.
+///
+/// The stream is read once, sequentially, and processed in batches by the workers
+/// (see [`RecordBatchReader`]). The report's `input_file` is `-` and `num_chunks`
+/// is the number of batches the stream was split into.
+///
+/// # Arguments
+///
+/// * `reader` - Stream to read the records from
+/// * `analysis_type` - Which rankings to compute
+/// * `config` - Worker, parsing and ranking configuration for the run
+///
+/// # Errors
+///
+/// Returns `CustomError` if the configuration is invalid, the stream cannot be read
+/// or, in strict mode, a record is malformed.
+pub fn run_analysis_from_reader<R: Read + Send>(
+    reader: R,
+    analysis_type: AnalysisType,
+    config: &AnalysisConfig,
+) -> Result<AnalysisReport, CustomError> {
+    let start = Instant::now();
+    let mut aggregators = AggregatorSet::new();
+    let handles = BuiltInHandles::add(&mut aggregators, analysis_type, config)?;
+    let results = run_aggregators_from_reader(reader, &aggregators, config)?;
+    Ok(build_report(
+        results,
+        handles,
+        STDIN_INPUT.to_string(),
+        analysis_type,
+        config,
+        start,
+    ))
+}
+
+/// Takes the rankings requested in `analysis_type` out of `results` and wraps them,
+/// with the run metadata, in a report. `start` is when the run began.
+fn build_report(
+    mut results: AggregationResults,
+    handles: BuiltInHandles,
+    input_file: String,
+    analysis_type: AnalysisType,
+    config: &AnalysisConfig,
+    start: Instant,
+) -> AnalysisReport {
+    let top_channels_by_language = handles
+        .top_channels
+        .map(|handle| take_output(&mut results, handle));
+    let top_languages_by_viewer_range = handles
+        .top_languages
+        .map(|handle| take_output(&mut results, handle));
+    let group_by = handles
+        .group_by
+        .map(|handle| take_output(&mut results, handle));
+    let time_series = handles
+        .time_series
+        .map(|handle| take_output(&mut results, handle));
+    eprintln!("Analysis complete!");
+
+    let metadata = RunMetadata {
+        input_file,
+        input_files: results.input_files,
+        analysis_type,
+        filter: config.filter.as_ref().map(ToString::to_string),
+        num_threads: config.num_threads,
+        num_chunks: results.chunk_timings.count,
+        chunk_timings: results.chunk_timings,
+        top_channels_limit: config.top_channels,
+        top_languages_limit: config.top_languages,
+        total_messages: results.total_messages,
+        matched_messages: results.matched_messages,
+        skipped_records: results.skipped_records,
+        elapsed_seconds: start.elapsed().as_secs_f64(),
+    };
+    AnalysisReport {
+        schema_version: REPORT_SCHEMA_VERSION,
+        metadata,
+        top_channels_by_language,
+        top_languages_by_viewer_range,
+        group_by,
+        time_series,
+    }
+}
@@ -0,0 +1,8 @@
+/// Number of messages with a given value of the ranked field of a group-by query.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize)]
+pub struct ValueCount {
+    /// Value of the ranked field
+    pub value: String,
+    /// Number of messages with the value
+    pub message_count: u64,
+}
@@ -0,0 +1,15 @@
+use crate::interner::InternId;
+
+/// Compact identifier of an interned field value of a group-by query.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct ValueId(pub u32);
+
+impl InternId for ValueId {
+    fn from_index(index: u32) -> Self {
+        ValueId(index)
+    }
+
+    fn index(self) -> u32 {
+        self.0
+    }
+}
@@ -0,0 +1,109 @@
+use crate::custom_error::CustomError;
+use crate::viewer_range::ViewerRange;
+
+/// Boundaries used by default, producing the ranges 0-100, 101-500, 501-1000 and 1000+.
+pub const DEFAULT_VIEWER_BOUNDARIES: [u64; 4] = [0, 100, 500, 1000];
+
+/// Ordered set of viewer-count ranges generated from a list of boundaries.
+///
+/// For boundaries `b0 < b1 < ... < bn` the ranges are `b0-b1`, `(b1+1)-b2`, ...,
+/// `(bn-1 + 1)-bn` and the open-ended `bn+` (every count greater than `bn`).
+/// Viewer counts below `b0` do not belong to any range.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewerBuckets {
+    ranges: Vec<ViewerRange>,
+}
+
+impl Default for ViewerBuckets {
+    fn default() -> Self {
+        Self::from_boundaries(&DEFAULT_VIEWER_BOUNDARIES)
+            .expect("default viewer boundaries are valid")
+    }
+}
+
+impl ViewerBuckets {
+    /// Builds the ranges for the given boundaries.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::ArgumentError` if fewer than two boundaries are given,
+    /// if they are not strictly increasing or if a boundary is `u64::MAX`, which
+    /// leaves no counts for the range above it.
+    pub fn from_boundaries(boundaries: &[u64]) -> Result<Self, CustomError> {
+        if boundaries.len() < 2 {
+            return Err(CustomError::ArgumentError(
+                "At least two viewer boundaries are required".to_string(),
+            ));
+        }
+        if boundaries.windows(2).any(|pair| pair[0] >= pair[1]) {
+            return Err(CustomError::ArgumentError(
+                "Viewer boundaries must be strictly increasing".to_string(),
+            ));
+        }
+
+        let next = |boundary: u64| {
+            boundary.checked_add(1).ok_or_else(|| {
+                CustomError::ArgumentError(format!(
+                    "Viewer boundaries must be lower than {}",
+                    u64::MAX
+                ))
+            })
+        };
+
+        let mut ranges = Vec::with_capacity(boundaries.len());
+        let mut lower_bound = boundaries[0];
+        for &upper_bound in &boundaries[1..] {
+            ranges.push(ViewerRange {
+                label: format!("{lower_bound}-{upper_bound}"),
+                lower_bound,
+                upper_bound: Some(upper_bound),
+            });
+            lower_bound = next(upper_bound)?;
+        }
+
+        let last = boundaries[boundaries.len() - 1];
+        ranges.push(ViewerRange {
+            label: format!("{last}+"),
+            lower_bound: next(last)?,
+            upper_bound: None,
+        });
+
+        Ok(Self { ranges })
+    }
+
+    /// Parses a comma-separated list of boundaries, e.g. `0,50,1000,10000`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `CustomError::ArgumentError` if a boundary is not a non-negative
+    /// integer or if the boundaries are invalid.
+    pub fn parse(spec: &str) -> Result<Self, CustomError> {
+        let boundaries = spec
+            .split(',')
+            .map(|value| {
+                value.trim().parse::<u64>().map_err(|_| {
+                    CustomError::ArgumentError(format!("Invalid viewer boundary '{value}'"))
+                })
+            })
+            .collect::<Result<Vec<u64>, CustomError>>()?;
+        Self::from_boundaries(&boundaries)
+    }
+
+    /// Returns the ranges in ascending order.
+    #[must_use]
+    pub fn ranges(&self) -> &[ViewerRange] {
+        &self.ranges
+    }
+
+    /// Returns the index of the range containing `viewer_count`, if any.
+    #[must_use]
+    pub fn range_index(&self, viewer_count: u64) -> Option<usize> {
+        let index = self
+            .ranges
+            .partition_point(|range| range.upper_bound.is_some_and(|upper| upper < viewer_count));
+        self.ranges
+            .get(index)
+            .filter(|range| range.contains(viewer_count))
+            .map(|_| index)
+    }
+}
@@ -0,0 +1,78 @@
+use serde::Deserializer;
+use serde::de::{self, Visitor};
+
+/// Deserializes a viewer count as a `u64` that cannot wrap.
+///
+/// Any non-negative JSON integer is accepted. Negative counts are rejected instead
+/// of wrapping into a high viewer range, so the record is skipped as a wrong type
+/// like one with a non-integer value (floats, strings, ...).
+///
+/// Meant to be used with `#[serde(deserialize_with = "crate::viewer_count::deserialize")]`.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the value is not a non-negative integer.
+pub fn deserialize<'de, D>(deserializer: D) -> Result<u64, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_u64(ViewerCountVisitor)
+}
+
+/// Deserializes an optional viewer count, validated like [`deserialize`].
+///
+/// Meant to be used on `Option<u64>` fields together with `#[serde(default)]`, so a
+/// missing field and an explicit `null` are both `None`.
+///
+/// # Errors
+///
+/// Returns the deserializer's error if the value is neither `null` nor a non-negative
+/// integer.
+pub fn deserialize_option<'de, D>(deserializer: D) -> Result<Option<u64>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    deserializer.deserialize_option(OptionalViewerCountVisitor)
+}
+
+/// Visitor for a viewer count that may be `null`.
+struct OptionalViewerCountVisitor;
+
+impl<'de> Visitor<'de> for OptionalViewerCountVisitor {
+    type Value = Option<u64>;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a non-negative integer viewer count or null")
+    }
+
+    fn visit_none<E: de::Error>(self) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+
+    fn visit_unit<E: de::Error>(self) -> Result<Option<u64>, E> {
+        Ok(None)
+    }
+
+    fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Option<u64>, D::Error> {
+        deserialize(deserializer).map(Some)
+    }
+}
+
+/// Visitor that accepts the integers in the `u64` range.
+struct ViewerCountVisitor;
+
+impl Visitor<'_> for ViewerCountVisitor {
+    type Value = u64;
+
+    fn expecting(&self, formatter: &mut std::fmt::Formatter) -> std::fmt::Result {
+        formatter.write_str("a non-negative integer viewer count")
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<u64, E> {
+        Ok(value)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<u64, E> {
+        u64::try_from(value).map_err(|_| E::invalid_value(de::Unexpected::Signed(value), &self))
+    }
+}
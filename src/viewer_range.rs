@@ -0,0 +1,19 @@
+/// A single viewer-count bucket with inclusive numeric bounds.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ViewerRange {
+    /// Human-readable label (e.g., "0-100", "101-500", "1000+")
+    pub label: String,
+    /// Smallest viewer count included in the range
+    pub lower_bound: u64,
+    /// Largest viewer count included in the range (`None` for the open-ended last range)
+    pub upper_bound: Option<u64>,
+}
+
+impl ViewerRange {
+    /// Returns true if `viewer_count` falls inside this range.
+    #[must_use]
+    pub fn contains(&self, viewer_count: u64) -> bool {
+        viewer_count >= self.lower_bound
+            && self.upper_bound.is_none_or(|upper| viewer_count <= upper)
+    }
+}
@@ -0,0 +1,195 @@
+use TP0ProgramacionConcurrente::aggregator::Aggregator;
+use TP0ProgramacionConcurrente::aggregator_set::AggregatorSet;
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::chat_message_ref::ChatMessageRef;
+use TP0ProgramacionConcurrente::chat_record::ChatRecord;
+use TP0ProgramacionConcurrente::top_channels_aggregator::TopChannelsAggregator;
+use TP0ProgramacionConcurrente::top_languages_aggregator::TopLanguagesAggregator;
+use TP0ProgramacionConcurrente::transformations::{
+    run_aggregators, run_aggregators_from_reader, run_analysis,
+};
+use common::create_test_file;
+use std::collections::HashMap;
+
+mod common;
+
+#[cfg(test)]
+mod aggregator_tests {
+    use super::*;
+
+    /// Highest viewer count seen for every language.
+    #[derive(Debug, Clone, Default)]
+    struct MaxViewersByLanguage(HashMap<String, u64>);
+
+    impl Aggregator for MaxViewersByLanguage {
+        type Output = HashMap<String, u64>;
+
+        fn update(&mut self, message: &ChatMessageRef<'_>) {
+            let max = self.0.entry(message.language.to_string()).or_insert(0);
+            *max = (*max).max(message.viewer_count);
+        }
+
+        fn merge(&mut self, other: Self) {
+            for (language, viewers) in other.0 {
+                let max = self.0.entry(language).or_insert(0);
+                *max = (*max).max(viewers);
+            }
+        }
+
+        fn finalize(self) -> Self::Output {
+            self.0
+        }
+    }
+
+    /// Number of messages sent by subscribers, which needs the full records.
+    #[derive(Debug, Clone, Default)]
+    struct SubscriberMessages(u64);
+
+    impl Aggregator for SubscriberMessages {
+        type Output = u64;
+
+        fn update(&mut self, _message: &ChatMessageRef<'_>) {}
+
+        fn update_record(&mut self, record: &ChatRecord) {
+            if record.is_subscriber == Some(true) {
+                self.0 += 1;
+            }
+        }
+
+        fn requires_full_record(&self) -> bool {
+            true
+        }
+
+        fn merge(&mut self, other: Self) {
+            self.0 += other.0;
+        }
+
+        fn finalize(self) -> u64 {
+            self.0
+        }
+    }
+
+    fn create_test_data() -> String {
+        let languages = ["en", "es", "fr"];
+        let mut content = String::new();
+        for i in 0..300 {
+            content.push_str(&format!(
+                r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}, "isSubscriber": {}}}"#,
+                i % 7,
+                languages[i % languages.len()],
+                (i * 53) % 2000,
+                i % 4 == 0
+            ));
+            content.push('\n');
+        }
+        content
+    }
+
+    #[test]
+    fn test_custom_aggregator_runs_with_built_ins() {
+        let (_temp_file, file_path) = create_test_file(&create_test_data());
+        let config = AnalysisConfig::new(3).with_chunks(5);
+
+        let mut aggregators = AggregatorSet::new();
+        let top_channels = aggregators.add(TopChannelsAggregator::from_config(&config));
+        let max_viewers = aggregators.add(MaxViewersByLanguage::default());
+        let mut results =
+            run_aggregators(std::slice::from_ref(&file_path), &aggregators, &config).unwrap();
+
+        assert_eq!(results.total_messages, 300);
+        assert_eq!(results.input_files, vec![file_path.clone()]);
+        assert_eq!(results.chunk_timings.count, 5);
+
+        let max_viewers = results.take(max_viewers).unwrap();
+        assert_eq!(max_viewers.len(), 3);
+        assert_eq!(max_viewers["en"], 1992);
+
+        let report = run_analysis(&file_path, AnalysisType::TopChannels, &config).unwrap();
+        assert_eq!(
+            Some(results.take(top_channels).unwrap()),
+            report.top_channels_by_language
+        );
+    }
+
+    #[test]
+    fn test_built_in_aggregators_match_analysis() {
+        let (_temp_file, file_path) = create_test_file(&create_test_data());
+        let expected =
+            run_analysis(&file_path, AnalysisType::Both, &AnalysisConfig::new(1)).unwrap();
+
+        for (threads, chunks) in [(1, 1), (2, 3), (4, 8)] {
+            let config = AnalysisConfig::new(threads).with_chunks(chunks);
+            let mut aggregators = AggregatorSet::new();
+            let top_languages = aggregators.add(TopLanguagesAggregator::from_config(&config));
+            let top_channels = aggregators.add(TopChannelsAggregator::from_config(&config));
+            let mut results =
+                run_aggregators(std::slice::from_ref(&file_path), &aggregators, &config).unwrap();
+
+            assert_eq!(
+                results.take(top_channels),
+                expected.top_channels_by_language
+            );
+            assert_eq!(
+                results.take(top_languages),
+                expected.top_languages_by_viewer_range
+            );
+        }
+    }
+
+    #[test]
+    fn test_full_record_aggregator_from_file_and_stream() {
+        let content = create_test_data();
+        let (_temp_file, file_path) = create_test_file(&content);
+        let config = AnalysisConfig::new(2).with_chunks(4);
+
+        let mut aggregators = AggregatorSet::new();
+        let subscribers = aggregators.add(SubscriberMessages::default());
+        let top_channels = aggregators.add(TopChannelsAggregator::from_config(&config));
+        assert!(aggregators.requires_full_record());
+
+        let mut from_file =
+            run_aggregators(std::slice::from_ref(&file_path), &aggregators, &config).unwrap();
+        let mut from_stream =
+            run_aggregators_from_reader(content.as_bytes(), &aggregators, &config).unwrap();
+
+        assert_eq!(from_file.take(subscribers), Some(75));
+        assert_eq!(from_stream.take(subscribers), Some(75));
+        assert_eq!(from_file.total_messages, 300);
+        assert_eq!(from_stream.total_messages, 300);
+        assert_eq!(from_file.take(top_channels), from_stream.take(top_channels));
+    }
+
+    #[test]
+    fn test_full_records_skip_missing_core_fields() {
+        let content = concat!(
+            r#"{"channelName": "gaming1", "language": "en", "viewerCount": 150, "isSubscriber": true}"#,
+            "\n",
+            r#"{"channelName": "gaming2", "viewerCount": 150, "isSubscriber": true}"#,
+            "\n",
+        );
+        let config = AnalysisConfig::new(1);
+
+        let mut aggregators = AggregatorSet::new();
+        let subscribers = aggregators.add(SubscriberMessages::default());
+        let mut results =
+            run_aggregators_from_reader(content.as_bytes(), &aggregators, &config).unwrap();
+
+        assert_eq!(results.take(subscribers), Some(1));
+        assert_eq!(results.total_messages, 1);
+        assert_eq!(results.skipped_records.total, 1);
+    }
+
+    #[test]
+    fn test_take_twice_returns_none() {
+        let mut aggregators = AggregatorSet::new();
+        let max_viewers = aggregators.add(MaxViewersByLanguage::default());
+        let subscribers = aggregators.add(SubscriberMessages::default());
+        assert_eq!(aggregators.len(), 2);
+
+        let mut results = aggregators.finalize();
+        assert_eq!(results.take(subscribers), Some(0));
+        assert_eq!(results.take(subscribers), None);
+        assert_eq!(results.take(max_viewers), Some(HashMap::new()));
+    }
+}
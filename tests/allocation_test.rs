@@ -0,0 +1,105 @@
+use TP0ProgramacionConcurrente::chunk_info::ChunkInfo;
+use TP0ProgramacionConcurrente::parse_options::ParseOptions;
+use TP0ProgramacionConcurrente::parser::{parse_chunk_borrowed, parse_chunk_with_report};
+use TP0ProgramacionConcurrente::streaming_aggregators::StreamingAggregators;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::cell::Cell;
+use std::fs;
+use tempfile::TempDir;
+
+/// Counts the allocations made by the current thread.
+struct CountingAllocator;
+
+thread_local! {
+    static ALLOCATIONS: Cell<usize> = const { Cell::new(0) };
+}
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        let _ = ALLOCATIONS.try_with(|count| count.set(count.get() + 1));
+        unsafe { System.alloc(layout) }
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        unsafe { System.dealloc(ptr, layout) }
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+fn allocations_during(f: impl FnOnce()) -> usize {
+    let before = ALLOCATIONS.with(Cell::get);
+    f();
+    ALLOCATIONS.with(Cell::get) - before
+}
+
+#[cfg(test)]
+mod allocation_tests {
+    use super::*;
+
+    const NUM_MESSAGES: usize = 2000;
+
+    fn create_test_file() -> (TempDir, String, ChunkInfo) {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("allocations.json");
+        let languages = ["en", "es", "ru"];
+        let content: Vec<String> = (0..NUM_MESSAGES)
+            .map(|i| {
+                format!(
+                    r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}}}"#,
+                    i % 10,
+                    languages[i % languages.len()],
+                    i
+                )
+            })
+            .collect();
+        let content = format!("[{}]", content.join(",\n"));
+        fs::write(&test_file, &content).unwrap();
+        let chunk = ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: content.len() as u64,
+            size: content.len() as u64,
+        };
+        (temp_dir, test_file.to_string_lossy().to_string(), chunk)
+    }
+
+    #[test]
+    fn test_borrowed_path_only_allocates_new_keys() {
+        let (_temp_dir, path, chunk) = create_test_file();
+
+        let mut owned = StreamingAggregators::new();
+        let owned_allocations = allocations_during(|| {
+            parse_chunk_with_report(&path, &chunk, ParseOptions::default(), |message| {
+                owned.process_message(message);
+            })
+            .unwrap();
+        });
+
+        let mut borrowed = StreamingAggregators::new();
+        let borrowed_allocations = allocations_during(|| {
+            parse_chunk_borrowed(&path, &chunk, ParseOptions::default(), |message| {
+                borrowed.process_message_ref(message);
+            })
+            .unwrap();
+        });
+
+        assert_eq!(owned.total_messages, NUM_MESSAGES as u64);
+        assert_eq!(borrowed.total_messages, NUM_MESSAGES as u64);
+        assert_eq!(
+            owned.language_channel_counts_by_name(),
+            borrowed.language_channel_counts_by_name()
+        );
+        assert_eq!(
+            owned.range_language_counts_by_name(),
+            borrowed.range_language_counts_by_name()
+        );
+
+        assert!(owned_allocations >= 2 * NUM_MESSAGES);
+        assert!(
+            borrowed_allocations < 200,
+            "borrowed path made {borrowed_allocations} allocations"
+        );
+    }
+}
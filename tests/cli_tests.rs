@@ -0,0 +1,358 @@
+use TP0ProgramacionConcurrente::analysis_config::DEFAULT_MIN_SPLIT_SIZE;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::cli_options::CliOptions;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::filter_expr::FilterExpr;
+use TP0ProgramacionConcurrente::group_order::GroupOrder;
+use TP0ProgramacionConcurrente::input_format::InputFormat;
+use TP0ProgramacionConcurrente::language_order::LanguageOrder;
+use TP0ProgramacionConcurrente::output_format::OutputFormat;
+use TP0ProgramacionConcurrente::query_field::QueryField;
+use TP0ProgramacionConcurrente::time_window::TimeWindow;
+
+#[cfg(test)]
+mod cli_tests {
+    use super::*;
+
+    fn args(values: &[&str]) -> Vec<String> {
+        values.iter().map(|v| v.to_string()).collect()
+    }
+
+    #[test]
+    fn test_positional_arguments() {
+        let options = CliOptions::parse(&args(&["data.json", "4", "top_channels"])).unwrap();
+
+        assert_eq!(options.inputs, vec!["data.json"]);
+        assert_eq!(options.analysis_type, AnalysisType::TopChannels);
+        assert_eq!(options.config.num_threads, 4);
+        assert_eq!(options.config.num_chunks, 16);
+        assert_eq!(options.config.adaptive_split, None);
+        assert_eq!(options.config.top_channels, Some(3));
+        assert_eq!(options.config.top_languages, Some(5));
+        assert_eq!(options.config.reject_file, None);
+    }
+
+    #[test]
+    fn test_multiple_inputs() {
+        let options =
+            CliOptions::parse(&args(&["day1.json", "logs/", "*.ndjson", "8", "both"])).unwrap();
+        assert_eq!(options.inputs, vec!["day1.json", "logs/", "*.ndjson"]);
+        assert_eq!(options.config.num_threads, 8);
+        assert_eq!(options.analysis_type, AnalysisType::Both);
+
+        let options = CliOptions::parse(&args(&["day1.json", "day2.json", "4"])).unwrap();
+        assert_eq!(options.inputs, vec!["day1.json", "day2.json"]);
+        assert_eq!(options.analysis_type, AnalysisType::Both);
+
+        assert!(CliOptions::parse(&args(&["day1.json", "day2.json"])).is_err());
+    }
+
+    #[test]
+    fn test_default_analysis_type_is_both() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert_eq!(options.analysis_type, AnalysisType::Both);
+    }
+
+    #[test]
+    fn test_chunks_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2", "--chunks", "16"])).unwrap();
+
+        assert_eq!(options.config.num_threads, 2);
+        assert_eq!(options.config.num_chunks, 16);
+    }
+
+    #[test]
+    fn test_adaptive_split_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2", "--adaptive-split"])).unwrap();
+
+        assert_eq!(options.config.adaptive_split, Some(DEFAULT_MIN_SPLIT_SIZE));
+        assert_eq!(options.config.num_chunks, 8);
+    }
+
+    #[test]
+    fn test_top_n_options() {
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--top-channels",
+            "10",
+            "--top-languages",
+            "all",
+        ]))
+        .unwrap();
+
+        assert_eq!(options.config.top_channels, Some(10));
+        assert_eq!(options.config.top_languages, None);
+    }
+
+    #[test]
+    fn test_zero_top_n_limits_are_rejected() {
+        for option in ["--top-channels", "--top-languages"] {
+            let result = CliOptions::parse(&args(&["data.json", "2", option, "0"]));
+            assert!(
+                matches!(&result, Err(CustomError::ArgumentError(msg)) if msg.contains(option)),
+                "expected {option} 0 to be rejected, got {result:?}"
+            );
+        }
+
+        let result = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--group-by",
+            "language",
+            "--top-by",
+            "channelName",
+            "--limit",
+            "0",
+        ]));
+        assert!(matches!(result, Err(CustomError::ArgumentError(_))));
+    }
+
+    #[test]
+    fn test_viewer_buckets_option() {
+        let options =
+            CliOptions::parse(&args(&["data.json", "2", "--viewer-buckets", "0,50,1000"])).unwrap();
+
+        let labels: Vec<&str> = options
+            .config
+            .viewer_buckets
+            .ranges()
+            .iter()
+            .map(|r| r.label.as_str())
+            .collect();
+        assert_eq!(labels, vec!["0-50", "51-1000", "1000+"]);
+    }
+
+    #[test]
+    fn test_format_options() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert_eq!(options.format, OutputFormat::Text);
+
+        let options = CliOptions::parse(&args(&["data.json", "2", "--format", "json"])).unwrap();
+        assert_eq!(options.format, OutputFormat::Json { pretty: true });
+
+        let options =
+            CliOptions::parse(&args(&["data.json", "2", "--format", "json", "--compact"])).unwrap();
+        assert_eq!(options.format, OutputFormat::Json { pretty: false });
+
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--compact"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--format", "xml"])).is_err());
+
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--format",
+            "csv",
+            "--output",
+            "out.csv",
+        ]))
+        .unwrap();
+        assert_eq!(options.format, OutputFormat::Csv);
+        assert_eq!(options.output_path.as_deref(), Some("out.csv"));
+        assert!(
+            CliOptions::parse(&args(&["data.json", "2", "--format", "csv", "--compact"])).is_err()
+        );
+    }
+
+    #[test]
+    fn test_language_order_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert_eq!(options.config.language_order, LanguageOrder::Volume);
+
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--language-order",
+            "alphabetical",
+        ]))
+        .unwrap();
+        assert_eq!(options.config.language_order, LanguageOrder::Alphabetical);
+
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--language-order", "x"])).is_err());
+    }
+
+    #[test]
+    fn test_reject_file_option() {
+        let options =
+            CliOptions::parse(&args(&["data.json", "2", "--reject-file", "bad.jsonl"])).unwrap();
+        assert_eq!(options.config.reject_file.as_deref(), Some("bad.jsonl"));
+    }
+
+    #[test]
+    fn test_input_format_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert_eq!(options.config.input_format, None);
+
+        let options =
+            CliOptions::parse(&args(&["data.json", "2", "--input-format", "ndjson"])).unwrap();
+        assert_eq!(options.config.input_format, Some(InputFormat::Ndjson));
+
+        let options =
+            CliOptions::parse(&args(&["data.json", "2", "--input-format", "array"])).unwrap();
+        assert_eq!(options.config.input_format, Some(InputFormat::JsonArray));
+
+        let options =
+            CliOptions::parse(&args(&["data.json", "2", "--input-format", "auto"])).unwrap();
+        assert_eq!(options.config.input_format, None);
+
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--input-format", "csv"])).is_err());
+    }
+
+    #[test]
+    fn test_mmap_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert!(!options.config.use_mmap);
+
+        let options = CliOptions::parse(&args(&["data.json", "2", "--mmap"])).unwrap();
+        assert!(options.config.use_mmap);
+    }
+
+    #[test]
+    fn test_strict_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert!(!options.config.strict);
+
+        let options = CliOptions::parse(&args(&["data.json", "2", "--strict"])).unwrap();
+        assert!(options.config.strict);
+    }
+
+    #[test]
+    fn test_where_option() {
+        let options = CliOptions::parse(&args(&["data.json", "2"])).unwrap();
+        assert_eq!(options.config.filter, None);
+
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--where",
+            "language = es and viewerCount > 1000",
+        ]))
+        .unwrap();
+        let expected: FilterExpr = "language = es and viewerCount > 1000".parse().unwrap();
+        assert_eq!(options.config.filter, Some(expected));
+
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--where",
+            "language = es or language = en",
+            "--where",
+            "channelName not in (bot1, bot2)",
+        ]))
+        .unwrap();
+        assert_eq!(
+            options.config.filter.unwrap().to_string(),
+            "(language = 'es' or language = 'en') and not channelName in ('bot1', 'bot2')"
+        );
+
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--where"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--where", "language >"])).is_err());
+    }
+
+    #[test]
+    fn test_group_by_options() {
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--group-by",
+            "language,categoryName",
+            "--top-by",
+            "channelName",
+            "--limit",
+            "all",
+            "--group-order",
+            "alphabetical",
+        ]))
+        .unwrap();
+        assert_eq!(options.analysis_type, AnalysisType::GroupBy);
+        let query = options.config.query.unwrap();
+        assert_eq!(
+            query.group_by,
+            vec![QueryField::Language, QueryField::CategoryName]
+        );
+        assert_eq!(query.top_by, QueryField::ChannelName);
+        assert_eq!(query.limit, None);
+        assert_eq!(query.group_order, GroupOrder::Alphabetical);
+
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--group-by",
+            "viewerRange",
+            "--top-by",
+            "language",
+        ]))
+        .unwrap();
+        assert_eq!(options.config.query.unwrap().limit, Some(10));
+
+        let invalid: [&[&str]; 5] = [
+            &["data.json", "2", "--group-by", "language"],
+            &["data.json", "2", "--top-by", "language"],
+            &["data.json", "2", "--limit", "5"],
+            &[
+                "data.json",
+                "2",
+                "both",
+                "--group-by",
+                "language",
+                "--top-by",
+                "channelName",
+            ],
+            &[
+                "data.json",
+                "2",
+                "--group-by",
+                "game",
+                "--top-by",
+                "channelName",
+            ],
+        ];
+        for values in invalid {
+            assert!(CliOptions::parse(&args(values)).is_err(), "{values:?}");
+        }
+    }
+
+    #[test]
+    fn test_window_option() {
+        let options = CliOptions::parse(&args(&[
+            "data.json",
+            "2",
+            "--window",
+            "hour",
+            "--top-channels",
+            "5",
+        ]))
+        .unwrap();
+        assert_eq!(options.analysis_type, AnalysisType::TimeSeries);
+        assert_eq!(options.config.time_window, Some(TimeWindow::Hour));
+        assert_eq!(options.config.top_channels, Some(5));
+
+        let invalid: [&[&str]; 3] = [
+            &["data.json", "2", "--window", "week"],
+            &["data.json", "2", "top_channels", "--window", "day"],
+            &[
+                "data.json",
+                "2",
+                "--window",
+                "day",
+                "--group-by",
+                "language",
+                "--top-by",
+                "channelName",
+            ],
+        ];
+        for values in invalid {
+            assert!(CliOptions::parse(&args(values)).is_err(), "{values:?}");
+        }
+    }
+
+    #[test]
+    fn test_invalid_arguments() {
+        assert!(CliOptions::parse(&args(&["data.json"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "abc"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "2", "other"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--chunks"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--unknown", "1"])).is_err());
+        assert!(CliOptions::parse(&args(&["data.json", "2", "--top-channels", "x"])).is_err());
+    }
+}
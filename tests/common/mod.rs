@@ -0,0 +1,45 @@
+//! Fixtures shared by the integration test suites.
+//!
+//! Every suite declares `mod common;` and only uses some of the helpers.
+#![allow(dead_code)]
+
+use TP0ProgramacionConcurrente::chat_message::ChatMessage;
+use TP0ProgramacionConcurrente::chat_message_ref::ChatMessageRef;
+use std::borrow::Cow;
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+/// Writes `content` to a temporary file and returns it together with its path.
+///
+/// The file is deleted when the returned `NamedTempFile` is dropped, so keep it
+/// alive for as long as the path is used.
+pub fn create_test_file(content: &str) -> (NamedTempFile, String) {
+    let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+    temp_file
+        .write_all(content.as_bytes())
+        .expect("Failed to write test data");
+    let file_path = temp_file.path().to_str().unwrap().to_string();
+    (temp_file, file_path)
+}
+
+/// Builds a message with the fields used by the built-in analyses.
+pub fn message(channel_name: &str, language: &str, viewer_count: u64) -> ChatMessage {
+    ChatMessage {
+        channel_name: channel_name.to_string(),
+        language: language.to_string(),
+        viewer_count,
+    }
+}
+
+/// Builds a message like [`message`], as the borrowed model the aggregators take.
+pub fn message_ref(
+    channel_name: &str,
+    language: &str,
+    viewer_count: u64,
+) -> ChatMessageRef<'static> {
+    ChatMessageRef {
+        channel_name: Cow::Owned(channel_name.to_string()),
+        language: Cow::Owned(language.to_string()),
+        viewer_count,
+    }
+}
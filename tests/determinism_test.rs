@@ -0,0 +1,144 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::language_order::LanguageOrder;
+use TP0ProgramacionConcurrente::output_format::OutputFormat;
+use TP0ProgramacionConcurrente::report_writer::writer_for;
+use TP0ProgramacionConcurrente::transformations::{run_analysis, top_channels_by_language};
+use std::io::Write;
+use tempfile::NamedTempFile;
+
+#[cfg(test)]
+mod determinism_tests {
+    use super::*;
+
+    fn create_tied_test_data() -> (NamedTempFile, String) {
+        let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+
+        let mut content = String::new();
+        let languages = ["fr", "en", "es", "de"];
+        for i in 0..200 {
+            let language = languages[i % languages.len()];
+            // Every channel of a language gets the same number of messages
+            let channel = format!("{}_channel{}", language, (i / languages.len()) % 5);
+            content.push_str(&format!(
+                r#"{{"channelName": "{}", "language": "{}", "viewerCount": {}}}"#,
+                channel,
+                language,
+                (i * 37) % 1500
+            ));
+            content.push('\n');
+        }
+        // Give "en" the largest volume and "de" one extra message
+        content.push_str(r#"{"channelName": "en_channel9", "language": "en", "viewerCount": 10}"#);
+        content.push('\n');
+        content.push_str(r#"{"channelName": "en_channel9", "language": "en", "viewerCount": 10}"#);
+        content.push('\n');
+        content.push_str(r#"{"channelName": "de_channel9", "language": "de", "viewerCount": 10}"#);
+
+        temp_file
+            .write_all(content.as_bytes())
+            .expect("Failed to write test data");
+        let file_path = temp_file.path().to_str().unwrap().to_string();
+        (temp_file, file_path)
+    }
+
+    fn render_rankings(file_path: &str, config: &AnalysisConfig) -> Vec<u8> {
+        let report = run_analysis(file_path, AnalysisType::Both, config).unwrap();
+        let mut out = Vec::new();
+        writer_for(OutputFormat::Csv)
+            .write_report(&report, &mut out)
+            .unwrap();
+
+        let mut json = serde_json::to_value(&report).unwrap();
+        json.as_object_mut().unwrap().remove("metadata");
+        out.extend(serde_json::to_vec(&json).unwrap());
+        out
+    }
+
+    #[test]
+    fn test_byte_identical_output_across_thread_counts() {
+        let (_temp_file, file_path) = create_tied_test_data();
+
+        let expected = render_rankings(&file_path, &AnalysisConfig::new(1));
+        for (threads, chunks) in [(1, 7), (2, 2), (3, 5), (4, 4), (8, 8), (2, 16)] {
+            let config = AnalysisConfig::new(threads).with_chunks(chunks);
+            for _ in 0..3 {
+                assert_eq!(
+                    render_rankings(&file_path, &config),
+                    expected,
+                    "output differs with {threads} threads and {chunks} chunks"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_adaptive_split_matches_planned_chunks() {
+        let (_temp_file, file_path) = create_tied_test_data();
+
+        let expected = render_rankings(&file_path, &AnalysisConfig::new(1).with_chunks(1));
+        for (threads, chunks) in [(1, 1), (2, 1), (4, 3), (4, 16)] {
+            let config = AnalysisConfig::new(threads)
+                .with_chunks(chunks)
+                .with_adaptive_split(Some(64));
+            assert_eq!(
+                render_rankings(&file_path, &config),
+                expected,
+                "output differs with {threads} threads, {chunks} chunks and adaptive splitting"
+            );
+        }
+    }
+
+    #[test]
+    fn test_chunk_timings_reported() {
+        let (_temp_file, file_path) = create_tied_test_data();
+
+        let config = AnalysisConfig::new(2).with_chunks(6);
+        let report = run_analysis(&file_path, AnalysisType::Both, &config).unwrap();
+        let timings = &report.metadata.chunk_timings;
+        assert_eq!(timings.count, 6);
+        assert_eq!(report.metadata.num_chunks, 6);
+        assert!(timings.min_seconds <= timings.mean_seconds);
+        assert!(timings.mean_seconds <= timings.max_seconds);
+        assert!(timings.imbalance >= 1.0);
+    }
+
+    #[test]
+    fn test_ties_broken_by_name() {
+        let (_temp_file, file_path) = create_tied_test_data();
+
+        let results = top_channels_by_language(&file_path, 4).unwrap();
+        let fr = results.iter().find(|r| r.language == "fr").unwrap();
+        let names: Vec<&str> = fr
+            .top_channels
+            .iter()
+            .map(|c| c.channel_name.as_str())
+            .collect();
+        assert_eq!(names, vec!["fr_channel0", "fr_channel1", "fr_channel2"]);
+    }
+
+    #[test]
+    fn test_languages_ordered_by_volume() {
+        let (_temp_file, file_path) = create_tied_test_data();
+
+        let results = top_channels_by_language(&file_path, 2).unwrap();
+        let languages: Vec<&str> = results.iter().map(|r| r.language.as_str()).collect();
+        assert_eq!(languages, vec!["en", "de", "es", "fr"]);
+    }
+
+    #[test]
+    fn test_languages_ordered_alphabetically() {
+        let (_temp_file, file_path) = create_tied_test_data();
+
+        let config = AnalysisConfig::new(2).with_language_order(LanguageOrder::Alphabetical);
+        let report = run_analysis(&file_path, AnalysisType::TopChannels, &config).unwrap();
+        let languages: Vec<&str> = report
+            .top_channels_by_language
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|r| r.language.as_str())
+            .collect();
+        assert_eq!(languages, vec!["de", "en", "es", "fr"]);
+    }
+}
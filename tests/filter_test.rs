@@ -0,0 +1,167 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::comparison_op::ComparisonOp;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::filter_expr::FilterExpr;
+use TP0ProgramacionConcurrente::filter_field::FilterField;
+use TP0ProgramacionConcurrente::filter_value::FilterValue;
+use TP0ProgramacionConcurrente::transformations::{run_analysis, run_analysis_from_reader};
+use common::{create_test_file, message};
+
+mod common;
+
+#[cfg(test)]
+mod filter_tests {
+    use super::*;
+
+    fn parse(expression: &str) -> FilterExpr {
+        expression.parse().unwrap()
+    }
+
+    fn parse_error(expression: &str) -> String {
+        match expression.parse::<FilterExpr>() {
+            Err(CustomError::ArgumentError(msg)) => msg,
+            other => panic!("expected an argument error for '{expression}', got {other:?}"),
+        }
+    }
+
+    fn create_test_data() -> String {
+        let mut content = String::new();
+        for (channel, language, viewers, count) in [
+            ("es_big", "es", 5000, 4),
+            ("es_small", "es", 50, 6),
+            ("en_big", "en", 2000, 3),
+            ("en_bot", "en", 2000, 9),
+            ("fr_mid", "fr", 700, 2),
+        ] {
+            for _ in 0..count {
+                content.push_str(&format!(
+                    r#"{{"channelName": "{channel}", "language": "{language}", "viewerCount": {viewers}}}"#
+                ));
+                content.push('\n');
+            }
+        }
+        content
+    }
+
+    #[test]
+    fn test_parse_comparison() {
+        assert_eq!(
+            parse("viewerCount >= 1000"),
+            FilterExpr::Compare {
+                field: FilterField::ViewerCount,
+                op: ComparisonOp::Ge,
+                value: FilterValue::Number(1000),
+            }
+        );
+        assert_eq!(
+            parse(r#"channelName == "some channel""#),
+            FilterExpr::Compare {
+                field: FilterField::ChannelName,
+                op: ComparisonOp::Eq,
+                value: FilterValue::Text("some channel".to_string()),
+            }
+        );
+        assert_eq!(
+            parse("language not in (en, 'pt-br')"),
+            FilterExpr::Not(Box::new(FilterExpr::In {
+                field: FilterField::Language,
+                values: vec![
+                    FilterValue::Text("en".to_string()),
+                    FilterValue::Text("pt-br".to_string()),
+                ],
+            }))
+        );
+    }
+
+    #[test]
+    fn test_precedence_and_display() {
+        assert_eq!(
+            parse("language = es or language = en and viewerCount > 10"),
+            parse("language = es or (language = en and viewerCount > 10)")
+        );
+        assert_eq!(
+            parse("NOT language = es AND viewerCount < 5"),
+            parse("(not language = es) and viewerCount < 5")
+        );
+
+        for expression in [
+            "language = 'es' and viewerCount > 1000",
+            "(language = 'es' or language = 'en') and not channelName in ('bot1', 'bot2')",
+            "language = 'es' or language = 'en' and viewerCount <= 10",
+            "not (viewerCount != 0 or channelName = 'it\\'s')",
+            "language = 'en' and (viewerCount > 1 and viewerCount < 9)",
+        ] {
+            let filter = parse(expression);
+            assert_eq!(filter.to_string(), expression);
+            assert_eq!(parse(&filter.to_string()), filter);
+        }
+    }
+
+    #[test]
+    fn test_matches() {
+        let filter = parse("language = es and viewerCount > 1000");
+        assert!(filter.matches_message(&message("a", "es", 1001)));
+        assert!(!filter.matches_message(&message("a", "es", 1000)));
+        assert!(!filter.matches_message(&message("a", "en", 5000)));
+
+        let filter = parse("channelName not in (bot1, bot2) or viewerCount = 0");
+        assert!(filter.matches_message(&message("gaming1", "en", 10)));
+        assert!(!filter.matches_message(&message("bot2", "en", 10)));
+        assert!(filter.matches_message(&message("bot2", "en", 0)));
+
+        let mismatched = FilterExpr::Compare {
+            field: FilterField::ViewerCount,
+            op: ComparisonOp::Eq,
+            value: FilterValue::Text("10".to_string()),
+        };
+        assert!(!mismatched.matches_message(&message("a", "en", 10)));
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(parse_error("").contains("expected a field name"));
+        assert!(parse_error("game = x").contains("unknown field 'game'"));
+        assert!(parse_error("language > es").contains("can only be used with viewerCount"));
+        assert!(parse_error("viewerCount > lots").contains("not a valid number"));
+        assert!(parse_error("viewerCount = '10'").contains("must be compared with a number"));
+        assert!(parse_error("language = 'es").contains("unterminated quoted value"));
+        assert!(parse_error("language = es)").contains("unexpected ')'"));
+        assert!(parse_error("(language = es").contains("expected ')'"));
+        assert!(parse_error("language in (en,)").contains("expected a value"));
+        assert!(parse_error("language ! es").contains("expected '=' after '!'"));
+        assert!(parse_error("language = es and").contains("(at offset 17)"));
+    }
+
+    #[test]
+    fn test_filter_applied_before_aggregation() {
+        let content = create_test_data();
+        let (_temp_file, file_path) = create_test_file(&content);
+
+        let config = AnalysisConfig::new(2)
+            .with_chunks(3)
+            .with_filter(Some(parse("viewerCount > 1000 and channelName != en_bot")));
+        let from_file = run_analysis(&file_path, AnalysisType::Both, &config).unwrap();
+        let from_stream =
+            run_analysis_from_reader(content.as_bytes(), AnalysisType::Both, &config).unwrap();
+
+        for report in [&from_file, &from_stream] {
+            assert_eq!(report.metadata.total_messages, 24);
+            assert_eq!(report.metadata.matched_messages, 7);
+            assert_eq!(
+                report.metadata.filter.as_deref(),
+                Some("viewerCount > 1000 and channelName != 'en_bot'")
+            );
+
+            let top_channels = report.top_channels_by_language.as_ref().unwrap();
+            let languages: Vec<&str> = top_channels.iter().map(|r| r.language.as_str()).collect();
+            assert_eq!(languages, vec!["es", "en"]);
+            assert_eq!(top_channels[0].top_channels.len(), 1);
+            assert_eq!(top_channels[0].top_channels[0].channel_name, "es_big");
+
+            let top_languages = report.top_languages_by_viewer_range.as_ref().unwrap();
+            assert_eq!(top_languages.len(), 1);
+            assert_eq!(top_languages[0].viewer_range, "1000+");
+        }
+    }
+}
@@ -0,0 +1,226 @@
+use TP0ProgramacionConcurrente::aggregator::Aggregator;
+use TP0ProgramacionConcurrente::aggregator_set::AggregatorSet;
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::group_by_aggregator::GroupByAggregator;
+use TP0ProgramacionConcurrente::group_by_query::GroupByQuery;
+use TP0ProgramacionConcurrente::group_by_result::GroupByResult;
+use TP0ProgramacionConcurrente::group_order::GroupOrder;
+use TP0ProgramacionConcurrente::language_order::LanguageOrder;
+use TP0ProgramacionConcurrente::query_field::QueryField;
+use TP0ProgramacionConcurrente::transformations::{
+    group_by_with_config, run_aggregators_from_reader, run_analysis,
+};
+use TP0ProgramacionConcurrente::viewer_buckets::ViewerBuckets;
+use common::{create_test_file, message_ref};
+
+mod common;
+
+#[cfg(test)]
+mod group_by_tests {
+    use super::*;
+
+    fn create_test_data() -> String {
+        let categories = ["Just Chatting", "Chess", "Music"];
+        let languages = ["en", "es"];
+        let mut content = String::new();
+        for i in 0..240 {
+            content.push_str(&format!(
+                r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}, "categoryName": "{}"}}"#,
+                (i * 7) % 11,
+                languages[i % languages.len()],
+                (i * 37) % 1500,
+                categories[(i / 2) % categories.len()]
+            ));
+            content.push('\n');
+        }
+        // Counted by the built-in analyses, but not by a query on categoryName
+        content.push_str(r#"{"channelName": "nocategory", "language": "en", "viewerCount": 5}"#);
+        content.push('\n');
+        content
+    }
+
+    fn parse_error(group_by: &str, top_by: &str) -> String {
+        match GroupByQuery::parse(group_by, top_by) {
+            Err(CustomError::ArgumentError(msg)) => msg,
+            other => panic!("expected an argument error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_query() {
+        let query = GroupByQuery::parse("language, categoryName", "channelName").unwrap();
+        assert_eq!(
+            query.group_by,
+            vec![QueryField::Language, QueryField::CategoryName]
+        );
+        assert_eq!(query.top_by, QueryField::ChannelName);
+        assert_eq!(query.limit, Some(10));
+        assert!(query.requires_full_record());
+        assert!(
+            !GroupByQuery::parse("viewerRange", "language")
+                .unwrap()
+                .requires_full_record()
+        );
+
+        assert!(parse_error("game", "channelName").contains("Unknown query field 'game'"));
+        assert!(parse_error("language,language", "channelName").contains("repeated"));
+        assert!(parse_error("language", "language").contains("both a group-by and the top-by"));
+        assert!(parse_error("", "language").contains("Unknown query field ''"));
+    }
+
+    #[test]
+    fn test_nested_groups_from_file_and_stream() {
+        let content = create_test_data();
+        let (_temp_file, file_path) = create_test_file(&content);
+        let query = GroupByQuery::parse("language,categoryName", "channelName")
+            .unwrap()
+            .with_limit(Some(2));
+
+        let expected = group_by_with_config(&file_path, &query, &AnalysisConfig::new(1)).unwrap();
+        assert_eq!(expected.group_by, vec!["language", "categoryName"]);
+        assert_eq!(expected.top_by, "channelName");
+        assert_eq!(expected.groups.len(), 2);
+        assert_eq!(
+            expected.groups.iter().map(|g| g.message_count).sum::<u64>(),
+            240
+        );
+        for language in &expected.groups {
+            assert_eq!(language.groups.len(), 3);
+            assert!(language.top.is_empty());
+            for category in &language.groups {
+                assert_eq!(category.top.len(), 2);
+                assert!(category.top[0].message_count >= category.top[1].message_count);
+            }
+        }
+        assert_eq!(expected.leaves().len(), 6);
+
+        for (threads, chunks) in [(2, 3), (4, 9)] {
+            let config = AnalysisConfig::new(threads).with_chunks(chunks);
+            assert_eq!(
+                group_by_with_config(&file_path, &query, &config).unwrap(),
+                expected
+            );
+
+            let mut aggregators = AggregatorSet::new();
+            let handle = aggregators.add(GroupByAggregator::new(
+                query.clone(),
+                ViewerBuckets::default(),
+            ));
+            let mut results =
+                run_aggregators_from_reader(content.as_bytes(), &aggregators, &config).unwrap();
+            assert_eq!(results.take(handle), Some(expected.clone()));
+        }
+    }
+
+    #[test]
+    fn test_equivalent_queries_match_built_in_analyses() {
+        let (_temp_file, file_path) = create_test_file(&create_test_data());
+        let config = AnalysisConfig::new(2)
+            .with_top_channels(Some(4))
+            .with_language_order(LanguageOrder::Alphabetical);
+        let report = run_analysis(&file_path, AnalysisType::Both, &config).unwrap();
+
+        let top_channels = group_by_with_config(
+            &file_path,
+            &GroupByQuery::top_channels_by_language(Some(4), LanguageOrder::Alphabetical),
+            &config,
+        )
+        .unwrap();
+        let top_channels_by_language = report.top_channels_by_language.unwrap();
+        assert_eq!(top_channels.groups.len(), top_channels_by_language.len());
+        for (group, result) in top_channels.groups.iter().zip(&top_channels_by_language) {
+            assert_eq!(group.value, result.language);
+            let channels: Vec<(&str, u64)> = group
+                .top
+                .iter()
+                .map(|c| (c.value.as_str(), c.message_count))
+                .collect();
+            let expected: Vec<(&str, u64)> = result
+                .top_channels
+                .iter()
+                .map(|c| (c.channel_name.as_str(), c.message_count))
+                .collect();
+            assert_eq!(channels, expected);
+        }
+
+        let top_languages = group_by_with_config(
+            &file_path,
+            &GroupByQuery::top_languages_by_viewer_range(Some(5)),
+            &config,
+        )
+        .unwrap();
+        let ranges: Vec<&str> = top_languages
+            .groups
+            .iter()
+            .map(|g| g.value.as_str())
+            .collect();
+        let expected: Vec<&str> = report
+            .top_languages_by_viewer_range
+            .as_ref()
+            .unwrap()
+            .iter()
+            .map(|r| r.viewer_range.as_str())
+            .collect();
+        assert_eq!(ranges, expected);
+    }
+
+    #[test]
+    fn test_group_order() {
+        let mut aggregator = GroupByAggregator::new(
+            GroupByQuery::parse("viewerCount", "channelName").unwrap(),
+            ViewerBuckets::default(),
+        );
+        for (channel, viewers) in [("a", 900), ("b", 80), ("c", 80), ("d", 1000), ("e", 1000)] {
+            aggregator.update(&message_ref(channel, "en", viewers));
+        }
+        aggregator.update(&message_ref("f", "en", 1000));
+
+        let values = |result: &GroupByResult| -> Vec<String> {
+            result.groups.iter().map(|g| g.value.clone()).collect()
+        };
+        assert_eq!(values(&aggregator.finalize()), ["1000", "80", "900"]);
+
+        let mut alphabetical = GroupByAggregator::new(
+            GroupByQuery::parse("viewerCount", "channelName")
+                .unwrap()
+                .with_group_order(GroupOrder::Alphabetical),
+            ViewerBuckets::default(),
+        );
+        let mut viewer_ranges = GroupByAggregator::new(
+            GroupByQuery::parse("viewerRange", "language").unwrap(),
+            ViewerBuckets::default(),
+        );
+        for (channel, viewers) in [("a", 900), ("b", 80), ("c", 1000), ("d", 5000), ("e", 5000)] {
+            alphabetical.update(&message_ref(channel, "en", viewers));
+            viewer_ranges.update(&message_ref(channel, "en", viewers));
+        }
+        assert_eq!(
+            values(&alphabetical.finalize()),
+            ["80", "900", "1000", "5000"]
+        );
+        assert_eq!(
+            values(&viewer_ranges.finalize()),
+            ["0-100", "501-1000", "1000+"]
+        );
+    }
+
+    #[test]
+    fn test_group_by_analysis_report() {
+        let (_temp_file, file_path) = create_test_file(&create_test_data());
+        let query = GroupByQuery::parse("categoryName", "language").unwrap();
+        let config = AnalysisConfig::new(2).with_query(Some(query));
+
+        let report = run_analysis(&file_path, AnalysisType::GroupBy, &config).unwrap();
+        assert!(report.top_channels_by_language.is_none());
+        assert!(report.top_languages_by_viewer_range.is_none());
+        assert_eq!(report.metadata.total_messages, 241);
+        let group_by = report.group_by.unwrap();
+        assert_eq!(group_by.groups.len(), 3);
+        assert_eq!(group_by.groups[0].top.len(), 2);
+
+        let result = run_analysis(&file_path, AnalysisType::GroupBy, &AnalysisConfig::new(2));
+        assert!(matches!(result, Err(CustomError::ArgumentError(_))));
+    }
+}
@@ -0,0 +1,205 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::counting_reader::CountingReader;
+use TP0ProgramacionConcurrente::parser::is_gzip_file;
+use TP0ProgramacionConcurrente::transformations::{
+    run_analysis, run_analysis_from_reader, run_analysis_on_inputs,
+};
+use flate2::Compression;
+use flate2::write::GzEncoder;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::Path;
+use std::sync::atomic::Ordering;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod gzip_tests {
+    use super::*;
+
+    fn dataset(num_messages: usize, ndjson: bool) -> String {
+        let records: Vec<String> = (0..num_messages)
+            .map(|i| {
+                format!(
+                    r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}}}"#,
+                    i % 11,
+                    ["en", "es", "ja", "ru"][i % 4],
+                    (i * 53) % 2000
+                )
+            })
+            .collect();
+        if ndjson {
+            records.join("\n") + "\n"
+        } else {
+            format!("[{}]", records.join(",\n"))
+        }
+    }
+
+    fn gzip(content: &[u8]) -> Vec<u8> {
+        let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+        encoder.write_all(content).unwrap();
+        encoder.finish().unwrap()
+    }
+
+    fn write(dir: &Path, name: &str, content: &[u8]) -> String {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_gzip_detection_by_magic_bytes() {
+        let temp_dir = TempDir::new().unwrap();
+        let compressed = write(temp_dir.path(), "data.bin", &gzip(b"[]"));
+        let plain = write(temp_dir.path(), "data.json.gz", b"[]");
+        let empty = write(temp_dir.path(), "empty.json", b"");
+
+        assert!(is_gzip_file(&compressed).unwrap());
+        assert!(!is_gzip_file(&plain).unwrap());
+        assert!(!is_gzip_file(&empty).unwrap());
+    }
+
+    #[test]
+    fn test_gzip_file_matches_uncompressed_file() {
+        let temp_dir = TempDir::new().unwrap();
+        for ndjson in [false, true] {
+            let content = dataset(400, ndjson);
+            let plain = write(temp_dir.path(), "plain.json", content.as_bytes());
+            let compressed = write(
+                temp_dir.path(),
+                "compressed.json.gz",
+                &gzip(content.as_bytes()),
+            );
+
+            let config = AnalysisConfig::new(3)
+                .with_top_channels(None)
+                .with_stream_batch_size(1024);
+            let expected = run_analysis(&plain, AnalysisType::Both, &config).unwrap();
+            let report = run_analysis(&compressed, AnalysisType::Both, &config).unwrap();
+
+            assert_eq!(report.metadata.total_messages, 400);
+            assert_eq!(
+                report.top_channels_by_language,
+                expected.top_channels_by_language
+            );
+            assert_eq!(
+                report.top_languages_by_viewer_range,
+                expected.top_languages_by_viewer_range
+            );
+        }
+    }
+
+    #[test]
+    fn test_mixed_compressed_and_plain_inputs() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = dataset(100, false);
+        let second = dataset(50, true);
+        let combined = write(
+            temp_dir.path(),
+            "combined.ndjson",
+            (dataset(100, true) + &second).as_bytes(),
+        );
+        let inputs = vec![
+            write(temp_dir.path(), "day1.json.gz", &gzip(first.as_bytes())),
+            write(temp_dir.path(), "day2.ndjson", second.as_bytes()),
+        ];
+
+        let config = AnalysisConfig::new(2).with_top_channels(None);
+        let expected = run_analysis(&combined, AnalysisType::Both, &config).unwrap();
+        let report = run_analysis_on_inputs(&inputs, AnalysisType::Both, &config).unwrap();
+
+        assert_eq!(report.metadata.total_messages, 150);
+        assert_eq!(
+            report.top_channels_by_language,
+            expected.top_channels_by_language
+        );
+    }
+
+    #[test]
+    fn test_several_gzip_files_in_one_job() {
+        let temp_dir = TempDir::new().unwrap();
+        let mut combined = String::new();
+        let mut inputs = Vec::new();
+        for day in 0..4 {
+            let content = dataset(150 + day * 10, true);
+            inputs.push(write(
+                temp_dir.path(),
+                &format!("day{day}.ndjson.gz"),
+                &gzip(content.as_bytes()),
+            ));
+            combined.push_str(&content);
+        }
+        let plain = dataset(80, true);
+        inputs.push(write(temp_dir.path(), "extra.ndjson", plain.as_bytes()));
+        combined.push_str(&plain);
+        let combined = write(temp_dir.path(), "combined.ndjson", combined.as_bytes());
+
+        for threads in [1, 4] {
+            let config = AnalysisConfig::new(threads)
+                .with_top_channels(None)
+                .with_stream_batch_size(512);
+            let expected = run_analysis(&combined, AnalysisType::Both, &config).unwrap();
+            for config in [config.clone(), config.with_adaptive_split(Some(256))] {
+                let report = run_analysis_on_inputs(&inputs, AnalysisType::Both, &config).unwrap();
+                assert_eq!(report.metadata.total_messages, 740);
+                assert_eq!(report.metadata.input_files.len(), 5);
+                assert_eq!(
+                    report.top_channels_by_language,
+                    expected.top_channels_by_language
+                );
+                assert_eq!(
+                    report.top_languages_by_viewer_range,
+                    expected.top_languages_by_viewer_range
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn test_gzip_stream_is_decompressed() {
+        let content = dataset(200, true);
+        let compressed = gzip(content.as_bytes());
+
+        let config = AnalysisConfig::new(2).with_stream_batch_size(512);
+        let report =
+            run_analysis_from_reader(compressed.as_slice(), AnalysisType::Both, &config).unwrap();
+        assert_eq!(report.metadata.total_messages, 200);
+        assert_eq!(report.metadata.skipped_records.total, 0);
+    }
+
+    #[test]
+    fn test_reject_offsets_refer_to_decompressed_data() {
+        let temp_dir = TempDir::new().unwrap();
+        let content = "{\"channelName\": \"a\", \"language\": \"en\", \"viewerCount\": 1}\n\
+                       {\"channelName\": \"b\", \"language\": \"en\"}\n";
+        let compressed = write(temp_dir.path(), "data.gz", &gzip(content.as_bytes()));
+        let reject_file = temp_dir.path().join("rejected.jsonl");
+
+        let config = AnalysisConfig::new(1)
+            .with_reject_file(Some(reject_file.to_string_lossy().to_string()));
+        let report = run_analysis(&compressed, AnalysisType::Both, &config).unwrap();
+        assert_eq!(report.metadata.skipped_records.missing_field, 1);
+
+        let line = fs::read_to_string(&reject_file).unwrap();
+        let record: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(record["line"], 2);
+        assert_eq!(
+            record["offset"],
+            content.find("{\"channelName\": \"b\"").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_counting_reader_counts_consumed_bytes() {
+        let data = gzip(dataset(50, false).as_bytes());
+        let mut reader = CountingReader::new(data.as_slice());
+        let bytes_read = reader.bytes_read();
+
+        let mut first = [0u8; 10];
+        reader.read_exact(&mut first).unwrap();
+        assert_eq!(bytes_read.load(Ordering::Relaxed), 10);
+
+        reader.read_to_end(&mut Vec::new()).unwrap();
+        assert_eq!(bytes_read.load(Ordering::Relaxed), data.len() as u64);
+    }
+}
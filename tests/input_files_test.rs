@@ -0,0 +1,196 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::input_files::resolve_input_files;
+use TP0ProgramacionConcurrente::input_format::InputFormat;
+use TP0ProgramacionConcurrente::parser::analize_files_for_chunks;
+use TP0ProgramacionConcurrente::transformations::{run_analysis, run_analysis_on_inputs};
+use std::fs;
+use std::path::Path;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod input_files_tests {
+    use super::*;
+
+    const DAY_1: &str = r#"[{"channelName": "gaming1", "language": "en", "viewerCount": 150},
+{"channelName": "music1", "language": "es", "viewerCount": 80}]"#;
+    const DAY_2: &str = r#"{"channelName": "gaming1", "language": "en", "viewerCount": 2000}
+{"channelName": "gaming2", "language": "en", "viewerCount": 50}
+"#;
+    const DAY_3: &str = r#"[{"channelName": "music1", "language": "es", "viewerCount": 90},
+{"channelName": "gaming1", "language": "en", "viewerCount": 300}]"#;
+
+    fn write(dir: &Path, name: &str, content: &str) -> String {
+        let path = dir.join(name);
+        fs::write(&path, content).unwrap();
+        path.to_string_lossy().to_string()
+    }
+
+    fn path_of(dir: &Path, name: &str) -> String {
+        dir.join(name).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_directory_expands_to_sorted_visible_files() {
+        let temp_dir = TempDir::new().unwrap();
+        write(temp_dir.path(), "2024-01-02.json", DAY_2);
+        write(temp_dir.path(), "2024-01-01.json", DAY_1);
+        write(temp_dir.path(), ".hidden.json", DAY_3);
+        fs::create_dir(temp_dir.path().join("nested")).unwrap();
+
+        let files = resolve_input_files(&[temp_dir.path().to_string_lossy().to_string()]).unwrap();
+
+        assert_eq!(
+            files,
+            vec![
+                path_of(temp_dir.path(), "2024-01-01.json"),
+                path_of(temp_dir.path(), "2024-01-02.json"),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_glob_pattern_and_duplicates() {
+        let temp_dir = TempDir::new().unwrap();
+        let day_1 = write(temp_dir.path(), "day1.json", DAY_1);
+        let day_2 = write(temp_dir.path(), "day2.json", DAY_2);
+        write(temp_dir.path(), "notes.txt", "not a dataset");
+
+        let pattern = path_of(temp_dir.path(), "day*.json");
+        let files = resolve_input_files(&[day_2.clone(), pattern]).unwrap();
+
+        assert_eq!(files, vec![day_2, day_1]);
+    }
+
+    #[test]
+    fn test_inputs_without_files_are_rejected() {
+        let temp_dir = TempDir::new().unwrap();
+        let pattern = path_of(temp_dir.path(), "*.json");
+
+        assert!(matches!(
+            resolve_input_files(&[pattern]),
+            Err(CustomError::ArgumentError(_))
+        ));
+        assert!(matches!(
+            resolve_input_files(&[temp_dir.path().to_string_lossy().to_string()]),
+            Err(CustomError::ArgumentError(_))
+        ));
+        assert!(matches!(
+            resolve_input_files(&[]),
+            Err(CustomError::ArgumentError(_))
+        ));
+    }
+
+    #[test]
+    fn test_chunks_carry_their_file_id() {
+        let temp_dir = TempDir::new().unwrap();
+        let files = vec![
+            write(temp_dir.path(), "day1.json", DAY_1),
+            write(temp_dir.path(), "day2.json", DAY_2),
+        ];
+
+        let chunks =
+            analize_files_for_chunks(&files, &[InputFormat::JsonArray, InputFormat::Ndjson], 4)
+                .unwrap();
+
+        for (file_id, file) in files.iter().enumerate() {
+            let file_chunks: Vec<_> = chunks.iter().filter(|c| c.file_id == file_id).collect();
+            assert!(!file_chunks.is_empty());
+            assert_eq!(file_chunks[0].start, 0);
+            assert_eq!(
+                file_chunks.last().unwrap().end,
+                fs::metadata(file).unwrap().len()
+            );
+        }
+    }
+
+    #[test]
+    fn test_input_paths_with_spaces_are_kept_whole() {
+        let temp_dir = TempDir::new().unwrap();
+        let first = write(temp_dir.path(), "day one.json", DAY_1);
+        let second = write(temp_dir.path(), "day two.json", DAY_2);
+
+        let report = run_analysis_on_inputs(
+            &[first.clone(), second.clone()],
+            AnalysisType::Both,
+            &AnalysisConfig::new(2),
+        )
+        .unwrap();
+
+        assert_eq!(report.metadata.input_file, first);
+        assert_eq!(report.metadata.input_files, vec![first, second]);
+    }
+
+    #[test]
+    fn test_several_files_aggregate_as_one_dataset() {
+        let temp_dir = TempDir::new().unwrap();
+        let day_dir = temp_dir.path().join("days");
+        fs::create_dir(&day_dir).unwrap();
+        write(&day_dir, "day1.json", DAY_1);
+        write(&day_dir, "day2.json", DAY_2);
+        let day_3 = write(temp_dir.path(), "day3.json", DAY_3);
+        let combined = write(
+            temp_dir.path(),
+            "combined.json",
+            &[DAY_1, DAY_2, DAY_3]
+                .iter()
+                .flat_map(|day| day.lines())
+                .map(|line| line.trim_matches(['[', ']', ',']))
+                .collect::<Vec<_>>()
+                .join("\n"),
+        );
+        for threads in [1, 3] {
+            let config = AnalysisConfig::new(threads).with_top_channels(None);
+            let expected = run_analysis(&combined, AnalysisType::Both, &config).unwrap();
+            let report = run_analysis_on_inputs(
+                &[day_dir.to_string_lossy().to_string(), day_3.clone()],
+                AnalysisType::Both,
+                &config,
+            )
+            .unwrap();
+
+            assert_eq!(expected.metadata.total_messages, 6);
+            assert_eq!(report.metadata.total_messages, 6);
+            assert_eq!(report.metadata.input_files.len(), 3);
+            assert_eq!(
+                report.top_channels_by_language,
+                expected.top_channels_by_language
+            );
+            assert_eq!(
+                report.top_languages_by_viewer_range,
+                expected.top_languages_by_viewer_range
+            );
+        }
+    }
+
+    #[test]
+    fn test_rejected_records_name_their_file() {
+        let temp_dir = TempDir::new().unwrap();
+        let day_1 = write(temp_dir.path(), "day1.json", DAY_1);
+        let broken = write(
+            temp_dir.path(),
+            "day2.json",
+            "{\"channelName\": \"gaming1\", \"language\": \"en\"}\n",
+        );
+        let reject_file = path_of(temp_dir.path(), "rejected.jsonl");
+
+        let config = AnalysisConfig::new(2).with_reject_file(Some(reject_file.clone()));
+        let report =
+            run_analysis_on_inputs(&[day_1, broken.clone()], AnalysisType::Both, &config).unwrap();
+        assert_eq!(report.metadata.skipped_records.total, 1);
+
+        let line = fs::read_to_string(&reject_file).unwrap();
+        let record: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(record["file"], broken.as_str());
+
+        let strict = AnalysisConfig::new(2).with_strict(true);
+        let error = run_analysis_on_inputs(
+            &[path_of(temp_dir.path(), "day*.json")],
+            AnalysisType::Both,
+            &strict,
+        )
+        .unwrap_err();
+        assert!(error.to_string().contains(&broken));
+    }
+}
@@ -0,0 +1,53 @@
+use TP0ProgramacionConcurrente::channel_id::ChannelId;
+use TP0ProgramacionConcurrente::interner::Interner;
+use TP0ProgramacionConcurrente::language_id::LanguageId;
+use TP0ProgramacionConcurrente::streaming_aggregators::StreamingAggregators;
+use common::message;
+
+mod common;
+
+#[cfg(test)]
+mod interner_tests {
+    use super::*;
+
+    #[test]
+    fn test_intern_returns_stable_ids() {
+        let mut channels: Interner<ChannelId> = Interner::new();
+        assert!(channels.is_empty());
+
+        let gaming = channels.intern("gaming");
+        let music = channels.intern("music");
+        assert_eq!(gaming, ChannelId(0));
+        assert_eq!(music, ChannelId(1));
+        assert_eq!(channels.intern("gaming"), gaming);
+        assert_eq!(channels.len(), 2);
+
+        assert_eq!(channels.resolve(music), "music");
+        assert_eq!(channels.get("gaming"), Some(gaming));
+        assert_eq!(channels.get("news"), None);
+
+        let names: Vec<(ChannelId, &str)> = channels.iter().collect();
+        assert_eq!(names, vec![(gaming, "gaming"), (music, "music")]);
+    }
+
+    #[test]
+    fn test_merge_remaps_ids_between_aggregators() {
+        let mut left = StreamingAggregators::new();
+        let mut right = StreamingAggregators::new();
+        left.process_message(message("gaming", "en", 50));
+        // Interned in the opposite order, so the IDs of both sides disagree.
+        right.process_message(message("música", "es", 50));
+        right.process_message(message("gaming", "en", 50));
+        assert_eq!(right.languages.get("es"), Some(LanguageId(0)));
+
+        left.merge(right);
+
+        assert_eq!(left.total_messages, 3);
+        assert_eq!(left.channel_count("en", "gaming"), 2);
+        assert_eq!(left.channel_count("es", "música"), 1);
+        assert_eq!(left.channel_count("es", "gaming"), 0);
+        assert_eq!(left.range_language_count(0, "en"), 2);
+        assert_eq!(left.languages.get("es"), Some(LanguageId(1)));
+        assert_eq!(left.channels.len(), 2);
+    }
+}
@@ -1,8 +1,12 @@
 use TP0ProgramacionConcurrente::channel_message_count::ChannelMessageCount;
 use TP0ProgramacionConcurrente::chat_message::ChatMessage;
+use TP0ProgramacionConcurrente::chat_message_ref::ChatMessageRef;
+use TP0ProgramacionConcurrente::chat_record::ChatRecord;
 use TP0ProgramacionConcurrente::language_message_count::LanguageMessageCount;
+use TP0ProgramacionConcurrente::timestamp::Timestamp;
 use TP0ProgramacionConcurrente::top_channels_result::TopChannelsByLanguageResult;
 use TP0ProgramacionConcurrente::top_languages_result::TopLanguagesByViewerRangeResult;
+use std::borrow::Cow;
 
 #[cfg(test)]
 mod tests {
@@ -20,6 +24,23 @@ mod tests {
         assert_eq!(message.viewer_count, 100);
     }
 
+    #[test]
+    fn test_chat_message_ref_borrows_from_input() {
+        let input = r#"{"channelName": "ゲーム実況", "language": "ja", "viewerCount": 7}"#;
+        let message: ChatMessageRef = serde_json::from_str(input).unwrap();
+        assert!(matches!(message.channel_name, Cow::Borrowed("ゲーム実況")));
+        assert!(matches!(message.language, Cow::Borrowed("ja")));
+
+        let escaped = r#"{"channelName": "say \"hi\"", "language": "en", "viewerCount": 7}"#;
+        let message: ChatMessageRef = serde_json::from_str(escaped).unwrap();
+        assert!(matches!(message.channel_name, Cow::Owned(_)));
+
+        let owned = message.into_owned();
+        assert_eq!(owned.channel_name, "say \"hi\"");
+        assert_eq!(owned.language, "en");
+        assert_eq!(owned.viewer_count, 7);
+    }
+
     #[test]
     fn test_top_channels_by_language_result_creation() {
         let top_channels = vec![
@@ -59,12 +80,171 @@ mod tests {
 
         let result = TopLanguagesByViewerRangeResult {
             viewer_range: "101-500".to_string(),
+            lower_bound: 101,
+            upper_bound: Some(500),
             top_languages,
         };
 
         assert_eq!(result.viewer_range, "101-500");
+        assert_eq!(result.lower_bound, 101);
+        assert_eq!(result.upper_bound, Some(500));
         assert_eq!(result.top_languages.len(), 2);
         assert_eq!(result.top_languages[0].language, "en");
         assert_eq!(result.top_languages[0].message_count, 15);
     }
+
+    #[test]
+    fn test_viewer_count_beyond_i32_is_parsed() {
+        let message: ChatMessage = serde_json::from_str(
+            r#"{"channelName": "big", "language": "en", "viewerCount": 5000000000}"#,
+        )
+        .unwrap();
+        assert_eq!(message.viewer_count, 5_000_000_000);
+    }
+
+    #[test]
+    fn test_negative_viewer_count_is_rejected() {
+        let result: Result<ChatMessageRef, _> =
+            serde_json::from_str(r#"{"channelName": "odd", "language": "en", "viewerCount": -42}"#);
+        assert!(result.is_err());
+
+        let record: Result<ChatRecord, _> =
+            serde_json::from_str(r#"{"channelName": "odd", "language": "en", "viewerCount": -42}"#);
+        assert!(record.is_err());
+    }
+
+    #[test]
+    fn test_non_integer_viewer_count_is_rejected() {
+        let result: Result<ChatMessage, _> =
+            serde_json::from_str(r#"{"channelName": "odd", "language": "en", "viewerCount": 1.5}"#);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_large_message_counts_serialize_as_plain_numbers() {
+        let count = ChannelMessageCount {
+            channel_name: "gaming".to_string(),
+            message_count: 5_000_000_000,
+        };
+        assert_eq!(
+            serde_json::to_string(&count).unwrap(),
+            r#"{"channel_name":"gaming","message_count":5000000000}"#
+        );
+    }
+
+    #[test]
+    fn test_chat_record_reads_every_field() {
+        let record: ChatRecord = serde_json::from_str(
+            r#"{
+                "_id": "3c27495c-ccec-499d-930c-64638b96b903",
+                "category": "509658",
+                "channel": "465131731",
+                "author": "707707619",
+                "text": "НУ НУ",
+                "channelName": "shadowkekw",
+                "authorName": "ddzp__",
+                "hasEmotes": false,
+                "isFirstMessage": false,
+                "isMod": false,
+                "isSubscriber": true,
+                "hasMalware": false,
+                "hasUrl": false,
+                "hasBadWords": false,
+                "hasBadDomain": false,
+                "spamingText": false,
+                "categoryName": "Just Chatting",
+                "isMature": false,
+                "language": "ru",
+                "streamStartedAt": "2021-11-05T14:03:26Z",
+                "streamTitle": "Показиваю квартиру гайки и делаю чупики",
+                "streamId": "43719739356",
+                "viewerCount": 7414,
+                "createAt": {"$date": "2021-11-05T14:59:41.071Z"}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            record.id.as_deref(),
+            Some("3c27495c-ccec-499d-930c-64638b96b903")
+        );
+        assert_eq!(record.author_name.as_deref(), Some("ddzp__"));
+        assert_eq!(record.text.as_deref(), Some("НУ НУ"));
+        assert_eq!(record.category_name.as_deref(), Some("Just Chatting"));
+        assert_eq!(record.stream_id.as_deref(), Some("43719739356"));
+        assert_eq!(record.is_subscriber, Some(true));
+        assert_eq!(record.spaming_text, Some(false));
+        assert_eq!(record.viewer_count, Some(7414));
+        assert_eq!(
+            record.stream_started_at.unwrap().to_string(),
+            "2021-11-05T14:03:26.000Z"
+        );
+        assert_eq!(record.created_at.unwrap().millis(), 1_636_124_381_071);
+
+        let message = record.to_message().unwrap();
+        assert_eq!(message.channel_name, "shadowkekw");
+        assert_eq!(message.language, "ru");
+        assert_eq!(message.viewer_count, 7414);
+    }
+
+    #[test]
+    fn test_chat_record_fields_are_optional() {
+        let record: ChatRecord =
+            serde_json::from_str(r#"{"channelName": "c1", "viewerCount": null, "isMod": null}"#)
+                .unwrap();
+
+        assert_eq!(record.channel_name.as_deref(), Some("c1"));
+        assert_eq!(record.viewer_count, None);
+        assert_eq!(record.is_mod, None);
+        assert_eq!(record.created_at, None);
+        assert!(record.as_message_ref().is_none());
+        assert_eq!(
+            serde_json::from_str::<ChatRecord>("{}").unwrap(),
+            ChatRecord::default()
+        );
+    }
+
+    #[test]
+    fn test_timestamp_layouts() {
+        let expected = Timestamp::from_millis(1_636_124_381_071).unwrap();
+        for layout in [
+            r#""2021-11-05T14:59:41.071Z""#,
+            r#""2021-11-05T11:59:41.071-03:00""#,
+            r#"1636124381071"#,
+            r#"{"$date": "2021-11-05T14:59:41.071Z"}"#,
+            r#"{"$date": 1636124381071}"#,
+            r#"{"$date": {"$numberLong": "1636124381071"}}"#,
+        ] {
+            let timestamp: Timestamp = serde_json::from_str(layout).unwrap();
+            assert_eq!(timestamp, expected, "{layout}");
+        }
+
+        assert_eq!(
+            serde_json::to_string(&expected).unwrap(),
+            r#""2021-11-05T14:59:41.071Z""#
+        );
+        assert_eq!(
+            "2021-11-05T14:59:41.071Z".parse::<Timestamp>().unwrap(),
+            expected
+        );
+    }
+
+    #[test]
+    fn test_invalid_timestamps_are_rejected() {
+        for layout in [
+            r#""yesterday""#,
+            r#"true"#,
+            r#"{"date": "2021-11-05T14:59:41.071Z"}"#,
+            r#"{"$date": {"$numberLong": "soon"}}"#,
+        ] {
+            assert!(
+                serde_json::from_str::<Timestamp>(layout).is_err(),
+                "{layout}"
+            );
+        }
+        // A full record treats an invalid optional timestamp as missing
+        let record: ChatRecord =
+            serde_json::from_str(r#"{"createAt": {"$date": "not a date"}}"#).unwrap();
+        assert_eq!(record.created_at, None);
+    }
 }
@@ -1,5 +1,13 @@
+use TP0ProgramacionConcurrente::chat_message::ChatMessage;
 use TP0ProgramacionConcurrente::chunk_info::ChunkInfo;
-use TP0ProgramacionConcurrente::parser::{analize_file_for_chunks, parse_chunk_streaming};
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::input_format::InputFormat;
+use TP0ProgramacionConcurrente::parse_options::ParseOptions;
+use TP0ProgramacionConcurrente::parser::{
+    analize_file_for_chunks, analize_file_for_chunks_with_format, detect_input_format, map_file,
+    parse_chunk_from_slice, parse_chunk_streaming, parse_chunk_with_report, split_chunk,
+};
+use TP0ProgramacionConcurrente::skip_reason::SkipReason;
 use std::fs;
 use std::io::Write;
 use tempfile::{NamedTempFile, TempDir};
@@ -95,6 +103,7 @@ mod streaming_parser_tests {
         write!(temp_file, "{}", test_content).unwrap();
 
         let chunk = ChunkInfo {
+            file_id: 0,
             start: 0,
             end: test_content.len() as u64,
             size: test_content.len() as u64,
@@ -126,6 +135,7 @@ mod streaming_parser_tests {
         write!(temp_file, "{}", test_content).unwrap();
 
         let chunk = ChunkInfo {
+            file_id: 0,
             start: 0,
             end: test_content.len() as u64,
             size: test_content.len() as u64,
@@ -154,6 +164,7 @@ mod streaming_parser_tests {
         write!(temp_file, "{}", test_content).unwrap();
 
         let chunk = ChunkInfo {
+            file_id: 0,
             start: 0,
             end: test_content.len() as u64,
             size: test_content.len() as u64,
@@ -190,6 +201,7 @@ mod streaming_parser_tests {
         write!(temp_file, "{}", content).unwrap();
 
         let chunk = ChunkInfo {
+            file_id: 0,
             start: 0,
             end: content.len() as u64,
             size: content.len() as u64,
@@ -208,3 +220,648 @@ mod streaming_parser_tests {
         assert_eq!(processed_count, 100);
     }
 }
+
+#[cfg(test)]
+mod malformed_records_tests {
+    use super::*;
+
+    fn whole_file_chunk(content: &str) -> ChunkInfo {
+        ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: content.len() as u64,
+            size: content.len() as u64,
+        }
+    }
+
+    #[test]
+    fn test_skipped_records_reasons_and_offsets() {
+        let lines = [
+            r#"{"channelName": "channel1", "language": "en", "viewerCount": 100}"#,
+            r#"{"channelName": "channel2", "viewerCount": 100}"#,
+            r#"{"channelName": "channel3", "language": "en", "viewerCount": "many"}"#,
+            r#"{"channelName": "channel4", "language": en, "viewerCount": 1}"#,
+            r#"{"channelName": "channel5", "language": "es", "viewerCount": 200}"#,
+        ];
+        let content = lines.join("\n");
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", content).unwrap();
+
+        let mut messages = Vec::new();
+        let report = parse_chunk_with_report(
+            temp_file.path().to_str().unwrap(),
+            &whole_file_chunk(&content),
+            ParseOptions::default(),
+            |message| messages.push(message),
+        )
+        .unwrap();
+
+        assert_eq!(report.processed_count, 2);
+        assert_eq!(messages.len(), 2);
+
+        let reasons: Vec<SkipReason> = report.skipped_records.iter().map(|r| r.reason).collect();
+        assert_eq!(
+            reasons,
+            vec![
+                SkipReason::MissingField,
+                SkipReason::WrongType,
+                SkipReason::InvalidJson
+            ]
+        );
+
+        let offsets: Vec<u64> = report.skipped_records.iter().map(|r| r.offset).collect();
+        let expected_offset =
+            |line: usize| -> u64 { lines[..line].iter().map(|l| l.len() as u64 + 1).sum() };
+        assert_eq!(
+            offsets,
+            vec![expected_offset(1), expected_offset(2), expected_offset(3)]
+        );
+        assert!(report.skipped_records[0].error.contains("language"));
+        assert!(report.skipped_records.iter().all(|r| r.raw.is_none()));
+    }
+
+    #[test]
+    fn test_skipped_records_keep_raw_text() {
+        let content = r#"{"channelName": "channel1", "language": "en", "viewerCount": 100}
+{"invalid": "json", "missing": "fields"}"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", content).unwrap();
+
+        let report = parse_chunk_with_report(
+            temp_file.path().to_str().unwrap(),
+            &whole_file_chunk(content),
+            ParseOptions {
+                keep_rejected: true,
+                ..ParseOptions::default()
+            },
+            |_message| {},
+        )
+        .unwrap();
+
+        assert_eq!(report.skipped_records.len(), 1);
+        assert_eq!(
+            report.skipped_records[0].raw.as_deref(),
+            Some(r#"{"invalid": "json", "missing": "fields"}"#)
+        );
+    }
+
+    #[test]
+    fn test_truncated_record_is_reported() {
+        let content = r#"{"channelName": "channel1", "language": "en", "viewerCount": 100}
+{"channelName": "channel2", "langu"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", content).unwrap();
+
+        let report = parse_chunk_with_report(
+            temp_file.path().to_str().unwrap(),
+            &whole_file_chunk(content),
+            ParseOptions::default(),
+            |_message| {},
+        )
+        .unwrap();
+
+        assert_eq!(report.processed_count, 1);
+        assert_eq!(report.skipped_records.len(), 1);
+        assert_eq!(report.skipped_records[0].reason, SkipReason::InvalidJson);
+        assert_eq!(
+            report.skipped_records[0].offset,
+            content.find(r#"{"channelName": "channel2""#).unwrap() as u64
+        );
+    }
+
+    #[test]
+    fn test_strict_mode_fails_on_first_bad_record() {
+        let content = r#"{"channelName": "channel1", "language": "en", "viewerCount": 100}
+{"channelName": "channel2", "viewerCount": 200}
+{"channelName": "channel3", "language": "es", "viewerCount": true}"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", content).unwrap();
+
+        let result = parse_chunk_with_report(
+            temp_file.path().to_str().unwrap(),
+            &whole_file_chunk(content),
+            ParseOptions {
+                strict: true,
+                ..ParseOptions::default()
+            },
+            |_message| {},
+        );
+
+        let offset = content.find(r#"{"channelName": "channel2""#).unwrap();
+        match result {
+            Err(CustomError::ParseError(msg)) => {
+                assert!(msg.contains(&format!("byte offset {offset}")));
+                assert!(msg.contains("missing field"));
+                assert!(msg.contains(r#"{"channelName": "channel2""#));
+            }
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_strict_mode_accepts_valid_file() {
+        let content = r#"{"channelName": "channel1", "language": "en", "viewerCount": 100}
+{"channelName": "channel2", "language": "es", "viewerCount": 200}"#;
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", content).unwrap();
+
+        let report = parse_chunk_with_report(
+            temp_file.path().to_str().unwrap(),
+            &whole_file_chunk(content),
+            ParseOptions {
+                strict: true,
+                ..ParseOptions::default()
+            },
+            |_message| {},
+        )
+        .unwrap();
+
+        assert_eq!(report.processed_count, 2);
+        assert!(report.skipped_records.is_empty());
+    }
+}
+
+#[cfg(test)]
+mod utf8_tests {
+    use super::*;
+
+    fn parse_all(content: &str) -> Vec<ChatMessage> {
+        let mut temp_file = NamedTempFile::new().unwrap();
+        write!(temp_file, "{}", content).unwrap();
+        let chunk = ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: content.len() as u64,
+            size: content.len() as u64,
+        };
+
+        let mut messages = Vec::new();
+        parse_chunk_streaming(temp_file.path().to_str().unwrap(), &chunk, |message| {
+            messages.push(message);
+        })
+        .unwrap();
+        messages
+    }
+
+    #[test]
+    fn test_russian_channel_name() {
+        let content = r#"{"channelName": "Стрим_Игры", "language": "ru", "viewerCount": 120}"#;
+        let messages = parse_all(content);
+
+        assert_eq!(messages.len(), 1);
+        assert_eq!(messages[0].channel_name, "Стрим_Игры");
+        assert_eq!(messages[0].language, "ru");
+    }
+
+    #[test]
+    fn test_japanese_channel_name_and_language() {
+        let content = r#"[{"channelName": "ゲーム実況", "language": "日本語", "viewerCount": 300},
+{"channelName": "音楽チャンネル", "language": "ja", "viewerCount": 45}]"#;
+        let messages = parse_all(content);
+
+        assert_eq!(messages.len(), 2);
+        assert_eq!(messages[0].channel_name, "ゲーム実況");
+        assert_eq!(messages[0].language, "日本語");
+        assert_eq!(messages[1].channel_name, "音楽チャンネル");
+    }
+}
+
+#[cfg(test)]
+mod chunk_boundary_tests {
+    use super::*;
+
+    fn parse_with_threads(path: &str, num_threads: usize) -> Vec<ChatMessage> {
+        let chunks = analize_file_for_chunks(path, num_threads).unwrap();
+        assert_eq!(chunks.len(), num_threads);
+        assert_eq!(chunks[0].start, 0);
+        for i in 1..chunks.len() {
+            assert_eq!(chunks[i - 1].end, chunks[i].start);
+        }
+        for chunk in &chunks {
+            assert_eq!(chunk.size, chunk.end - chunk.start);
+        }
+        assert_eq!(
+            chunks.last().unwrap().end,
+            fs::metadata(path).unwrap().len()
+        );
+
+        let mut messages = Vec::new();
+        for chunk in &chunks {
+            parse_chunk_streaming(path, chunk, |message| messages.push(message)).unwrap();
+        }
+        messages
+    }
+
+    fn write_records(records: &[String], separator: &str) -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("adversarial.json");
+        fs::write(&test_file, format!("[{}]", records.join(separator))).unwrap();
+        (temp_dir, test_file.to_string_lossy().to_string())
+    }
+
+    #[test]
+    fn test_braces_inside_strings_do_not_split_records() {
+        let texts = [
+            r#"},{"#,
+            r#"}]"#,
+            r#"},{\"channelName\": \"fake\", \"language\": \"xx\", \"viewerCount\": 1},"#,
+            r#"\"}, {\"channelName\": \"evil\"}"#,
+            r#"{}, {}, [{}]"#,
+            r#"\\\\\"}, {"#,
+        ];
+        let records: Vec<String> = (0..60)
+            .map(|i| {
+                format!(
+                    r#"{{"channelName": "channel{i}", "text": "{}", "language": "en", "viewerCount": {i}}}"#,
+                    texts[i % texts.len()]
+                )
+            })
+            .collect();
+        let (_temp_dir, test_file) = write_records(&records, ",\n");
+
+        for num_threads in 1..=12 {
+            let messages = parse_with_threads(&test_file, num_threads);
+            let names: Vec<String> = messages.into_iter().map(|m| m.channel_name).collect();
+            let expected: Vec<String> = (0..60).map(|i| format!("channel{i}")).collect();
+            assert_eq!(names, expected, "threads: {num_threads}");
+        }
+    }
+
+    #[test]
+    fn test_newline_delimited_records_are_split() {
+        let records: Vec<String> = (0..40)
+            .map(|i| format!(r#"{{"channelName": "c{i}", "language": "en", "viewerCount": {i}}}"#))
+            .collect();
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("lines.json");
+        fs::write(&test_file, records.join("\n")).unwrap();
+        let path = test_file.to_string_lossy().to_string();
+
+        let chunks = analize_file_for_chunks(&path, 4).unwrap();
+        assert!(chunks.iter().all(|chunk| chunk.size > 0));
+        assert_eq!(parse_with_threads(&path, 4).len(), 40);
+    }
+
+    #[test]
+    fn test_record_larger_than_search_window_is_not_duplicated() {
+        let long_text = "},{".repeat(350_000);
+        let records = vec![
+            r#"{"channelName": "before", "language": "en", "viewerCount": 1}"#.to_string(),
+            format!(
+                r#"{{"channelName": "huge", "text": "{long_text}", "language": "en", "viewerCount": 2}}"#
+            ),
+            r#"{"channelName": "after", "language": "en", "viewerCount": 3}"#.to_string(),
+        ];
+        let (_temp_dir, test_file) = write_records(&records, ",");
+
+        for num_threads in [2, 5] {
+            let names: Vec<String> = parse_with_threads(&test_file, num_threads)
+                .into_iter()
+                .map(|m| m.channel_name)
+                .collect();
+            assert_eq!(names, ["before", "huge", "after"]);
+        }
+    }
+
+    #[test]
+    fn test_boundaries_across_read_blocks() {
+        // Records of a few KiB spanning several blocks of the boundary search, with
+        // fake boundaries inside their texts
+        let records: Vec<String> = (0..120)
+            .map(|i| {
+                format!(
+                    r#"{{"channelName": "channel{i}", "text": "{}", "language": "en", "viewerCount": {i}}}"#,
+                    r#"},{\"channelName\": \"fake\"} "#.repeat(40 + i % 90)
+                )
+            })
+            .collect();
+        let (_temp_dir, test_file) = write_records(&records, ",\n");
+        assert!(fs::metadata(&test_file).unwrap().len() > 3 * 64 * 1024);
+
+        let expected: Vec<String> = (0..120).map(|i| format!("channel{i}")).collect();
+        for num_threads in [3, 7, 16] {
+            let names: Vec<String> = parse_with_threads(&test_file, num_threads)
+                .into_iter()
+                .map(|m| m.channel_name)
+                .collect();
+            assert_eq!(names, expected, "threads: {num_threads}");
+        }
+    }
+
+    #[test]
+    fn test_more_chunks_than_records() {
+        let records =
+            vec![r#"{"channelName": "only", "language": "en", "viewerCount": 1}"#.to_string()];
+        let (_temp_dir, test_file) = write_records(&records, ",");
+
+        let messages = parse_with_threads(&test_file, 16);
+        assert_eq!(messages.len(), 1);
+    }
+}
+
+#[cfg(test)]
+mod ndjson_tests {
+    use super::*;
+
+    fn write_file(content: &str) -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("input.json");
+        fs::write(&test_file, content).unwrap();
+        (temp_dir, test_file.to_string_lossy().to_string())
+    }
+
+    fn ndjson_options() -> ParseOptions {
+        ParseOptions {
+            input_format: InputFormat::Ndjson,
+            ..ParseOptions::default()
+        }
+    }
+
+    #[test]
+    fn test_detect_input_format() {
+        let (_dir, array) = write_file(
+            r#"[{"channelName": "c1", "language": "en", "viewerCount": 1},
+{"channelName": "c2", "language": "en", "viewerCount": 2}]"#,
+        );
+        assert_eq!(detect_input_format(&array).unwrap(), InputFormat::JsonArray);
+
+        let (_dir, ndjson) = write_file(
+            "\u{feff}{\"channelName\": \"c1\", \"language\": \"en\", \"viewerCount\": 1}\n\
+             {\"channelName\": \"c2\", \"language\": \"en\", \"viewerCount\": 2}\n",
+        );
+        assert_eq!(detect_input_format(&ndjson).unwrap(), InputFormat::Ndjson);
+
+        let (_dir, pretty) = write_file(
+            "{\n  \"channelName\": \"c1\",\n  \"language\": \"en\",\n  \"viewerCount\": 1\n}\n",
+        );
+        assert_eq!(
+            detect_input_format(&pretty).unwrap(),
+            InputFormat::JsonArray
+        );
+    }
+
+    #[test]
+    fn test_ndjson_chunks_end_on_newlines() {
+        let content: String = (0..50)
+            .map(|i| {
+                format!(
+                    "{{\"channelName\": \"c{i}\", \"language\": \"en\", \"viewerCount\": {i}}}\n"
+                )
+            })
+            .collect();
+        let (_dir, path) = write_file(&content);
+
+        for num_chunks in 1..=8 {
+            let chunks =
+                analize_file_for_chunks_with_format(&path, num_chunks, InputFormat::Ndjson)
+                    .unwrap();
+            assert_eq!(chunks.len(), num_chunks);
+            for i in 1..chunks.len() {
+                assert_eq!(chunks[i - 1].end, chunks[i].start);
+            }
+            for chunk in &chunks[..num_chunks - 1] {
+                if chunk.end > 0 && chunk.end < content.len() as u64 {
+                    assert_eq!(content.as_bytes()[chunk.end as usize - 1], b'\n');
+                }
+            }
+
+            let mut total = 0;
+            for chunk in &chunks {
+                let report =
+                    parse_chunk_with_report(&path, chunk, ndjson_options(), |_message| {}).unwrap();
+                total += report.processed_count;
+            }
+            assert_eq!(total, 50, "chunks: {num_chunks}");
+        }
+    }
+
+    #[test]
+    fn test_split_chunk_keeps_records_whole() {
+        let records: Vec<String> = (0..40)
+            .map(|i| {
+                format!(
+                    r#"{{"channelName": "c{i}", "text": "}},{{", "language": "en", "viewerCount": {i}}}"#
+                )
+            })
+            .collect();
+        let array = format!("[{}]", records.join(",\n"));
+        let ndjson = records.join("\n");
+
+        for (content, input_format) in [
+            (array, InputFormat::JsonArray),
+            (ndjson, InputFormat::Ndjson),
+        ] {
+            let (_dir, path) = write_file(&content);
+            let options = ParseOptions {
+                input_format,
+                ..ParseOptions::default()
+            };
+
+            // Split every chunk until no boundary is left inside it.
+            let mut pending = analize_file_for_chunks_with_format(&path, 1, input_format).unwrap();
+            let mut chunks = Vec::new();
+            while let Some(chunk) = pending.pop() {
+                match split_chunk(&path, &chunk, input_format).unwrap() {
+                    Some((first, second)) => {
+                        assert_eq!(first.start, chunk.start);
+                        assert_eq!(first.end, second.start);
+                        assert_eq!(second.end, chunk.end);
+                        assert!(first.size > 0 && second.size > 0);
+                        pending.push(first);
+                        pending.push(second);
+                    }
+                    None => chunks.push(chunk),
+                }
+            }
+            assert!(chunks.len() > 20, "{input_format}: {} chunks", chunks.len());
+
+            let mut names = Vec::new();
+            for chunk in &chunks {
+                let report = parse_chunk_with_report(&path, chunk, options, |message| {
+                    names.push(message.channel_name)
+                })
+                .unwrap();
+                assert!(report.skipped_records.is_empty());
+            }
+            names.sort();
+            let mut expected: Vec<String> = (0..40).map(|i| format!("c{i}")).collect();
+            expected.sort();
+            assert_eq!(names, expected, "{input_format}");
+        }
+    }
+
+    #[test]
+    fn test_split_chunk_single_record() {
+        let content = r#"{"channelName": "c1", "language": "en", "viewerCount": 1}"#;
+        let (_dir, path) = write_file(content);
+        let chunk = ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: content.len() as u64,
+            size: content.len() as u64,
+        };
+
+        assert_eq!(
+            split_chunk(&path, &chunk, InputFormat::Ndjson).unwrap(),
+            None
+        );
+        assert_eq!(
+            split_chunk(&path, &chunk, InputFormat::JsonArray).unwrap(),
+            None
+        );
+    }
+
+    #[test]
+    fn test_ndjson_skipped_records_have_line_numbers() {
+        let mut lines: Vec<String> = (1..=9)
+            .map(|i| format!(r#"{{"channelName": "c{i}", "language": "en", "viewerCount": {i}}}"#))
+            .collect();
+        lines[2] = r#"{"channelName": "c3", "viewerCount": 3}"#.to_string();
+        lines[4] = String::new();
+        lines[7] = r#"  {"channelName": oops}"#.to_string();
+        let content = lines.join("\n");
+        let (_dir, path) = write_file(&content);
+
+        let chunks = analize_file_for_chunks(&path, 3).unwrap();
+        let mut skipped = Vec::new();
+        for chunk in &chunks {
+            let report =
+                parse_chunk_with_report(&path, chunk, ndjson_options(), |_message| {}).unwrap();
+            skipped.extend(report.skipped_records);
+        }
+
+        assert_eq!(skipped.len(), 2);
+        assert_eq!(skipped[0].line, Some(3));
+        assert_eq!(skipped[0].reason, SkipReason::MissingField);
+        assert_eq!(skipped[1].line, Some(8));
+        assert_eq!(skipped[1].reason, SkipReason::InvalidJson);
+        assert_eq!(
+            skipped[1].offset,
+            content.find(r#"{"channelName": oops}"#).unwrap() as u64
+        );
+    }
+
+    #[test]
+    fn test_ndjson_strict_error_reports_line() {
+        let content = "{\"channelName\": \"c1\", \"language\": \"en\", \"viewerCount\": 1}\n\
+                       {\"channelName\": \"c2\", \"language\": \"en\", \"viewerCount\": \"x\"}\n";
+        let (_dir, path) = write_file(content);
+        let chunk = ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: content.len() as u64,
+            size: content.len() as u64,
+        };
+
+        let options = ParseOptions {
+            strict: true,
+            ..ndjson_options()
+        };
+        match parse_chunk_with_report(&path, &chunk, options, |_message| {}) {
+            Err(CustomError::ParseError(msg)) => assert!(msg.contains("line 2")),
+            other => panic!("Expected a parse error, got {other:?}"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod mmap_tests {
+    use super::*;
+
+    fn write_file(content: &str) -> (TempDir, String) {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("input.json");
+        fs::write(&test_file, content).unwrap();
+        (temp_dir, test_file.to_string_lossy().to_string())
+    }
+
+    fn assert_same_results(path: &str, num_chunks: usize) {
+        let input_format = detect_input_format(path).unwrap();
+        let options = ParseOptions {
+            keep_rejected: true,
+            input_format,
+            ..ParseOptions::default()
+        };
+        let chunks = analize_file_for_chunks_with_format(path, num_chunks, input_format).unwrap();
+        let data = map_file(path).unwrap();
+
+        for chunk in &chunks {
+            let mut buffered_names = Vec::new();
+            let buffered = parse_chunk_with_report(path, chunk, options, |message| {
+                buffered_names.push(message.channel_name)
+            })
+            .unwrap();
+
+            let mut mapped_names = Vec::new();
+            let mapped = parse_chunk_from_slice(&data, chunk, options, |message| {
+                mapped_names.push(message.channel_name.into_owned())
+            })
+            .unwrap();
+
+            assert_eq!(buffered_names, mapped_names);
+            assert_eq!(buffered.processed_count, mapped.processed_count);
+            assert_eq!(buffered.skipped_records, mapped.skipped_records);
+        }
+    }
+
+    #[test]
+    fn test_mmap_matches_buffered_json_array() {
+        let content = r#"[{"channelName": "Стрим", "text": "},{", "language": "ru", "viewerCount": 1},
+{"channelName": "c2", "viewerCount": 2},
+{"channelName": "ゲーム", "language": "ja", "viewerCount": 3},
+{"channelName": "c4", "language": "en", "viewerCount": "x"},
+{"channelName": "c5", "language": "en", "viewerCount": 5}]"#;
+        let (_dir, path) = write_file(content);
+
+        for num_chunks in 1..=4 {
+            assert_same_results(&path, num_chunks);
+        }
+    }
+
+    #[test]
+    fn test_mmap_matches_buffered_ndjson() {
+        let content = "{\"channelName\": \"c1\", \"language\": \"en\", \"viewerCount\": 1}\n\
+                       {\"channelName\": \"c2\", \"viewerCount\": 2}\n\
+                       \n\
+                       {\"channelName\": \"c3\", \"language\": \"es\", \"viewerCount\": 3}\n\
+                       {\"channelName\": oops}\n\
+                       {\"channelName\": \"c5\", \"language\": \"en\", \"viewerCount\": 5}";
+        let (_dir, path) = write_file(content);
+
+        for num_chunks in 1..=4 {
+            assert_same_results(&path, num_chunks);
+        }
+    }
+
+    #[test]
+    fn test_mmap_truncated_record() {
+        let content =
+            r#"{"channelName": "c1", "language": "en", "viewerCount": 1} {"channelName": "c2""#;
+        let (_dir, path) = write_file(content);
+        assert_same_results(&path, 1);
+    }
+
+    #[test]
+    fn test_mmap_empty_file_and_out_of_range_chunk() {
+        let (_dir, path) = write_file("");
+        let data = map_file(&path).unwrap();
+        let chunk = ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: 0,
+            size: 0,
+        };
+        let report =
+            parse_chunk_from_slice(&data, &chunk, ParseOptions::default(), |_message| {}).unwrap();
+        assert_eq!(report.processed_count, 0);
+
+        let chunk = ChunkInfo {
+            file_id: 0,
+            start: 0,
+            end: 10,
+            size: 10,
+        };
+        let result = parse_chunk_from_slice(&data, &chunk, ParseOptions::default(), |_message| {});
+        assert!(matches!(result, Err(CustomError::ParseError(_))));
+    }
+}
@@ -0,0 +1,226 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_report::REPORT_SCHEMA_VERSION;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::transformations::{run_analysis, run_analysis_from_reader};
+use std::fs;
+use std::io::Write;
+use tempfile::{NamedTempFile, TempDir};
+
+#[cfg(test)]
+mod report_tests {
+    use super::*;
+
+    fn create_report_test_data() -> (NamedTempFile, String) {
+        let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        let test_data = r#"{"channelName": "gaming1", "language": "en", "viewerCount": 150}
+{"channelName": "gaming2", "language": "en", "viewerCount": 50}
+{"channelName": "gaming1", "language": "en", "viewerCount": 2000}
+{"channelName": "music1", "language": "es", "viewerCount": 80}"#;
+        temp_file
+            .write_all(test_data.as_bytes())
+            .expect("Failed to write test data");
+        let file_path = temp_file.path().to_str().unwrap().to_string();
+        (temp_file, file_path)
+    }
+
+    #[test]
+    fn test_report_metadata() {
+        let (_temp_file, file_path) = create_report_test_data();
+
+        let report = run_analysis(&file_path, AnalysisType::Both, &AnalysisConfig::new(2)).unwrap();
+
+        assert_eq!(report.schema_version, REPORT_SCHEMA_VERSION);
+        assert_eq!(report.metadata.input_file, file_path);
+        assert_eq!(report.metadata.num_threads, 2);
+        assert_eq!(report.metadata.total_messages, 4);
+        assert!(report.metadata.elapsed_seconds >= 0.0);
+        assert!(report.top_channels_by_language.is_some());
+        assert!(report.top_languages_by_viewer_range.is_some());
+    }
+
+    #[test]
+    fn test_report_only_contains_requested_analysis() {
+        let (_temp_file, file_path) = create_report_test_data();
+
+        let report = run_analysis(
+            &file_path,
+            AnalysisType::TopChannels,
+            &AnalysisConfig::new(1),
+        )
+        .unwrap();
+        assert!(report.top_channels_by_language.is_some());
+        assert!(report.top_languages_by_viewer_range.is_none());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert!(json.get("top_languages_by_viewer_range").is_none());
+        assert_eq!(json["metadata"]["analysis_type"], "top_channels");
+    }
+
+    #[test]
+    fn test_report_json_schema() {
+        let (_temp_file, file_path) = create_report_test_data();
+
+        let report = run_analysis(&file_path, AnalysisType::Both, &AnalysisConfig::new(2)).unwrap();
+        let json = serde_json::to_value(&report).unwrap();
+
+        assert_eq!(json["schema_version"], 1);
+        assert_eq!(json["metadata"]["total_messages"], 4);
+        assert_eq!(json["metadata"]["num_threads"], 2);
+        assert!(json["metadata"]["elapsed_seconds"].is_f64());
+
+        let en = json["top_channels_by_language"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|r| r["language"] == "en")
+            .unwrap();
+        assert_eq!(en["top_channels"][0]["channel_name"], "gaming1");
+        assert_eq!(en["top_channels"][0]["message_count"], 2);
+
+        let ranges = json["top_languages_by_viewer_range"].as_array().unwrap();
+        assert_eq!(ranges[0]["viewer_range"], "0-100");
+        assert_eq!(ranges[0]["lower_bound"], 0);
+        assert_eq!(ranges[0]["upper_bound"], 100);
+        let last = ranges.last().unwrap();
+        assert_eq!(last["viewer_range"], "1000+");
+        assert!(last["upper_bound"].is_null());
+    }
+
+    #[test]
+    fn test_malformed_records_summary_and_reject_file() {
+        let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        let test_data = r#"{"channelName": "gaming1", "language": "en", "viewerCount": 150}
+{"channelName": "gaming2", "viewerCount": 50}
+{"channelName": "gaming1", "language": "en", "viewerCount": true}
+{"channelName": "music1", "language": "es", "viewerCount": 80}
+{"channelName": oops}"#;
+        temp_file
+            .write_all(test_data.as_bytes())
+            .expect("Failed to write test data");
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let temp_dir = TempDir::new().unwrap();
+        let reject_path = temp_dir.path().join("rejects.jsonl");
+        let config = AnalysisConfig::new(2)
+            .with_reject_file(Some(reject_path.to_string_lossy().to_string()));
+
+        let report = run_analysis(file_path, AnalysisType::Both, &config).unwrap();
+        let skipped = &report.metadata.skipped_records;
+        assert_eq!(report.metadata.total_messages, 2);
+        assert_eq!(skipped.total, 3);
+        assert_eq!(skipped.missing_field, 1);
+        assert_eq!(skipped.wrong_type, 1);
+        assert_eq!(skipped.invalid_json, 1);
+
+        let rejects = fs::read_to_string(&reject_path).unwrap();
+        let lines: Vec<serde_json::Value> = rejects
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(lines.len(), 3);
+        assert_eq!(lines[0]["reason"], "missing_field");
+        assert_eq!(
+            lines[0]["record"],
+            r#"{"channelName": "gaming2", "viewerCount": 50}"#
+        );
+        assert_eq!(lines[1]["reason"], "wrong_type");
+        assert_eq!(lines[2]["reason"], "invalid_json");
+        assert!(lines[0]["offset"].as_u64().unwrap() < lines[1]["offset"].as_u64().unwrap());
+
+        let json = serde_json::to_value(&report).unwrap();
+        assert_eq!(json["metadata"]["skipped_records"]["total"], 3);
+    }
+
+    #[test]
+    fn test_negative_viewer_count_is_skipped_as_wrong_type() {
+        let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        let test_data = r#"{"channelName": "gaming1", "language": "en", "viewerCount": 150}
+{"channelName": "gaming2", "language": "en", "viewerCount": -3}"#;
+        temp_file
+            .write_all(test_data.as_bytes())
+            .expect("Failed to write test data");
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let report = run_analysis(file_path, AnalysisType::Both, &AnalysisConfig::new(1)).unwrap();
+        let skipped = &report.metadata.skipped_records;
+        assert_eq!(report.metadata.total_messages, 1);
+        assert_eq!(skipped.total, 1);
+        assert_eq!(skipped.wrong_type, 1);
+
+        let top_languages = report.top_languages_by_viewer_range.unwrap();
+        assert_eq!(top_languages.len(), 1);
+        assert_eq!(top_languages[0].viewer_range, "101-500");
+    }
+
+    #[test]
+    fn test_strict_mode_aborts_run() {
+        let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        let test_data = r#"{"channelName": "gaming1", "language": "en", "viewerCount": 150}
+{"channelName": "gaming2", "viewerCount": 50}
+{"channelName": "music1", "language": "es", "viewerCount": 80}"#;
+        temp_file
+            .write_all(test_data.as_bytes())
+            .expect("Failed to write test data");
+        let file_path = temp_file.path().to_str().unwrap();
+
+        let config = AnalysisConfig::new(2).with_strict(true);
+        let result = run_analysis(file_path, AnalysisType::Both, &config);
+        assert!(matches!(result, Err(CustomError::ParseError(_))));
+
+        let (_temp_file, valid_path) = create_report_test_data();
+        assert!(run_analysis(&valid_path, AnalysisType::Both, &config).is_ok());
+    }
+
+    #[test]
+    fn test_strict_mode_reports_earliest_error() {
+        // The first bad record sits at the end of the first chunk, while every later
+        // chunk fails right away, so the first error found in time is a later one.
+        let mut test_data = String::new();
+        let mut first_bad_offset = None;
+        for i in 0..2000 {
+            if i == 240 {
+                first_bad_offset = Some(test_data.len());
+            }
+            if i == 240 || (i > 250 && i % 250 == 1) {
+                test_data.push_str(r#"{"channelName": "broken", "viewerCount": 1}"#);
+            } else {
+                test_data.push_str(&format!(
+                    r#"{{"channelName": "channel{}", "language": "en", "viewerCount": {i}}}"#,
+                    i % 7
+                ));
+            }
+            test_data.push('\n');
+        }
+        let mut temp_file = NamedTempFile::new().expect("Failed to create temp file");
+        temp_file
+            .write_all(test_data.as_bytes())
+            .expect("Failed to write test data");
+        let file_path = temp_file.path().to_str().unwrap();
+        let expected = format!("byte offset {})", first_bad_offset.unwrap());
+
+        for config in [
+            AnalysisConfig::new(4).with_chunks(8),
+            AnalysisConfig::new(4).with_adaptive_split(Some(1024)),
+        ] {
+            let config = config.with_strict(true);
+            match run_analysis(file_path, AnalysisType::Both, &config) {
+                Err(CustomError::ParseError(message)) => {
+                    assert!(message.contains(&expected), "{message}");
+                }
+                other => panic!("expected a parse error, got {other:?}"),
+            }
+        }
+
+        let config = AnalysisConfig::new(4)
+            .with_stream_batch_size(4096)
+            .with_strict(true);
+        match run_analysis_from_reader(test_data.as_bytes(), AnalysisType::Both, &config) {
+            Err(CustomError::ParseError(message)) => {
+                assert!(message.contains("line 241"), "{message}");
+                assert!(message.contains(&expected), "{message}");
+            }
+            other => panic!("expected a parse error, got {other:?}"),
+        }
+    }
+}
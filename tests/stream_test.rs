@@ -0,0 +1,188 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::input_format::InputFormat;
+use TP0ProgramacionConcurrente::parse_options::ParseOptions;
+use TP0ProgramacionConcurrente::parser::parse_record_batch;
+use TP0ProgramacionConcurrente::record_batch::RecordBatch;
+use TP0ProgramacionConcurrente::record_batch_reader::RecordBatchReader;
+use TP0ProgramacionConcurrente::transformations::{
+    run_analysis, run_analysis_from_reader, run_analysis_on_inputs,
+};
+use std::fs;
+use std::io::Read;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod stream_tests {
+    use super::*;
+
+    /// Reader that returns at most `step` bytes per call, like a pipe.
+    struct TrickleReader<'a> {
+        data: &'a [u8],
+        step: usize,
+    }
+
+    impl Read for TrickleReader<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            let len = self.step.min(buf.len()).min(self.data.len());
+            buf[..len].copy_from_slice(&self.data[..len]);
+            self.data = &self.data[len..];
+            Ok(len)
+        }
+    }
+
+    fn array_dataset(num_messages: usize) -> String {
+        let records: Vec<String> = (0..num_messages)
+            .map(|i| {
+                format!(
+                    r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": {}, "text": "}},{{ [{i}] \"quoted\" }}"}}"#,
+                    i % 7,
+                    ["en", "es", "fr"][i % 3],
+                    (i * 37) % 1500
+                )
+            })
+            .collect();
+        format!("[{}]", records.join(",\n"))
+    }
+
+    fn read_batches(data: &[u8], batch_size: usize) -> Vec<RecordBatch> {
+        RecordBatchReader::new(TrickleReader { data, step: 7 }, None, batch_size)
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_batches_hold_whole_records() {
+        let dataset = array_dataset(50);
+        let batches = read_batches(dataset.as_bytes(), 200);
+
+        assert!(batches.len() > 5);
+        let mut offset = 0;
+        let mut total = 0;
+        for batch in &batches {
+            assert_eq!(batch.input_format, InputFormat::JsonArray);
+            assert_eq!(batch.offset, offset);
+            offset += batch.data.len() as u64;
+
+            let report = parse_record_batch(batch, ParseOptions::default(), |_| {}).unwrap();
+            assert!(report.skipped_records.is_empty());
+            total += report.processed_count;
+        }
+        assert_eq!(total, 50);
+        let joined: Vec<u8> = batches.into_iter().flat_map(|batch| batch.data).collect();
+        assert_eq!(joined, dataset.as_bytes());
+    }
+
+    #[test]
+    fn test_ndjson_batches_report_stream_positions() {
+        let mut lines: Vec<String> = (0..20)
+            .map(|i| format!(r#"{{"channelName": "c{i}", "language": "en", "viewerCount": 1}}"#))
+            .collect();
+        lines[13] = r#"{"channelName": "broken", "language": "en"}"#.to_string();
+        let dataset = lines.join("\n") + "\n";
+        let offset_of_line_14: usize = lines[..13].iter().map(|line| line.len() + 1).sum();
+
+        let batches = read_batches(dataset.as_bytes(), 100);
+        assert!(batches.len() > 5);
+        assert!(
+            batches
+                .iter()
+                .all(|b| b.input_format == InputFormat::Ndjson)
+        );
+
+        let skipped: Vec<_> = batches
+            .iter()
+            .flat_map(|batch| {
+                parse_record_batch(batch, ParseOptions::default(), |_| {})
+                    .unwrap()
+                    .skipped_records
+            })
+            .collect();
+        assert_eq!(skipped.len(), 1);
+        assert_eq!(skipped[0].line, Some(14));
+        assert_eq!(skipped[0].offset, offset_of_line_14 as u64);
+    }
+
+    #[test]
+    fn test_stream_matches_file_analysis() {
+        let temp_dir = TempDir::new().unwrap();
+        let dataset = array_dataset(300);
+        let path = temp_dir.path().join("dataset.json");
+        fs::write(&path, &dataset).unwrap();
+
+        for threads in [1, 2, 4] {
+            let config = AnalysisConfig::new(threads)
+                .with_top_channels(None)
+                .with_top_languages(None)
+                .with_stream_batch_size(512);
+            let expected =
+                run_analysis(path.to_str().unwrap(), AnalysisType::Both, &config).unwrap();
+            let report = run_analysis_from_reader(
+                TrickleReader {
+                    data: dataset.as_bytes(),
+                    step: 4096,
+                },
+                AnalysisType::Both,
+                &config,
+            )
+            .unwrap();
+
+            assert_eq!(report.metadata.input_file, "-");
+            assert_eq!(report.metadata.total_messages, 300);
+            assert!(report.metadata.num_chunks > 1);
+            assert_eq!(
+                report.top_channels_by_language,
+                expected.top_channels_by_language
+            );
+            assert_eq!(
+                report.top_languages_by_viewer_range,
+                expected.top_languages_by_viewer_range
+            );
+        }
+    }
+
+    #[test]
+    fn test_stream_reject_file_and_strict_mode() {
+        let temp_dir = TempDir::new().unwrap();
+        let reject_file = temp_dir.path().join("rejected.jsonl");
+        let dataset = "[{\"channelName\": \"a\", \"language\": \"en\", \"viewerCount\": 1},\n\
+                       {\"channelName\": \"b\", \"language\": \"en\", \"viewerCount\": \"x\"},\n\
+                       {\"channelName\": \"c\", \"language\": \"en\", \"viewerCount\": 2}]";
+        let bad_offset = dataset.find("{\"channelName\": \"b\"").unwrap() as u64;
+
+        let config = AnalysisConfig::new(2)
+            .with_stream_batch_size(1)
+            .with_reject_file(Some(reject_file.to_string_lossy().to_string()));
+        let report =
+            run_analysis_from_reader(dataset.as_bytes(), AnalysisType::Both, &config).unwrap();
+        assert_eq!(report.metadata.total_messages, 2);
+        assert_eq!(report.metadata.skipped_records.wrong_type, 1);
+
+        let line = fs::read_to_string(&reject_file).unwrap();
+        let record: serde_json::Value = serde_json::from_str(line.trim()).unwrap();
+        assert_eq!(record["offset"], bad_offset);
+
+        let strict = AnalysisConfig::new(2)
+            .with_stream_batch_size(1)
+            .with_strict(true);
+        let error =
+            run_analysis_from_reader(dataset.as_bytes(), AnalysisType::Both, &strict).unwrap_err();
+        assert!(matches!(error, CustomError::ParseError(_)));
+        assert!(
+            error
+                .to_string()
+                .contains(&format!("byte offset {bad_offset}"))
+        );
+    }
+
+    #[test]
+    fn test_stdin_cannot_be_combined_with_files() {
+        let inputs = vec!["-".to_string(), "data.json".to_string()];
+        assert!(matches!(
+            run_analysis_on_inputs(&inputs, AnalysisType::Both, &AnalysisConfig::new(1)),
+            Err(CustomError::ArgumentError(_))
+        ));
+    }
+}
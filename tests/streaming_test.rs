@@ -1,9 +1,13 @@
+use TP0ProgramacionConcurrente::streaming_aggregators::StreamingAggregators;
 use TP0ProgramacionConcurrente::transformations::{
     analyze_both, top_channels_by_language, top_languages_by_viewer_range,
 };
+use common::message;
 use std::io::Write;
 use tempfile::NamedTempFile;
 
+mod common;
+
 #[cfg(test)]
 mod streaming_tests {
     use super::*;
@@ -210,4 +214,42 @@ mod streaming_tests {
             }
         }
     }
+
+    #[test]
+    fn test_merged_partials_match_single_aggregator() {
+        let messages = [
+            ("gaming1", "en", 150),
+            ("gaming2", "en", 50),
+            ("gaming1", "en", 2000),
+            ("music1", "es", 700),
+            ("music1", "es", 80),
+        ];
+
+        let mut single = StreamingAggregators::new();
+        let mut left = StreamingAggregators::new();
+        let mut right = StreamingAggregators::new();
+        for (i, (channel, language, viewers)) in messages.iter().enumerate() {
+            single.process_message(message(channel, language, *viewers));
+            if i % 2 == 0 {
+                left.process_message(message(channel, language, *viewers));
+            } else {
+                right.process_message(message(channel, language, *viewers));
+            }
+        }
+
+        left.merge(right);
+
+        assert_eq!(left.total_messages, single.total_messages);
+        assert_eq!(
+            left.language_channel_counts_by_name(),
+            single.language_channel_counts_by_name()
+        );
+        assert_eq!(
+            left.range_language_counts_by_name(),
+            single.range_language_counts_by_name()
+        );
+        assert_eq!(left.channel_count("en", "gaming1"), 2);
+        assert_eq!(left.range_language_count(0, "es"), 1);
+        assert_eq!(left.channel_count("en", "missing"), 0);
+    }
 }
@@ -0,0 +1,219 @@
+use TP0ProgramacionConcurrente::aggregator::Aggregator;
+use TP0ProgramacionConcurrente::aggregator_set::AggregatorSet;
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::chat_record::ChatRecord;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::language_order::LanguageOrder;
+use TP0ProgramacionConcurrente::time_window::TimeWindow;
+use TP0ProgramacionConcurrente::time_window_aggregator::TimeWindowAggregator;
+use TP0ProgramacionConcurrente::timestamp::Timestamp;
+use TP0ProgramacionConcurrente::transformations::{
+    run_aggregators_from_reader, run_analysis, time_series_with_config,
+};
+use common::create_test_file;
+
+mod common;
+
+#[cfg(test)]
+mod time_series_tests {
+    use super::*;
+
+    /// Start of the data set: 2021-11-05T14:00:00Z
+    const START_MILLIS: i64 = 1_636_120_800_000;
+
+    fn create_test_data() -> String {
+        let languages = ["en", "es"];
+        let mut content = String::new();
+        for i in 0..360_i64 {
+            // One message every 30 seconds over three hours
+            content.push_str(&format!(
+                r#"{{"channelName": "channel{}", "language": "{}", "viewerCount": 10, "createAt": {{"$date": {}}}}}"#,
+                (i / 120 + i) % 5,
+                languages[(i % 2) as usize],
+                START_MILLIS + i * 30_000
+            ));
+            content.push('\n');
+        }
+        // Counted by the other analyses, but not placed in any window
+        content.push_str(r#"{"channelName": "undated", "language": "en", "viewerCount": 5}"#);
+        content.push('\n');
+        content
+    }
+
+    fn record(channel_name: &str, created_at: &str) -> ChatRecord {
+        serde_json::from_str(&format!(
+            r#"{{"channelName": "{channel_name}", "language": "en", "viewerCount": 1, "createAt": {{"$date": "{created_at}"}}}}"#
+        ))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_time_window_parse_and_start() {
+        assert_eq!("minute".parse::<TimeWindow>().unwrap(), TimeWindow::Minute);
+        assert_eq!("day".parse::<TimeWindow>().unwrap(), TimeWindow::Day);
+        assert!(matches!(
+            "week".parse::<TimeWindow>(),
+            Err(CustomError::ArgumentError(_))
+        ));
+
+        let timestamp: Timestamp = "2021-11-05T14:59:41.071Z".parse().unwrap();
+        let start = |window: TimeWindow| {
+            Timestamp::from_millis(window.start_millis(&timestamp))
+                .unwrap()
+                .to_string()
+        };
+        assert_eq!(start(TimeWindow::Minute), "2021-11-05T14:59:00.000Z");
+        assert_eq!(start(TimeWindow::Hour), "2021-11-05T14:00:00.000Z");
+        assert_eq!(start(TimeWindow::Day), "2021-11-05T00:00:00.000Z");
+
+        let before_epoch = Timestamp::from_millis(-1).unwrap();
+        assert_eq!(TimeWindow::Hour.start_millis(&before_epoch), -3_600_000);
+    }
+
+    #[test]
+    fn test_windows_are_chronological() {
+        let mut aggregator =
+            TimeWindowAggregator::new(TimeWindow::Hour, Some(1), LanguageOrder::Volume);
+        let mut other = aggregator.clone();
+        aggregator.update_record(&record("late", "2021-11-05T16:10:00Z"));
+        aggregator.update_record(&record("early", "2021-11-05T14:10:00Z"));
+        other.update_record(&record("late", "2021-11-05T16:50:00Z"));
+        other.update_record(&record("other", "2021-11-05T16:20:00Z"));
+        aggregator.merge(other);
+
+        let series = aggregator.finalize();
+        assert_eq!(series.window, TimeWindow::Hour);
+        assert_eq!(series.windows.len(), 2);
+        assert_eq!(
+            series.windows[0].window_start.to_string(),
+            "2021-11-05T14:00:00.000Z"
+        );
+        let last = &series.windows[1];
+        assert_eq!(last.window_end.to_string(), "2021-11-05T17:00:00.000Z");
+        assert_eq!(last.message_count, 3);
+        assert_eq!(last.top_channels_by_language[0].top_channels.len(), 1);
+        assert_eq!(
+            last.top_channels_by_language[0].top_channels[0].channel_name,
+            "late"
+        );
+        assert_eq!(
+            last.top_channels_by_language[0].top_channels[0].message_count,
+            2
+        );
+    }
+
+    #[test]
+    fn test_time_series_from_file_and_stream() {
+        let content = create_test_data();
+        let (_temp_file, file_path) = create_test_file(&content);
+
+        let expected =
+            time_series_with_config(&file_path, TimeWindow::Hour, &AnalysisConfig::new(1)).unwrap();
+        assert_eq!(expected.windows.len(), 3);
+        for window in &expected.windows {
+            assert_eq!(window.message_count, 120);
+            assert_eq!(window.top_channels_by_language.len(), 2);
+            assert!(
+                window
+                    .top_channels_by_language
+                    .iter()
+                    .all(|result| result.top_channels.len() == 3)
+            );
+        }
+        let minutes =
+            time_series_with_config(&file_path, TimeWindow::Minute, &AnalysisConfig::new(1))
+                .unwrap();
+        assert_eq!(minutes.windows.len(), 180);
+
+        for (threads, chunks) in [(2, 3), (4, 9)] {
+            let config = AnalysisConfig::new(threads).with_chunks(chunks);
+            assert_eq!(
+                time_series_with_config(&file_path, TimeWindow::Hour, &config).unwrap(),
+                expected
+            );
+
+            let mut aggregators = AggregatorSet::new();
+            let handle =
+                aggregators.add(TimeWindowAggregator::from_config(TimeWindow::Hour, &config));
+            let mut results =
+                run_aggregators_from_reader(content.as_bytes(), &aggregators, &config).unwrap();
+            assert_eq!(results.take(handle), Some(expected.clone()));
+        }
+    }
+
+    #[test]
+    fn test_time_series_analysis_report() {
+        let (_temp_file, file_path) = create_test_file(&create_test_data());
+        let config = AnalysisConfig::new(2)
+            .with_top_channels(None)
+            .with_time_window(Some(TimeWindow::Day));
+
+        let report = run_analysis(&file_path, AnalysisType::TimeSeries, &config).unwrap();
+        assert!(report.top_channels_by_language.is_none());
+        assert!(report.group_by.is_none());
+        assert_eq!(report.metadata.total_messages, 361);
+        let time_series = report.time_series.unwrap();
+        assert_eq!(time_series.windows.len(), 1);
+        assert_eq!(time_series.windows[0].message_count, 360);
+        assert_eq!(
+            time_series.windows[0].top_channels_by_language[0]
+                .top_channels
+                .len(),
+            5
+        );
+
+        let result = run_analysis(
+            &file_path,
+            AnalysisType::TimeSeries,
+            &AnalysisConfig::new(2),
+        );
+        assert!(matches!(result, Err(CustomError::ArgumentError(_))));
+    }
+
+    #[test]
+    fn test_malformed_optional_fields_match_core_analyses() {
+        let mut content = create_test_data();
+        // Usable by every analysis: only fields that no analysis requires are malformed
+        content.push_str(
+            r#"{"channelName": "flags", "language": "en", "viewerCount": 5, "isSubscriber": "yes", "_id": {"$oid": "6185"}, "createAt": {"$date": "2021-11-05T14:10:00Z"}}"#,
+        );
+        content.push('\n');
+        content.push_str(
+            r#"{"channelName": "bad_date", "language": "en", "viewerCount": 5, "createAt": "not a date"}"#,
+        );
+        content.push('\n');
+        // Skipped by every analysis
+        content
+            .push_str(r#"{"channelName": "bad_count", "language": "en", "viewerCount": "many"}"#);
+        content.push('\n');
+        let (_temp_file, file_path) = create_test_file(&content);
+
+        let core = run_analysis(
+            &file_path,
+            AnalysisType::TopChannels,
+            &AnalysisConfig::new(2),
+        )
+        .unwrap();
+        let config = AnalysisConfig::new(2).with_time_window(Some(TimeWindow::Hour));
+        let report = run_analysis(&file_path, AnalysisType::TimeSeries, &config).unwrap();
+        assert_eq!(report.metadata.total_messages, 363);
+        assert_eq!(report.metadata.total_messages, core.metadata.total_messages);
+        assert_eq!(
+            report.metadata.skipped_records,
+            core.metadata.skipped_records
+        );
+        assert_eq!(report.metadata.skipped_records.total, 1);
+
+        // The record with a bad date is counted but not placed in any window
+        let time_series = report.time_series.unwrap();
+        assert_eq!(
+            time_series
+                .windows
+                .iter()
+                .map(|window| window.message_count)
+                .sum::<u64>(),
+            361
+        );
+    }
+}
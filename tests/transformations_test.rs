@@ -1,5 +1,8 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
 use TP0ProgramacionConcurrente::transformations::{
-    analyze_both, top_channels_by_language, top_languages_by_viewer_range,
+    analyze_both, analyze_both_with_config, build_thread_pool, top_channels_by_language,
+    top_channels_by_language_with_config, top_languages_by_viewer_range,
+    top_languages_by_viewer_range_with_config,
 };
 use std::fs;
 use tempfile::TempDir;
@@ -158,6 +161,7 @@ mod transformations_tests {
     }
 
     #[test]
+    #[allow(clippy::len_zero)]
     fn test_analyze_both_function() {
         let (_temp_dir, test_file) = create_test_file_for_top_channels_by_language();
         let result = analyze_both(&test_file, 2);
@@ -241,4 +245,117 @@ mod transformations_tests {
             }
         }
     }
+
+    #[test]
+    fn test_thread_pool_has_requested_workers() {
+        for num_threads in [1, 2, 3] {
+            let pool = build_thread_pool(&AnalysisConfig::new(num_threads)).unwrap();
+            assert_eq!(pool.current_num_threads(), num_threads);
+        }
+    }
+
+    #[test]
+    fn test_zero_threads_or_chunks_is_rejected() {
+        let (_temp_dir, test_file) = create_test_file_for_top_channels_by_language();
+
+        assert!(analyze_both_with_config(&test_file, &AnalysisConfig::new(0)).is_err());
+        assert!(
+            analyze_both_with_config(&test_file, &AnalysisConfig::new(2).with_chunks(0)).is_err()
+        );
+    }
+
+    #[test]
+    fn test_chunk_count_independent_of_thread_count() {
+        let (_temp_dir, test_file) = create_test_file_for_top_channels_by_language();
+
+        let (channels_few, ranges_few) =
+            analyze_both_with_config(&test_file, &AnalysisConfig::new(2)).unwrap();
+        let (channels_many, ranges_many) =
+            analyze_both_with_config(&test_file, &AnalysisConfig::new(2).with_chunks(6)).unwrap();
+
+        assert_eq!(channels_few.len(), channels_many.len());
+        assert_eq!(ranges_few.len(), ranges_many.len());
+
+        let en_few = channels_few.iter().find(|r| r.language == "en").unwrap();
+        let en_many = channels_many.iter().find(|r| r.language == "en").unwrap();
+        assert_eq!(
+            en_few.top_channels[0].message_count,
+            en_many.top_channels[0].message_count
+        );
+    }
+
+    #[test]
+    fn test_top_channels_custom_limit() {
+        let (_temp_dir, test_file) = create_test_file_for_top_channels_by_language();
+
+        let config = AnalysisConfig::new(2).with_top_channels(Some(1));
+        let results = top_channels_by_language_with_config(&test_file, &config).unwrap();
+        for language_result in &results {
+            assert_eq!(language_result.top_channels.len(), 1);
+        }
+
+        let config = AnalysisConfig::new(2).with_top_channels(Some(10));
+        let results = top_channels_by_language_with_config(&test_file, &config).unwrap();
+        let en_result = results.iter().find(|r| r.language == "en").unwrap();
+        assert_eq!(en_result.top_channels.len(), 4);
+    }
+
+    #[test]
+    fn test_full_ranking_without_limit() {
+        let (_temp_dir, test_file) = create_test_file_for_viewer_ranges();
+
+        let config = AnalysisConfig::new(2)
+            .with_top_channels(None)
+            .with_top_languages(None);
+        let (top_channels, top_languages) = analyze_both_with_config(&test_file, &config).unwrap();
+
+        let en_result = top_channels.iter().find(|r| r.language == "en").unwrap();
+        assert_eq!(en_result.top_channels.len(), 5);
+
+        let total_languages: usize = top_languages.iter().map(|r| r.top_languages.len()).sum();
+        assert_eq!(total_languages, 9);
+
+        let limited =
+            top_languages_by_viewer_range_with_config(&test_file, &AnalysisConfig::new(2)).unwrap();
+        assert_eq!(limited.len(), top_languages.len());
+    }
+
+    #[test]
+    fn test_non_ascii_channel_names_are_counted_exactly() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("utf8_test_data.json");
+
+        let content = r#"{"channelName": "Стрим_Игры", "language": "ru", "viewerCount": 100}
+{"channelName": "ゲーム実況", "language": "ja", "viewerCount": 150}
+{"channelName": "Стрим_Игры", "language": "ru", "viewerCount": 200}
+{"channelName": "Музыка", "language": "ru", "viewerCount": 250}
+{"channelName": "ゲーム実況", "language": "ja", "viewerCount": 300}
+{"channelName": "Стрим_Игры", "language": "ru", "viewerCount": 350}"#;
+        fs::write(&test_file, content).unwrap();
+
+        let results = top_channels_by_language(&test_file.to_string_lossy(), 3).unwrap();
+
+        let ru = results.iter().find(|r| r.language == "ru").unwrap();
+        assert_eq!(ru.top_channels[0].channel_name, "Стрим_Игры");
+        assert_eq!(ru.top_channels[0].message_count, 3);
+        assert_eq!(ru.top_channels[1].channel_name, "Музыка");
+
+        let ja = results.iter().find(|r| r.language == "ja").unwrap();
+        assert_eq!(ja.top_channels[0].channel_name, "ゲーム実況");
+        assert_eq!(ja.top_channels[0].message_count, 2);
+    }
+
+    #[test]
+    fn test_mmap_matches_buffered_reads() {
+        let (_temp_dir, test_file) = create_test_file_for_top_channels_by_language();
+
+        for num_threads in [1, 2, 4] {
+            let buffered = AnalysisConfig::new(num_threads);
+            let mapped = AnalysisConfig::new(num_threads).with_mmap(true);
+            assert_eq!(
+                analyze_both_with_config(&test_file, &buffered).unwrap(),
+                analyze_both_with_config(&test_file, &mapped).unwrap()
+            );
+        }
+    }
 }
@@ -0,0 +1,99 @@
+use TP0ProgramacionConcurrente::analysis_config::AnalysisConfig;
+use TP0ProgramacionConcurrente::custom_error::CustomError;
+use TP0ProgramacionConcurrente::transformations::top_languages_by_viewer_range_with_config;
+use TP0ProgramacionConcurrente::viewer_buckets::ViewerBuckets;
+use std::fs;
+use tempfile::TempDir;
+
+#[cfg(test)]
+mod viewer_buckets_tests {
+    use super::*;
+
+    #[test]
+    fn test_default_buckets_match_original_ranges() {
+        let buckets = ViewerBuckets::default();
+        let labels: Vec<&str> = buckets.ranges().iter().map(|r| r.label.as_str()).collect();
+        assert_eq!(labels, vec!["0-100", "101-500", "501-1000", "1000+"]);
+
+        assert_eq!(buckets.range_index(0), Some(0));
+        assert_eq!(buckets.range_index(100), Some(0));
+        assert_eq!(buckets.range_index(101), Some(1));
+        assert_eq!(buckets.range_index(1000), Some(2));
+        assert_eq!(buckets.range_index(1001), Some(3));
+        assert_eq!(buckets.range_index(u64::MAX), Some(3));
+    }
+
+    #[test]
+    fn test_custom_buckets_from_spec() {
+        let buckets = ViewerBuckets::parse("0,50,1000,10000,100000").unwrap();
+        let ranges = buckets.ranges();
+
+        assert_eq!(ranges.len(), 5);
+        assert_eq!(ranges[0].label, "0-50");
+        assert_eq!(ranges[1].label, "51-1000");
+        assert_eq!(ranges[1].lower_bound, 51);
+        assert_eq!(ranges[1].upper_bound, Some(1000));
+        assert_eq!(ranges[4].label, "100000+");
+        assert_eq!(ranges[4].lower_bound, 100_001);
+        assert_eq!(ranges[4].upper_bound, None);
+    }
+
+    #[test]
+    fn test_counts_below_first_boundary_have_no_range() {
+        let buckets = ViewerBuckets::parse("10,20").unwrap();
+        assert_eq!(buckets.range_index(9), None);
+        assert_eq!(buckets.range_index(10), Some(0));
+        assert_eq!(buckets.range_index(21), Some(1));
+    }
+
+    #[test]
+    fn test_invalid_specs() {
+        assert!(ViewerBuckets::parse("").is_err());
+        assert!(ViewerBuckets::parse("100").is_err());
+        assert!(ViewerBuckets::parse("0,100,50").is_err());
+        assert!(ViewerBuckets::parse("0,abc").is_err());
+        assert!(ViewerBuckets::parse("-1,10").is_err());
+    }
+
+    #[test]
+    fn test_max_boundary_is_rejected() {
+        let max = u64::MAX.to_string();
+        for spec in [format!("0,{max}"), format!("0,100,{max}")] {
+            assert!(matches!(
+                ViewerBuckets::parse(&spec),
+                Err(CustomError::ArgumentError(_))
+            ));
+        }
+
+        let buckets = ViewerBuckets::parse(&format!("0,{}", u64::MAX - 1)).unwrap();
+        assert_eq!(buckets.ranges()[1].lower_bound, u64::MAX);
+        assert_eq!(buckets.range_index(u64::MAX), Some(1));
+    }
+
+    #[test]
+    fn test_top_languages_with_custom_buckets() {
+        let temp_dir = TempDir::new().unwrap();
+        let test_file = temp_dir.path().join("buckets.json");
+        let content = r#"{"channelName": "a", "language": "en", "viewerCount": 10}
+{"channelName": "b", "language": "es", "viewerCount": 40}
+{"channelName": "c", "language": "en", "viewerCount": 60}
+{"channelName": "d", "language": "fr", "viewerCount": 20000}"#;
+        fs::write(&test_file, content).unwrap();
+
+        let config = AnalysisConfig::new(2)
+            .with_viewer_buckets(ViewerBuckets::parse("0,50,1000,10000").unwrap());
+        let results =
+            top_languages_by_viewer_range_with_config(&test_file.to_string_lossy(), &config)
+                .unwrap();
+
+        let labels: Vec<&str> = results.iter().map(|r| r.viewer_range.as_str()).collect();
+        assert_eq!(labels, vec!["0-50", "51-1000", "10000+"]);
+
+        assert_eq!(results[0].lower_bound, 0);
+        assert_eq!(results[0].upper_bound, Some(50));
+        assert_eq!(results[0].top_languages.len(), 2);
+        assert_eq!(results[2].lower_bound, 10_001);
+        assert_eq!(results[2].upper_bound, None);
+        assert_eq!(results[2].top_languages[0].language, "fr");
+    }
+}
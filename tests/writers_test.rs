@@ -0,0 +1,283 @@
+use TP0ProgramacionConcurrente::analysis_report::{AnalysisReport, REPORT_SCHEMA_VERSION};
+use TP0ProgramacionConcurrente::analysis_type::AnalysisType;
+use TP0ProgramacionConcurrente::channel_message_count::ChannelMessageCount;
+use TP0ProgramacionConcurrente::chunk_timings::ChunkTimings;
+use TP0ProgramacionConcurrente::group_by_result::GroupByResult;
+use TP0ProgramacionConcurrente::group_node::GroupNode;
+use TP0ProgramacionConcurrente::language_message_count::LanguageMessageCount;
+use TP0ProgramacionConcurrente::output_format::OutputFormat;
+use TP0ProgramacionConcurrente::report_writer::writer_for;
+use TP0ProgramacionConcurrente::run_metadata::RunMetadata;
+use TP0ProgramacionConcurrente::skipped_records_summary::SkippedRecordsSummary;
+use TP0ProgramacionConcurrente::time_series_result::TimeSeriesResult;
+use TP0ProgramacionConcurrente::time_window::TimeWindow;
+use TP0ProgramacionConcurrente::time_window_result::TimeWindowResult;
+use TP0ProgramacionConcurrente::top_channels_result::TopChannelsByLanguageResult;
+use TP0ProgramacionConcurrente::top_languages_result::TopLanguagesByViewerRangeResult;
+use TP0ProgramacionConcurrente::value_count::ValueCount;
+
+#[cfg(test)]
+mod writers_tests {
+    use super::*;
+
+    fn sample_report() -> AnalysisReport {
+        AnalysisReport {
+            schema_version: REPORT_SCHEMA_VERSION,
+            metadata: RunMetadata {
+                input_file: "data.json".to_string(),
+                input_files: vec!["data.json".to_string()],
+                analysis_type: AnalysisType::Both,
+                filter: None,
+                num_threads: 2,
+                num_chunks: 2,
+                chunk_timings: ChunkTimings::from_durations(&[0.1, 0.3]),
+                top_channels_limit: Some(3),
+                top_languages_limit: None,
+                total_messages: 6,
+                matched_messages: 6,
+                skipped_records: SkippedRecordsSummary::default(),
+                elapsed_seconds: 0.5,
+            },
+            top_channels_by_language: Some(vec![TopChannelsByLanguageResult {
+                language: "en".to_string(),
+                top_channels: vec![
+                    ChannelMessageCount {
+                        channel_name: "gaming1".to_string(),
+                        message_count: 4,
+                    },
+                    ChannelMessageCount {
+                        channel_name: "odd,name".to_string(),
+                        message_count: 2,
+                    },
+                ],
+            }]),
+            top_languages_by_viewer_range: Some(vec![TopLanguagesByViewerRangeResult {
+                viewer_range: "0-100".to_string(),
+                lower_bound: 0,
+                upper_bound: Some(100),
+                top_languages: vec![LanguageMessageCount {
+                    language: "en".to_string(),
+                    message_count: 6,
+                }],
+            }]),
+            group_by: None,
+            time_series: None,
+        }
+    }
+
+    fn group_by_report() -> AnalysisReport {
+        let leaf = |value: &str, top: &[(&str, u64)]| GroupNode {
+            value: value.to_string(),
+            message_count: top.iter().map(|(_, count)| count).sum(),
+            groups: Vec::new(),
+            top: top
+                .iter()
+                .map(|(value, message_count)| ValueCount {
+                    value: value.to_string(),
+                    message_count: *message_count,
+                })
+                .collect(),
+        };
+
+        let mut report = sample_report();
+        report.metadata.analysis_type = AnalysisType::GroupBy;
+        report.top_channels_by_language = None;
+        report.top_languages_by_viewer_range = None;
+        report.group_by = Some(GroupByResult {
+            group_by: vec!["language".to_string(), "categoryName".to_string()],
+            top_by: "channelName".to_string(),
+            limit: Some(2),
+            groups: vec![GroupNode {
+                value: "en".to_string(),
+                message_count: 6,
+                groups: vec![
+                    leaf("Just Chatting", &[("gaming1", 3), ("gaming2", 1)]),
+                    leaf("Chess, Go", &[("board", 2)]),
+                ],
+                top: Vec::new(),
+            }],
+        });
+        report
+    }
+
+    fn time_series_report() -> AnalysisReport {
+        let window = |start: &str, end: &str, channels: &[(&str, &str, u64)]| TimeWindowResult {
+            window_start: start.parse().unwrap(),
+            window_end: end.parse().unwrap(),
+            message_count: channels.iter().map(|(_, _, count)| count).sum(),
+            top_channels_by_language: channels
+                .iter()
+                .map(|(language, channel, count)| TopChannelsByLanguageResult {
+                    language: language.to_string(),
+                    top_channels: vec![ChannelMessageCount {
+                        channel_name: channel.to_string(),
+                        message_count: *count,
+                    }],
+                })
+                .collect(),
+        };
+
+        let mut report = sample_report();
+        report.metadata.analysis_type = AnalysisType::TimeSeries;
+        report.top_channels_by_language = None;
+        report.top_languages_by_viewer_range = None;
+        report.time_series = Some(TimeSeriesResult {
+            window: TimeWindow::Hour,
+            windows: vec![
+                window(
+                    "2021-11-05T14:00:00Z",
+                    "2021-11-05T15:00:00Z",
+                    &[("en", "gaming1", 3), ("es", "juegos", 1)],
+                ),
+                window(
+                    "2021-11-05T15:00:00Z",
+                    "2021-11-05T16:00:00Z",
+                    &[("en", "odd,name", 2)],
+                ),
+            ],
+        });
+        report
+    }
+
+    fn render(format: OutputFormat, report: &AnalysisReport) -> String {
+        let mut out = Vec::new();
+        writer_for(format).write_report(report, &mut out).unwrap();
+        String::from_utf8(out).unwrap()
+    }
+
+    #[test]
+    fn test_csv_output() {
+        let output = render(OutputFormat::Csv, &sample_report());
+
+        assert_eq!(
+            output,
+            "language,rank,channel,count\n\
+             en,1,gaming1,4\n\
+             en,2,\"odd,name\",2\n\
+             \n\
+             viewer_range,rank,language,count\n\
+             0-100,1,en,6\n"
+        );
+    }
+
+    #[test]
+    fn test_csv_output_single_ranking() {
+        let mut report = sample_report();
+        report.top_channels_by_language = None;
+
+        let output = render(OutputFormat::Csv, &report);
+        assert_eq!(output, "viewer_range,rank,language,count\n0-100,1,en,6\n");
+    }
+
+    #[test]
+    fn test_markdown_output() {
+        let output = render(OutputFormat::Markdown, &sample_report());
+
+        assert!(output.starts_with("# Twitch Chat Analysis Report\n"));
+        assert!(output.contains("| Total messages | 6 |"));
+        assert!(output.contains("| Malformed records | 0 skipped"));
+        assert!(output.contains(
+            "| Chunk timings | 2 chunks (min: 0.100s, mean: 0.200s, max: 0.300s, imbalance: 1.50x) |"
+        ));
+        assert!(output.contains("## Top 3 channels by language"));
+        assert!(output.contains("## All languages by viewer range"));
+        assert!(output.contains("### 0-100 viewers"));
+        assert!(output.contains("| 1 | gaming1 | 4 |"));
+    }
+
+    #[test]
+    fn test_text_output() {
+        let output = render(OutputFormat::Text, &sample_report());
+
+        assert!(output.contains("=== TOP 3 CHANNELS BY LANGUAGE ==="));
+        assert!(output.contains("=== ALL LANGUAGES BY VIEWER RANGE ==="));
+        assert!(output.contains("  1. gaming1: 4 messages"));
+        assert!(output.contains("Viewer Range: 0-100"));
+    }
+
+    #[test]
+    fn test_group_by_output() {
+        let report = group_by_report();
+
+        assert_eq!(
+            render(OutputFormat::Csv, &report),
+            "language,categoryName,rank,channelName,count\n\
+             en,Just Chatting,1,gaming1,3\n\
+             en,Just Chatting,2,gaming2,1\n\
+             en,\"Chess, Go\",1,board,2\n"
+        );
+
+        let text = render(OutputFormat::Text, &report);
+        assert!(text.contains("=== TOP 2 channelName BY language, categoryName ==="));
+        assert!(text.contains("language: en (6 messages)\n  categoryName: Just Chatting (4 messages)\n    1. gaming1: 3 messages\n"));
+
+        let markdown = render(OutputFormat::Markdown, &report);
+        assert!(markdown.contains("## Top 2 channelName by language, categoryName"));
+        assert!(markdown.contains("### en / Chess, Go"));
+        assert!(markdown.contains("| Rank | channelName | Messages |"));
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json { pretty: false }, &report)).unwrap();
+        assert_eq!(json["metadata"]["analysis_type"], "group_by");
+        assert_eq!(
+            json["group_by"]["groups"][0]["groups"][1]["top"][0]["value"],
+            "board"
+        );
+        assert!(json["group_by"]["groups"][0].get("top").is_none());
+    }
+
+    #[test]
+    fn test_time_series_output() {
+        let report = time_series_report();
+
+        assert_eq!(
+            render(OutputFormat::Csv, &report),
+            "window_start,window_end,language,rank,channel,count\n\
+             2021-11-05T14:00:00.000Z,2021-11-05T15:00:00.000Z,en,1,gaming1,3\n\
+             2021-11-05T14:00:00.000Z,2021-11-05T15:00:00.000Z,es,1,juegos,1\n\
+             2021-11-05T15:00:00.000Z,2021-11-05T16:00:00.000Z,en,1,\"odd,name\",2\n"
+        );
+
+        let text = render(OutputFormat::Text, &report);
+        assert!(text.contains("=== TOP 3 CHANNELS BY LANGUAGE PER HOUR ==="));
+        assert!(text.contains(
+            "[2021-11-05T14:00:00.000Z - 2021-11-05T15:00:00.000Z] (4 messages)\n  Language: en\n    1. gaming1: 3 messages\n"
+        ));
+
+        let markdown = render(OutputFormat::Markdown, &report);
+        assert!(markdown.contains("## Top 3 channels by language per hour"));
+        assert!(
+            markdown
+                .contains("### 2021-11-05T15:00:00.000Z to 2021-11-05T16:00:00.000Z (2 messages)")
+        );
+
+        let json: serde_json::Value =
+            serde_json::from_str(&render(OutputFormat::Json { pretty: false }, &report)).unwrap();
+        assert_eq!(json["metadata"]["analysis_type"], "time_series");
+        assert_eq!(json["time_series"]["window"], "hour");
+        assert_eq!(
+            json["time_series"]["windows"][1]["window_start"],
+            "2021-11-05T15:00:00.000Z"
+        );
+        assert_eq!(
+            json["time_series"]["windows"][0]["top_channels_by_language"][1]["top_channels"][0]["channel_name"],
+            "juegos"
+        );
+        assert!(json.get("group_by").is_none());
+    }
+
+    #[test]
+    fn test_json_output_compact_and_pretty() {
+        let report = sample_report();
+
+        let compact = render(OutputFormat::Json { pretty: false }, &report);
+        let pretty = render(OutputFormat::Json { pretty: true }, &report);
+
+        assert_eq!(compact.lines().count(), 1);
+        assert!(pretty.lines().count() > 1);
+
+        let compact_value: serde_json::Value = serde_json::from_str(&compact).unwrap();
+        let pretty_value: serde_json::Value = serde_json::from_str(&pretty).unwrap();
+        assert_eq!(compact_value, pretty_value);
+    }
+}